@@ -0,0 +1,91 @@
+from datetime import datetime
+from os import PathLike
+from typing import Any, Callable, Iterator, Optional, Sequence
+
+class ParseError(Exception): ...
+class JsonError(ParseError): ...
+class FormatError(ParseError): ...
+class IoError(ParseError): ...
+class CancelledError(ParseError): ...
+
+class CancellationToken:
+    def __init__(self) -> None: ...
+    def cancel(self) -> None: ...
+    @property
+    def is_cancelled(self) -> bool: ...
+
+class Message:
+    @property
+    def conversation(self) -> str: ...
+    @property
+    def sender_name(self) -> str: ...
+    @property
+    def timestamp_ms(self) -> int: ...
+    @property
+    def timestamp(self) -> datetime: ...
+    @property
+    def content(self) -> Optional[str]: ...
+    @property
+    def is_unsent(self) -> bool: ...
+    @property
+    def reactions(self) -> list[dict[str, Any]]: ...
+    def to_dict(self) -> dict[str, Any]: ...
+
+class MessageIter(Iterator[Message]):
+    def __iter__(self) -> MessageIter: ...
+    def __next__(self) -> Message: ...
+
+class Conversation:
+    @property
+    def id(self) -> str: ...
+    @property
+    def title(self) -> str: ...
+    @property
+    def participants(self) -> list[str]: ...
+    @property
+    def messages(self) -> list[Message]: ...
+    def to_dict(self) -> dict[str, Any]: ...
+
+class Messages:
+    @property
+    def conversations(self) -> dict[str, list[Conversation]]: ...
+    def __iter__(self) -> MessageIter: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, pretty: bool = False) -> str: ...
+
+class Activity:
+    @property
+    def messages(self) -> Optional[Messages]: ...
+    @property
+    def comments(self) -> Optional[dict[str, Any]]: ...
+    @property
+    def likes(self) -> Optional[dict[str, Any]]: ...
+    @property
+    def saved(self) -> Optional[dict[str, Any]]: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, pretty: bool = False) -> str: ...
+
+class Connections:
+    @property
+    def contacts(self) -> Optional[dict[str, Any]]: ...
+    @property
+    def followers_and_following(self) -> Optional[dict[str, Any]]: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, pretty: bool = False) -> str: ...
+
+class InstagramArchive:
+    @property
+    def connections(self) -> Optional[Connections]: ...
+    @property
+    def activity(self) -> Optional[Activity]: ...
+    def messages(self) -> MessageIter: ...
+    def save_to_file(self, path: str | PathLike[str]) -> None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, pretty: bool = False) -> str: ...
+
+def load_instagram(
+    path: str | PathLike[str],
+    sections: Optional[Sequence[str]] = None,
+    progress: Optional[Callable[[dict[str, Any]], None]] = None,
+    cancellation: Optional[CancellationToken] = None,
+) -> InstagramArchive: ...
@@ -0,0 +1,80 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use pyo3::prelude::*;
+
+/// Forwards `log` records to Python's `logging` module. Records from
+/// `social_parser::platforms::meta` go to the `social_parser.platforms.meta` logger.
+///
+/// Records below the effective level of the `social_parser` logger are dropped before
+/// taking the GIL. The level is read by [`refresh_level`], so levels set on child loggers
+/// only lower it when set on `social_parser` as well.
+struct PyLogger;
+
+static LOGGER: PyLogger = PyLogger;
+
+impl Log for PyLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        Python::with_gil(|py| {
+            if let Err(err) = forward(py, record) {
+                err.write_unraisable(py, None);
+            }
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+fn forward(py: Python<'_>, record: &Record) -> PyResult<()> {
+    let logger = py
+        .import("logging")?
+        .call_method1("getLogger", (record.target().replace("::", "."),))?;
+    let level = match record.level() {
+        Level::Error => 40,
+        Level::Warn => 30,
+        Level::Info => 20,
+        Level::Debug => 10,
+        Level::Trace => 5,
+    };
+
+    if logger
+        .call_method1("isEnabledFor", (level,))?
+        .extract::<bool>()?
+    {
+        logger.call_method1("log", (level, "%s", record.args().to_string()))?;
+    }
+    Ok(())
+}
+
+/// Install the logger, enabling levels from the current `logging` configuration
+pub(super) fn init(py: Python<'_>) {
+    if log::set_logger(&LOGGER).is_ok() {
+        refresh_level(py);
+    }
+}
+
+/// Set the most verbose level forwarded from the effective level of the `social_parser`
+/// logger, or `INFO` if it can't be read. Called before loading, so `logging` can be
+/// configured after importing the module.
+pub(super) fn refresh_level(py: Python<'_>) {
+    let level = py
+        .import("logging")
+        .and_then(|logging| logging.call_method1("getLogger", ("social_parser",)))
+        .and_then(|logger| logger.call_method0("getEffectiveLevel"))
+        .and_then(|level| level.extract::<u32>());
+
+    log::set_max_level(match level {
+        Ok(0..=5) => LevelFilter::Trace,
+        Ok(6..=10) => LevelFilter::Debug,
+        Ok(11..=20) => LevelFilter::Info,
+        Ok(21..=30) => LevelFilter::Warn,
+        Ok(31..=40) => LevelFilter::Error,
+        Ok(_) => LevelFilter::Off,
+        Err(_) => LevelFilter::Info,
+    });
+}
@@ -0,0 +1,586 @@
+#[cfg(feature = "python")]
+pub use python::*;
+
+#[cfg(feature = "python")]
+mod logger;
+
+#[cfg(feature = "python")]
+#[allow(clippy::module_inception)]
+mod python {
+    use std::{collections::HashMap, path::PathBuf, sync::Arc, time::SystemTime, vec};
+
+    use pyo3::{
+        create_exception,
+        exceptions::{PyException, PyValueError},
+        prelude::*,
+    };
+    use pythonize::pythonize;
+    use serde::Serialize;
+
+    use super::logger;
+    use crate::{
+        common::{self, Archive, LoadOptions, Progress, ProgressEvent, Sections},
+        platforms::meta::instagram::{
+            activity::{
+                messages::{Inbox, InboxData, Message, Messages},
+                Activity,
+            },
+            connections::Connections,
+            InstagramArchive,
+        },
+    };
+
+    create_exception!(
+        social_parser,
+        ParseError,
+        PyException,
+        "Raised when an archive cannot be parsed."
+    );
+    create_exception!(
+        social_parser,
+        JsonError,
+        ParseError,
+        "Raised when a file in the archive does not match the expected JSON schema."
+    );
+    create_exception!(
+        social_parser,
+        FormatError,
+        ParseError,
+        "Raised when the archive layout is not what was expected."
+    );
+    create_exception!(
+        social_parser,
+        IoError,
+        ParseError,
+        "Raised when the archive could not be read."
+    );
+    create_exception!(
+        social_parser,
+        CancelledError,
+        ParseError,
+        "Raised when loading was cancelled through a CancellationToken."
+    );
+
+    impl From<common::ParseError> for PyErr {
+        fn from(err: common::ParseError) -> PyErr {
+            let message = err.to_string();
+            match err {
+                common::ParseError::Serde(..) => JsonError::new_err(message),
+                common::ParseError::Csv(..) => FormatError::new_err(message),
+                common::ParseError::Zip(..) => FormatError::new_err(message),
+                common::ParseError::Io(_) => IoError::new_err(message),
+                common::ParseError::UnexpectedFormat(_) => FormatError::new_err(message),
+                common::ParseError::Cancelled => CancelledError::new_err(message),
+            }
+        }
+    }
+
+    /// Initialize the `social_parser` Python module
+    pub fn init_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        let py = m.py();
+
+        logger::init(py);
+
+        m.add_function(wrap_pyfunction!(load_instagram, m)?)?;
+
+        m.add_class::<PyCancellationToken>()?;
+        m.add_class::<PyInstagramArchive>()?;
+        m.add_class::<PyConnections>()?;
+        m.add_class::<PyActivity>()?;
+        m.add_class::<PyMessages>()?;
+        m.add_class::<PyConversation>()?;
+        m.add_class::<PyMessage>()?;
+        m.add_class::<PyMessageIter>()?;
+
+        m.add("ParseError", py.get_type::<ParseError>())?;
+        m.add("JsonError", py.get_type::<JsonError>())?;
+        m.add("FormatError", py.get_type::<FormatError>())?;
+        m.add("IoError", py.get_type::<IoError>())?;
+        m.add("CancelledError", py.get_type::<CancelledError>())?;
+
+        Ok(())
+    }
+
+    fn to_dict<'py, T: Serialize>(py: Python<'py>, value: &T) -> PyResult<Bound<'py, PyAny>> {
+        Ok(pythonize(py, value)?)
+    }
+
+    fn to_json<T: Serialize>(value: &T, pretty: bool) -> PyResult<String> {
+        let json = if pretty {
+            serde_json::to_string_pretty(value)
+        } else {
+            serde_json::to_string(value)
+        };
+        json.map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    fn parse_sections(names: &[String]) -> PyResult<Sections> {
+        names.iter().try_fold(Sections::empty(), |sections, name| {
+            Sections::from_name(&name.to_uppercase())
+                .map(|section| sections | section)
+                .ok_or_else(|| PyValueError::new_err(format!("Unknown section: {name}")))
+        })
+    }
+
+    /// Forwards progress events to a Python callable as dicts
+    struct PyProgress(PyObject);
+
+    impl Progress for PyProgress {
+        fn on_event(&self, event: &ProgressEvent) {
+            Python::with_gil(|py| {
+                let result = to_dict(py, event).and_then(|event| self.0.call1(py, (event,)));
+                if let Err(err) = result {
+                    err.write_unraisable(py, Some(self.0.bind(py)));
+                }
+            });
+        }
+    }
+
+    /// Load an Instagram archive from a directory.
+    ///
+    /// `sections` limits loading to the given section names (e.g. `["connections", "messages"]`),
+    /// `progress` is called with a dict for every progress event and `cancellation` stops
+    /// loading with `CancelledError` once cancelled.
+    #[pyfunction]
+    #[pyo3(signature = (path, sections = None, progress = None, cancellation = None))]
+    fn load_instagram(
+        py: Python<'_>,
+        path: PathBuf,
+        sections: Option<Vec<String>>,
+        progress: Option<PyObject>,
+        cancellation: Option<PyRef<'_, PyCancellationToken>>,
+    ) -> PyResult<PyInstagramArchive> {
+        logger::refresh_level(py);
+
+        let mut options = LoadOptions::new();
+        if let Some(sections) = sections {
+            options = options.with_sections(parse_sections(&sections)?);
+        }
+        if let Some(progress) = progress {
+            options = options.with_progress(PyProgress(progress));
+        }
+        if let Some(cancellation) = cancellation {
+            options = options.with_cancellation(cancellation.0.clone());
+        }
+
+        let archive = py.allow_threads(|| InstagramArchive::load(&path, &options))?;
+        Ok(PyInstagramArchive(Arc::new(archive)))
+    }
+
+    /// Token used to cancel an in-progress load from another thread
+    #[pyclass(name = "CancellationToken", module = "social_parser", frozen)]
+    struct PyCancellationToken(common::CancellationToken);
+
+    #[pymethods]
+    impl PyCancellationToken {
+        #[new]
+        fn new() -> Self {
+            Self(common::CancellationToken::new())
+        }
+
+        fn cancel(&self) {
+            self.0.cancel();
+        }
+
+        #[getter]
+        fn is_cancelled(&self) -> bool {
+            self.0.is_cancelled()
+        }
+    }
+
+    /// Archive shared by every object taken from it. Attributes and iterators hold a
+    /// reference to it and look up their part when accessed, so nothing is copied.
+    #[pyclass(name = "InstagramArchive", module = "social_parser", frozen)]
+    struct PyInstagramArchive(Arc<InstagramArchive>);
+
+    #[pymethods]
+    impl PyInstagramArchive {
+        #[getter]
+        fn connections(&self) -> Option<PyConnections> {
+            self.0
+                .connections
+                .is_some()
+                .then(|| PyConnections(self.0.clone()))
+        }
+
+        #[getter]
+        fn activity(&self) -> Option<PyActivity> {
+            self.0
+                .activity
+                .is_some()
+                .then(|| PyActivity(self.0.clone()))
+        }
+
+        /// Iterate over every message in every conversation
+        fn messages(&self) -> PyMessageIter {
+            PyMessageIter::new(self.0.clone())
+        }
+
+        fn save_to_file(&self, path: PathBuf) -> PyResult<()> {
+            self.0
+                .save_to_file(path)
+                .map_err(|e| IoError::new_err(e.to_string()))
+        }
+
+        fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+            to_dict(py, &*self.0)
+        }
+
+        #[pyo3(signature = (pretty = false))]
+        fn to_json(&self, pretty: bool) -> PyResult<String> {
+            to_json(&*self.0, pretty)
+        }
+
+        fn __repr__(&self) -> String {
+            format!(
+                "InstagramArchive(connections={}, activity={})",
+                self.0.connections.is_some(),
+                self.0.activity.is_some()
+            )
+        }
+    }
+
+    /// Inbox of an archive, if messages were loaded
+    fn inbox(archive: &InstagramArchive) -> Option<&Inbox> {
+        archive.activity.as_ref()?.messages.as_ref()?.inbox.as_ref()
+    }
+
+    /// Connections of an archive that has them
+    #[pyclass(name = "Connections", module = "social_parser", frozen)]
+    struct PyConnections(Arc<InstagramArchive>);
+
+    impl PyConnections {
+        fn get(&self) -> &Connections {
+            self.0
+                .connections
+                .as_ref()
+                .expect("archive has connections")
+        }
+    }
+
+    #[pymethods]
+    impl PyConnections {
+        #[getter]
+        fn contacts<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+            self.get()
+                .contacts
+                .as_ref()
+                .map(|contacts| to_dict(py, contacts))
+                .transpose()
+        }
+
+        #[getter]
+        fn followers_and_following<'py>(
+            &self,
+            py: Python<'py>,
+        ) -> PyResult<Option<Bound<'py, PyAny>>> {
+            self.get()
+                .followers_n_following
+                .as_ref()
+                .map(|fnf| to_dict(py, fnf))
+                .transpose()
+        }
+
+        fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+            to_dict(py, self.get())
+        }
+
+        #[pyo3(signature = (pretty = false))]
+        fn to_json(&self, pretty: bool) -> PyResult<String> {
+            to_json(self.get(), pretty)
+        }
+    }
+
+    /// Activity of an archive that has it
+    #[pyclass(name = "Activity", module = "social_parser", frozen)]
+    struct PyActivity(Arc<InstagramArchive>);
+
+    impl PyActivity {
+        fn get(&self) -> &Activity {
+            self.0.activity.as_ref().expect("archive has activity")
+        }
+    }
+
+    #[pymethods]
+    impl PyActivity {
+        #[getter]
+        fn messages(&self) -> Option<PyMessages> {
+            self.get()
+                .messages
+                .is_some()
+                .then(|| PyMessages(self.0.clone()))
+        }
+
+        #[getter]
+        fn comments<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+            self.get()
+                .comments
+                .as_ref()
+                .map(|comments| to_dict(py, comments))
+                .transpose()
+        }
+
+        #[getter]
+        fn likes<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+            self.get()
+                .likes
+                .as_ref()
+                .map(|likes| to_dict(py, likes))
+                .transpose()
+        }
+
+        #[getter]
+        fn saved<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+            self.get()
+                .saved
+                .as_ref()
+                .map(|saved| to_dict(py, saved))
+                .transpose()
+        }
+
+        fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+            to_dict(py, self.get())
+        }
+
+        #[pyo3(signature = (pretty = false))]
+        fn to_json(&self, pretty: bool) -> PyResult<String> {
+            to_json(self.get(), pretty)
+        }
+    }
+
+    /// Messages of an archive that has them
+    #[pyclass(name = "Messages", module = "social_parser", frozen)]
+    struct PyMessages(Arc<InstagramArchive>);
+
+    impl PyMessages {
+        fn get(&self) -> &Messages {
+            self.0
+                .activity
+                .as_ref()
+                .and_then(|activity| activity.messages.as_ref())
+                .expect("archive has messages")
+        }
+    }
+
+    #[pymethods]
+    impl PyMessages {
+        /// Conversations keyed by inbox ID
+        #[getter]
+        fn conversations(&self) -> HashMap<String, Vec<PyConversation>> {
+            inbox(&self.0)
+                .iter()
+                .flat_map(|inbox| inbox.0.iter())
+                .map(|(id, data)| {
+                    let conversations = (0..data.len())
+                        .map(|index| PyConversation {
+                            archive: self.0.clone(),
+                            id: id.clone(),
+                            index,
+                        })
+                        .collect();
+                    (id.clone(), conversations)
+                })
+                .collect()
+        }
+
+        fn __iter__(&self) -> PyMessageIter {
+            PyMessageIter::new(self.0.clone())
+        }
+
+        fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+            to_dict(py, self.get())
+        }
+
+        #[pyo3(signature = (pretty = false))]
+        fn to_json(&self, pretty: bool) -> PyResult<String> {
+            to_json(self.get(), pretty)
+        }
+    }
+
+    /// Conversation at `index` of the inbox entry `id`. Conversations split over several
+    /// `message_N.json` files have one entry per file.
+    #[pyclass(name = "Conversation", module = "social_parser", frozen)]
+    struct PyConversation {
+        archive: Arc<InstagramArchive>,
+        id: String,
+        index: usize,
+    }
+
+    impl PyConversation {
+        fn get(&self) -> &InboxData {
+            conversation(&self.archive, &self.id, self.index)
+        }
+    }
+
+    #[pymethods]
+    impl PyConversation {
+        #[getter]
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        #[getter]
+        fn title(&self) -> &str {
+            &self.get().title
+        }
+
+        #[getter]
+        fn participants(&self) -> Vec<String> {
+            self.get()
+                .participants
+                .iter()
+                .map(|p| p.name.clone())
+                .collect()
+        }
+
+        #[getter]
+        fn messages(&self) -> Vec<PyMessage> {
+            (0..self.get().messages.len())
+                .map(|position| PyMessage {
+                    archive: self.archive.clone(),
+                    conversation: self.id.clone(),
+                    index: self.index,
+                    position,
+                })
+                .collect()
+        }
+
+        fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+            to_dict(py, self.get())
+        }
+
+        fn __repr__(&self) -> String {
+            format!(
+                "Conversation(id={:?}, title={:?})",
+                self.id,
+                self.get().title
+            )
+        }
+    }
+
+    /// Conversation a Python object was created for, which is always in the archive
+    fn conversation<'a>(archive: &'a InstagramArchive, id: &str, index: usize) -> &'a InboxData {
+        &inbox(archive).expect("archive has messages").0[id][index]
+    }
+
+    /// Message at `position` in a conversation
+    #[pyclass(name = "Message", module = "social_parser", frozen)]
+    struct PyMessage {
+        archive: Arc<InstagramArchive>,
+        conversation: String,
+        index: usize,
+        position: usize,
+    }
+
+    impl PyMessage {
+        fn get(&self) -> &Message {
+            &conversation(&self.archive, &self.conversation, self.index).messages[self.position]
+        }
+    }
+
+    #[pymethods]
+    impl PyMessage {
+        /// Inbox ID of the conversation this message belongs to
+        #[getter]
+        fn conversation(&self) -> &str {
+            &self.conversation
+        }
+
+        #[getter]
+        fn sender_name(&self) -> &str {
+            &self.get().sender_name
+        }
+
+        #[getter]
+        fn timestamp_ms(&self) -> i64 {
+            self.get().timestamp_ms.value()
+        }
+
+        /// Time the message was sent as a UTC `datetime`
+        #[getter]
+        fn timestamp(&self) -> SystemTime {
+            self.get().timestamp_ms.to_system_time()
+        }
+
+        #[getter]
+        fn content(&self) -> Option<&str> {
+            self.get().content.as_deref()
+        }
+
+        #[getter]
+        fn is_unsent(&self) -> bool {
+            self.get().is_unsent == Some(true)
+        }
+
+        #[getter]
+        fn reactions<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+            to_dict(py, &self.get().reactions.as_deref().unwrap_or_default())
+        }
+
+        fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+            to_dict(py, self.get())
+        }
+
+        fn __repr__(&self) -> String {
+            format!(
+                "Message(sender_name={:?}, timestamp_ms={})",
+                self.get().sender_name,
+                self.get().timestamp_ms.value()
+            )
+        }
+    }
+
+    /// Iterator over the messages of every conversation, creating each message object as it
+    /// is reached
+    #[pyclass(name = "MessageIter", module = "social_parser")]
+    struct PyMessageIter {
+        archive: Arc<InstagramArchive>,
+        /// Inbox ID and index of the conversations left to visit
+        conversations: vec::IntoIter<(String, usize)>,
+        /// Conversation being visited and the position of its next message
+        current: Option<(String, usize, usize)>,
+    }
+
+    impl PyMessageIter {
+        fn new(archive: Arc<InstagramArchive>) -> Self {
+            let conversations: Vec<(String, usize)> = inbox(&archive)
+                .into_iter()
+                .flat_map(|inbox| inbox.0.iter())
+                .flat_map(|(id, data)| (0..data.len()).map(move |index| (id.clone(), index)))
+                .collect();
+            let mut conversations = conversations.into_iter();
+            let current = conversations.next().map(|(id, index)| (id, index, 0));
+            Self {
+                archive,
+                conversations,
+                current,
+            }
+        }
+    }
+
+    #[pymethods]
+    impl PyMessageIter {
+        fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+            slf
+        }
+
+        fn __next__(mut slf: PyRefMut<'_, Self>) -> Option<PyMessage> {
+            let slf = &mut *slf;
+            loop {
+                let (id, index, position) = slf.current.as_mut()?;
+                let len = conversation(&slf.archive, id, *index).messages.len();
+                if *position < len {
+                    let message = PyMessage {
+                        archive: slf.archive.clone(),
+                        conversation: id.clone(),
+                        index: *index,
+                        position: *position,
+                    };
+                    *position += 1;
+                    return Some(message);
+                }
+                slf.current = slf.conversations.next().map(|(id, index)| (id, index, 0));
+            }
+        }
+    }
+}
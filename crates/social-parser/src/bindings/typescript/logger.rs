@@ -0,0 +1,111 @@
+use std::{
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+
+use log::{LevelFilter, Log, Metadata, Record};
+use neon::prelude::*;
+
+/// Forwards `log` records to a JS callback registered with `setLogger`
+struct JsLogger {
+    sink: RwLock<Option<JsSink>>,
+}
+
+struct JsSink {
+    channel: Channel,
+    callback: Arc<Root<JsFunction>>,
+}
+
+static LOGGER: JsLogger = JsLogger {
+    sink: RwLock::new(None),
+};
+
+impl Log for JsLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        self.sink.read().map(|sink| sink.is_some()).unwrap_or(false)
+    }
+
+    fn log(&self, record: &Record) {
+        let Ok(sink) = self.sink.read() else {
+            return;
+        };
+        let Some(sink) = sink.as_ref() else {
+            return;
+        };
+
+        let level = record.level().as_str().to_lowercase();
+        let target = record.target().to_owned();
+        let message = record.args().to_string();
+        let callback = sink.callback.clone();
+
+        sink.channel.send(move |mut cx| {
+            let callback = callback.to_inner(&mut cx);
+            let record = cx.empty_object();
+            let level = cx.string(level);
+            record.set(&mut cx, "level", level)?;
+            let target = cx.string(target);
+            record.set(&mut cx, "target", target)?;
+            let message = cx.string(message);
+            record.set(&mut cx, "message", message)?;
+            callback.call_with(&cx).arg(record).exec(&mut cx)
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+/// Initialize context for logger
+pub(super) fn init_context(cx: &mut ModuleContext) -> NeonResult<()> {
+    cx.export_function("setLogger", set_logger)?;
+
+    Ok(())
+}
+
+/// `setLogger(callback, level?)`: forward log records at or above `level` (default "info")
+/// to `callback`. Passing `null` stops forwarding.
+fn set_logger(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let callback = match cx.argument_opt(0) {
+        Some(arg) if !arg.is_a::<JsNull, _>(&mut cx) && !arg.is_a::<JsUndefined, _>(&mut cx) => {
+            Some(arg.downcast_or_throw::<JsFunction, _>(&mut cx)?)
+        }
+        _ => None,
+    };
+    let level = match cx.argument_opt(1) {
+        Some(arg) if !arg.is_a::<JsUndefined, _>(&mut cx) => {
+            let level = arg
+                .downcast_or_throw::<JsString, _>(&mut cx)?
+                .value(&mut cx);
+            match LevelFilter::from_str(&level) {
+                Ok(level) => level,
+                Err(_) => return cx.throw_type_error(format!("Unknown log level: {level}")),
+            }
+        }
+        _ => LevelFilter::Info,
+    };
+
+    // Only the first call can install the logger, later calls just swap the sink
+    let _ = log::set_logger(&LOGGER);
+
+    let sink = match callback {
+        Some(callback) => {
+            let mut channel = cx.channel();
+            // Don't keep the process alive just to deliver log records
+            channel.unref(&mut cx);
+            Some(JsSink {
+                channel,
+                callback: Arc::new(callback.root(&mut cx)),
+            })
+        }
+        None => None,
+    };
+    log::set_max_level(if sink.is_some() {
+        level
+    } else {
+        LevelFilter::Off
+    });
+    if let Ok(mut current) = LOGGER.sink.write() {
+        *current = sink;
+    }
+
+    Ok(cx.undefined())
+}
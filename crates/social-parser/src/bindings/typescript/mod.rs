@@ -1,48 +1,130 @@
 #[cfg(feature = "typescript")]
 pub use typescript::*;
 
-use crate::platforms;
-
-// use log::{info, Log, Metadata, Record};
-
-// struct JsLogger {
-//     cx: Arc<Mutex<ModuleContext>>,
-// }
-
-// unsafe impl<'a> Send for JsLogger<'a> {}
-// unsafe impl<'a> Sync for JsLogger<'a> {}
-
-// impl Log for JsLogger<'_> {
-//     fn enabled(&self, _metadata: &Metadata) -> bool {
-//         true
-//     }
-
-//     fn log(&self, record: &Record) {
-//         // if self.enabled(record.metadata()) {
-//         //     if let Ok(mut cx) = self.cx.lock() {
-//         //         // let f = &mut **cx;
-//         //         let js_log: Handle<JsFunction> = cx
-//         //             .string("console.log")
-//         //             .downcast_or_throw(&mut **cx)
-//         //             .unwrap();
-//         //         js_log.call(&mut **cx, cx.undefined(), vec![]).unwrap();
-//         //     }
-//         // }
-//     }
-
-//     fn flush(&self) {}
-// }
-
+#[cfg(feature = "typescript")]
+mod logger;
 
 #[cfg(feature = "typescript")]
+#[allow(clippy::module_inception)]
 mod typescript {
+    use std::sync::Arc;
+
     use neon::prelude::*;
+    use serde::Serialize;
+
+    use super::logger;
+    use crate::{
+        common::{CancellationToken, LoadOptions, ParseError, Progress, ProgressEvent, Sections},
+        platforms,
+    };
+
+    impl Finalize for CancellationToken {}
 
     /// Initialize context for root module
     pub fn init_context(cx: &mut ModuleContext) -> NeonResult<()> {
-        // TODO: Implement logger
+        logger::init_context(cx)?;
+        cx.export_function("createCancellationToken", create_cancellation_token)?;
+        cx.export_function("cancel", cancel)?;
         platforms::init_context(cx)?;
 
         Ok(())
     }
+
+    fn create_cancellation_token(mut cx: FunctionContext) -> JsResult<JsBox<CancellationToken>> {
+        Ok(cx.boxed(CancellationToken::new()))
+    }
+
+    fn cancel(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+        cx.argument::<JsBox<CancellationToken>>(0)?.cancel();
+        Ok(cx.undefined())
+    }
+
+    /// Convert a serializable value into a plain JS value
+    pub(crate) fn to_js<'a, C: Context<'a>, T: Serialize>(
+        cx: &mut C,
+        value: &T,
+    ) -> JsResult<'a, JsValue> {
+        let json = match serde_json::to_string(value) {
+            Ok(json) => cx.string(json),
+            Err(e) => return cx.throw_error(e.to_string()),
+        };
+        let parse: Handle<JsFunction> = cx.global::<JsObject>("JSON")?.get(cx, "parse")?;
+        parse.call_with(cx).arg(json).apply(cx)
+    }
+
+    /// Throw a parse error as a JS `Error` with a `code` property
+    pub(crate) fn throw_parse_error<'a, C: Context<'a>, T>(
+        cx: &mut C,
+        err: ParseError,
+    ) -> NeonResult<T> {
+        let code = match err {
+            ParseError::Serde(..) => "ERR_JSON",
+            ParseError::Csv(..) => "ERR_CSV",
+            ParseError::Zip(..) => "ERR_ZIP",
+            ParseError::Io(_) => "ERR_IO",
+            ParseError::UnexpectedFormat(_) => "ERR_FORMAT",
+            ParseError::Cancelled => "ERR_CANCELLED",
+        };
+        let error = cx.error(err.to_string())?;
+        let code = cx.string(code);
+        error.set(cx, "code", code)?;
+        cx.throw(error)
+    }
+
+    /// Forwards progress events to a JS callback on the main thread
+    struct JsProgress {
+        channel: Channel,
+        callback: Arc<Root<JsFunction>>,
+    }
+
+    impl Progress for JsProgress {
+        fn on_event(&self, event: &ProgressEvent) {
+            let event = event.clone();
+            let callback = self.callback.clone();
+            self.channel.send(move |mut cx| {
+                let callback = callback.to_inner(&mut cx);
+                let event = to_js(&mut cx, &event)?;
+                callback.call_with(&cx).arg(event).exec(&mut cx)
+            });
+        }
+    }
+
+    /// Read `{ sections, onProgress, cancellation }` from the argument at `index`.
+    /// Progress events are delivered asynchronously on the JS main thread.
+    pub(crate) fn load_options(cx: &mut FunctionContext, index: usize) -> NeonResult<LoadOptions> {
+        let mut options = LoadOptions::new();
+
+        let Some(arg) = cx.argument_opt(index) else {
+            return Ok(options);
+        };
+        if arg.is_a::<JsUndefined, _>(cx) || arg.is_a::<JsNull, _>(cx) {
+            return Ok(options);
+        }
+        let arg = arg.downcast_or_throw::<JsObject, _>(cx)?;
+
+        if let Some(sections) = arg.get_opt::<JsArray, _, _>(cx, "sections")? {
+            let mut selected = Sections::empty();
+            for name in sections.to_vec(cx)? {
+                let name = name.downcast_or_throw::<JsString, _>(cx)?.value(cx);
+                match Sections::from_name(&name.to_uppercase()) {
+                    Some(section) => selected |= section,
+                    None => return cx.throw_type_error(format!("Unknown section: {name}")),
+                }
+            }
+            options = options.with_sections(selected);
+        }
+
+        if let Some(callback) = arg.get_opt::<JsFunction, _, _>(cx, "onProgress")? {
+            options = options.with_progress(JsProgress {
+                channel: cx.channel(),
+                callback: Arc::new(callback.root(cx)),
+            });
+        }
+
+        if let Some(token) = arg.get_opt::<JsBox<CancellationToken>, _, _>(cx, "cancellation")? {
+            options = options.with_cancellation((**token).clone());
+        }
+
+        Ok(options)
+    }
 }
@@ -0,0 +1,155 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use super::{
+    csv::CsvOptions, jsonl::JsonlWriter, Conversation, Dataset, LoadOptions, ParseError, WriteError,
+};
+
+/// Data export downloaded from a platform
+pub trait Archive: Serialize + Sized {
+    /// Load from a directory or bundle, only parsing the sections selected in `options`
+    fn load<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError>;
+
+    /// Records that map to the common models, for writing out in a platform-independent
+    /// layout
+    fn to_dataset(&self) -> Dataset;
+
+    /// Save to file as pretty-printed JSON
+    fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), WriteError> {
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(file, self)?;
+        Ok(())
+    }
+
+    /// Save to file as JSON lines, one record of [`to_dataset`](Archive::to_dataset) per
+    /// line, each tagged with `source`, the path the archive was loaded from. See
+    /// [`jsonl`](super::jsonl) for the layout, and
+    /// [`StreamingArchive::stream_to_jsonl`] for exports too large to load at once.
+    fn save_to_jsonl<P: AsRef<Path>>(&self, path: P, source: &Path) -> Result<(), WriteError> {
+        let mut writer = JsonlWriter::new(BufWriter::new(File::create(path)?));
+        writer.write_dataset(&self.to_dataset(), source)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Write each collection to a CSV file in the directory `dir`. See [`csv`](super::csv)
+    /// for the files and columns.
+    fn save_to_csv<P: AsRef<Path>>(&self, dir: P, options: &CsvOptions) -> Result<(), WriteError> {
+        super::csv::write(&self.to_dataset(), dir.as_ref(), options)
+    }
+
+    /// Add to the SQLite database at `path`, creating it if needed. See
+    /// [`sqlite`](super::sqlite) for the schema.
+    #[cfg(feature = "sqlite")]
+    fn save_to_sqlite<P: AsRef<Path>>(&self, path: P) -> Result<(), WriteError> {
+        super::sqlite::write(&self.to_dataset(), path.as_ref())?;
+        Ok(())
+    }
+
+    /// Write each collection to `<collection>.parquet` in the directory `dir`. See
+    /// [`arrow`](super::arrow) for the columns.
+    #[cfg(feature = "arrow")]
+    fn save_to_parquet<P: AsRef<Path>>(&self, dir: P) -> Result<(), WriteError> {
+        super::arrow::write_parquet(&self.to_dataset(), dir.as_ref())
+    }
+}
+
+/// Callback passed the conversations in each file, for loaders that can stream them
+pub(crate) type OnConversation<'a> = dyn FnMut(&Path, Conversation) -> Result<(), ParseError> + 'a;
+
+/// Archive whose conversations can be passed on file by file as they are parsed, for
+/// exports too large to load at once
+pub trait StreamingArchive: Archive {
+    /// Name of the platform records are tagged with, as in [`Dataset::platform`]
+    const PLATFORM: &'static str;
+
+    /// Load like [`Archive::load`], but pass the conversations in each file to
+    /// `on_conversation` along with the path of the file instead of keeping their
+    /// messages. A conversation split over several files is passed once per file, with the
+    /// same id. The returned archive has everything else. An error from `on_conversation`
+    /// stops loading and is returned.
+    fn stream_conversations<P, F>(
+        path: P,
+        options: &LoadOptions,
+        on_conversation: F,
+    ) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&Path, Conversation) -> Result<(), ParseError>;
+
+    /// Path that records outside of the streamed conversations are tagged with. Defaults to
+    /// `path` itself, for archives spread over many files.
+    fn dataset_source(path: &Path) -> PathBuf {
+        path.to_owned()
+    }
+
+    /// Load from `path`, writing each message to `writer` as its file is parsed, tagged with
+    /// that file. Conversations are written after the last message, once all their
+    /// participants are known, tagged with the first file they were found in. The rest of
+    /// [`to_dataset`](Archive::to_dataset) follows, tagged with
+    /// [`dataset_source`](Self::dataset_source). Returns the archive
+    /// without messages, like [`stream_conversations`](Self::stream_conversations).
+    fn stream_to_jsonl<P, W>(
+        path: P,
+        options: &LoadOptions,
+        writer: &mut JsonlWriter<W>,
+    ) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+        W: Write,
+    {
+        let path = path.as_ref();
+        let source = Self::dataset_source(path);
+        // Conversations without their messages, in the order they were found
+        let mut conversations: Vec<(PathBuf, Conversation)> = vec![];
+        let mut index: HashMap<String, usize> = HashMap::new();
+
+        let archive = Self::stream_conversations(path, options, |source, mut conversation| {
+            writer.write_messages(Self::PLATFORM, source, &conversation)?;
+            conversation.messages = vec![];
+            match index.get(&conversation.id) {
+                Some(&i) => merge(&mut conversations[i].1, conversation),
+                None => {
+                    index.insert(conversation.id.clone(), conversations.len());
+                    conversations.push((source.to_owned(), conversation));
+                }
+            }
+            Ok(())
+        })?;
+
+        // The archive still has conversations that had no messages to stream
+        let mut dataset = archive.to_dataset();
+        for conversation in std::mem::take(&mut dataset.conversations) {
+            match index.get(&conversation.id) {
+                Some(&i) => merge(&mut conversations[i].1, conversation),
+                None => conversations.push((source.clone(), conversation)),
+            }
+        }
+
+        for (source, conversation) in &conversations {
+            writer.write_conversation(Self::PLATFORM, source, conversation)?;
+        }
+        writer.write_dataset(&dataset, &source)?;
+
+        Ok(archive)
+    }
+}
+
+/// Add the title and participants of another part of the same conversation
+fn merge(conversation: &mut Conversation, part: Conversation) {
+    if conversation.title.is_none() {
+        conversation.title = part.title;
+    }
+    for name in part.participants {
+        if !conversation.participants.contains(&name) {
+            conversation.participants.push(name);
+        }
+    }
+    conversation.messages.extend(part.messages);
+}
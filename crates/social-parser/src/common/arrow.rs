@@ -0,0 +1,502 @@
+//! Converting datasets to Arrow record batches, one per collection, and writing them as
+//! Parquet files for tools like DuckDB or Polars.
+//!
+//! Every batch starts with a `platform` column so files from several archives can be
+//! queried together. Timestamps are `Timestamp(Millisecond, "UTC")`, and reactions,
+//! attachments, poll options and participants are list columns.
+
+use std::{fs, fs::File, path::Path, sync::Arc};
+
+use arrow::{
+    array::{
+        ArrayRef, BooleanArray, ListArray, RecordBatch, StringArray, StructArray,
+        TimestampMillisecondArray, UInt64Array,
+    },
+    buffer::OffsetBuffer,
+    datatypes::{DataType, Field, Fields, TimeUnit},
+    error::ArrowError,
+};
+use parquet::{arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties};
+
+use super::{
+    ArchiveTimestamp, Attachment, ConversationMessage, Dataset, MessageKind, MessageReaction,
+    Milliseconds, PollOption, ReplyTo, WriteError,
+};
+
+/// Every collection of `dataset` as a record batch, keyed by collection name. Empty
+/// collections are included as empty batches.
+pub fn record_batches(dataset: &Dataset) -> Result<Vec<(&'static str, RecordBatch)>, ArrowError> {
+    Ok(vec![
+        ("conversations", conversations(dataset)?),
+        ("messages", messages(dataset)?),
+        ("posts", posts(dataset)?),
+        ("comments", comments(dataset)?),
+        ("follows", follows(dataset)?),
+        ("likes", likes(dataset)?),
+        ("saved_items", saved_items(dataset)?),
+        ("logins", logins(dataset)?),
+    ])
+}
+
+/// Write every collection of `dataset` to `<collection>.parquet` in `dir`, creating the
+/// directory if needed and replacing existing files. Files are Snappy compressed.
+pub fn write_parquet(dataset: &Dataset, dir: &Path) -> Result<(), WriteError> {
+    fs::create_dir_all(dir)?;
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+
+    for (name, batch) in record_batches(dataset)? {
+        let file = File::create(dir.join(format!("{}.parquet", name)))?;
+        let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(properties.clone()))?;
+        writer.write(&batch)?;
+        writer.close()?;
+    }
+
+    Ok(())
+}
+
+/// One row per conversation, with its participants as a list
+pub fn conversations(dataset: &Dataset) -> Result<RecordBatch, ArrowError> {
+    let rows = &dataset.conversations;
+    let participants: Vec<&[String]> = rows.iter().map(|c| c.participants.as_slice()).collect();
+
+    RecordBatch::try_from_iter_with_nullable([
+        ("platform", platform(dataset, rows.len()), false),
+        (
+            "id",
+            strings(rows.iter().map(|c| Some(c.id.as_str()))),
+            false,
+        ),
+        (
+            "title",
+            strings(rows.iter().map(|c| c.title.as_deref())),
+            true,
+        ),
+        ("participants", string_list(&participants)?, false),
+    ])
+}
+
+/// One row per message, with the id and title of its conversation. Fields specific to a
+/// kind of message are null for the others.
+pub fn messages(dataset: &Dataset) -> Result<RecordBatch, ArrowError> {
+    let rows: Vec<_> = dataset
+        .conversations
+        .iter()
+        .flat_map(|c| c.messages.iter().map(move |m| (c, m)))
+        .collect();
+
+    let poll_options: Vec<&[PollOption]> = rows
+        .iter()
+        .map(|(_, m)| match &m.kind {
+            MessageKind::Poll(poll) => poll.options.as_slice(),
+            _ => &[],
+        })
+        .collect();
+    let reactions: Vec<&[MessageReaction]> =
+        rows.iter().map(|(_, m)| m.reactions.as_slice()).collect();
+    let attachments: Vec<&[Attachment]> =
+        rows.iter().map(|(_, m)| m.attachments.as_slice()).collect();
+    let reply_to = |field: fn(&ReplyTo) -> Option<&str>| {
+        strings(
+            rows.iter()
+                .map(|(_, m)| m.reply_to.as_ref().and_then(field)),
+        )
+    };
+
+    RecordBatch::try_from_iter_with_nullable([
+        ("platform", platform(dataset, rows.len()), false),
+        (
+            "conversation_id",
+            strings(rows.iter().map(|(c, _)| Some(c.id.as_str()))),
+            false,
+        ),
+        (
+            "conversation_title",
+            strings(rows.iter().map(|(c, _)| c.title.as_deref())),
+            true,
+        ),
+        (
+            "id",
+            strings(rows.iter().map(|(_, m)| m.id.as_deref())),
+            true,
+        ),
+        (
+            "sender",
+            strings(rows.iter().map(|(_, m)| Some(m.sender.as_str()))),
+            false,
+        ),
+        (
+            "timestamp",
+            timestamps(rows.iter().map(|(_, m)| m.timestamp)),
+            true,
+        ),
+        (
+            "content",
+            strings(rows.iter().map(|(_, m)| m.content.as_deref())),
+            true,
+        ),
+        (
+            "kind",
+            strings(rows.iter().map(|(_, m)| Some(m.kind.as_str()))),
+            false,
+        ),
+        (
+            "share_link",
+            strings(
+                rows.iter()
+                    .map(|(_, m)| share(m).and_then(|(link, _)| link)),
+            ),
+            true,
+        ),
+        (
+            "share_text",
+            strings(
+                rows.iter()
+                    .map(|(_, m)| share(m).and_then(|(_, text)| text)),
+            ),
+            true,
+        ),
+        (
+            "call_duration_secs",
+            Arc::new(UInt64Array::from_iter(
+                rows.iter()
+                    .map(|(_, m)| call(m).and_then(|(duration, _)| duration)),
+            )),
+            true,
+        ),
+        (
+            "call_missed",
+            Arc::new(BooleanArray::from_iter(
+                rows.iter().map(|(_, m)| call(m).map(|(_, missed)| missed)),
+            )),
+            true,
+        ),
+        (
+            "poll_question",
+            strings(rows.iter().map(|(_, m)| match &m.kind {
+                MessageKind::Poll(poll) => Some(poll.question.as_str()),
+                _ => None,
+            })),
+            true,
+        ),
+        ("poll_options", poll_option_list(&poll_options)?, false),
+        ("reply_to_id", reply_to(|r| r.message_id.as_deref()), true),
+        ("reply_to_sender", reply_to(|r| r.sender.as_deref()), true),
+        ("reply_to_content", reply_to(|r| r.content.as_deref()), true),
+        (
+            "is_unsent",
+            Arc::new(BooleanArray::from_iter(
+                rows.iter().map(|(_, m)| Some(m.is_unsent)),
+            )),
+            false,
+        ),
+        ("reactions", reaction_list(&reactions)?, false),
+        ("attachments", attachment_list(&attachments)?, false),
+    ])
+}
+
+pub fn posts(dataset: &Dataset) -> Result<RecordBatch, ArrowError> {
+    let rows = &dataset.posts;
+    let attachments: Vec<&[Attachment]> = rows.iter().map(|p| p.attachments.as_slice()).collect();
+
+    RecordBatch::try_from_iter_with_nullable([
+        ("platform", platform(dataset, rows.len()), false),
+        ("id", strings(rows.iter().map(|p| p.id.as_deref())), true),
+        (
+            "author",
+            strings(rows.iter().map(|p| p.author.as_deref())),
+            true,
+        ),
+        (
+            "timestamp",
+            timestamps(rows.iter().map(|p| p.timestamp)),
+            true,
+        ),
+        (
+            "title",
+            strings(rows.iter().map(|p| p.title.as_deref())),
+            true,
+        ),
+        (
+            "text",
+            strings(rows.iter().map(|p| p.text.as_deref())),
+            true,
+        ),
+        ("url", strings(rows.iter().map(|p| p.url.as_deref())), true),
+        (
+            "link",
+            strings(rows.iter().map(|p| p.link.as_deref())),
+            true,
+        ),
+        (
+            "community",
+            strings(rows.iter().map(|p| p.community.as_deref())),
+            true,
+        ),
+        ("attachments", attachment_list(&attachments)?, false),
+    ])
+}
+
+pub fn comments(dataset: &Dataset) -> Result<RecordBatch, ArrowError> {
+    let rows = &dataset.comments;
+
+    RecordBatch::try_from_iter_with_nullable([
+        ("platform", platform(dataset, rows.len()), false),
+        ("id", strings(rows.iter().map(|c| c.id.as_deref())), true),
+        (
+            "author",
+            strings(rows.iter().map(|c| c.author.as_deref())),
+            true,
+        ),
+        (
+            "timestamp",
+            timestamps(rows.iter().map(|c| c.timestamp)),
+            true,
+        ),
+        (
+            "text",
+            strings(rows.iter().map(|c| Some(c.text.as_str()))),
+            false,
+        ),
+        ("url", strings(rows.iter().map(|c| c.url.as_deref())), true),
+        (
+            "post",
+            strings(rows.iter().map(|c| c.post.as_deref())),
+            true,
+        ),
+        (
+            "parent",
+            strings(rows.iter().map(|c| c.parent.as_deref())),
+            true,
+        ),
+        (
+            "community",
+            strings(rows.iter().map(|c| c.community.as_deref())),
+            true,
+        ),
+    ])
+}
+
+pub fn follows(dataset: &Dataset) -> Result<RecordBatch, ArrowError> {
+    let rows = &dataset.follows;
+
+    RecordBatch::try_from_iter_with_nullable([
+        ("platform", platform(dataset, rows.len()), false),
+        (
+            "direction",
+            strings(rows.iter().map(|f| Some(f.direction.as_str()))),
+            false,
+        ),
+        (
+            "name",
+            strings(rows.iter().map(|f| Some(f.name.as_str()))),
+            false,
+        ),
+        ("url", strings(rows.iter().map(|f| f.url.as_deref())), true),
+        (
+            "timestamp",
+            timestamps(rows.iter().map(|f| f.timestamp)),
+            true,
+        ),
+    ])
+}
+
+pub fn likes(dataset: &Dataset) -> Result<RecordBatch, ArrowError> {
+    let rows = &dataset.likes;
+
+    RecordBatch::try_from_iter_with_nullable([
+        ("platform", platform(dataset, rows.len()), false),
+        (
+            "target",
+            strings(rows.iter().map(|l| l.target.as_deref())),
+            true,
+        ),
+        (
+            "title",
+            strings(rows.iter().map(|l| l.title.as_deref())),
+            true,
+        ),
+        (
+            "reaction",
+            strings(rows.iter().map(|l| l.reaction.as_deref())),
+            true,
+        ),
+        (
+            "timestamp",
+            timestamps(rows.iter().map(|l| l.timestamp)),
+            true,
+        ),
+    ])
+}
+
+pub fn saved_items(dataset: &Dataset) -> Result<RecordBatch, ArrowError> {
+    let rows = &dataset.saved_items;
+
+    RecordBatch::try_from_iter_with_nullable([
+        ("platform", platform(dataset, rows.len()), false),
+        (
+            "target",
+            strings(rows.iter().map(|s| s.target.as_deref())),
+            true,
+        ),
+        (
+            "title",
+            strings(rows.iter().map(|s| s.title.as_deref())),
+            true,
+        ),
+        (
+            "collection",
+            strings(rows.iter().map(|s| s.collection.as_deref())),
+            true,
+        ),
+        (
+            "timestamp",
+            timestamps(rows.iter().map(|s| s.timestamp)),
+            true,
+        ),
+    ])
+}
+
+pub fn logins(dataset: &Dataset) -> Result<RecordBatch, ArrowError> {
+    let rows = &dataset.logins;
+
+    RecordBatch::try_from_iter_with_nullable([
+        ("platform", platform(dataset, rows.len()), false),
+        (
+            "timestamp",
+            timestamps(rows.iter().map(|l| l.timestamp)),
+            true,
+        ),
+        (
+            "ip_address",
+            strings(rows.iter().map(|l| l.ip_address.as_deref())),
+            true,
+        ),
+        (
+            "device",
+            strings(rows.iter().map(|l| l.device.as_deref())),
+            true,
+        ),
+        (
+            "location",
+            strings(rows.iter().map(|l| l.location.as_deref())),
+            true,
+        ),
+    ])
+}
+
+fn platform(dataset: &Dataset, len: usize) -> ArrayRef {
+    strings(std::iter::repeat_n(Some(dataset.platform.as_str()), len))
+}
+
+fn strings<'a>(values: impl IntoIterator<Item = Option<&'a str>>) -> ArrayRef {
+    Arc::new(StringArray::from_iter(values))
+}
+
+fn timestamp_type() -> DataType {
+    DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+}
+
+fn timestamps(
+    values: impl IntoIterator<Item = Option<ArchiveTimestamp<Milliseconds>>>,
+) -> ArrayRef {
+    let millis = values.into_iter().map(|t| t.map(|t| t.as_millis()));
+    Arc::new(TimestampMillisecondArray::from_iter(millis).with_timezone("UTC"))
+}
+
+/// List column with one list of values per row
+fn list(
+    lengths: impl IntoIterator<Item = usize>,
+    values: ArrayRef,
+) -> Result<ArrayRef, ArrowError> {
+    let field = Arc::new(Field::new_list_field(values.data_type().clone(), false));
+    let offsets = OffsetBuffer::from_lengths(lengths);
+    Ok(Arc::new(ListArray::try_new(field, offsets, values, None)?))
+}
+
+fn string_list(rows: &[&[String]]) -> Result<ArrayRef, ArrowError> {
+    let values = strings(
+        rows.iter()
+            .flat_map(|row| row.iter().map(|s| Some(s.as_str()))),
+    );
+    list(rows.iter().map(|row| row.len()), values)
+}
+
+/// List column of structs, with `columns` building each field from every item of every
+/// row
+fn struct_list<T>(
+    rows: &[&[T]],
+    fields: Vec<Field>,
+    columns: impl FnOnce(&[&T]) -> Result<Vec<ArrayRef>, ArrowError>,
+) -> Result<ArrayRef, ArrowError> {
+    let items: Vec<&T> = rows.iter().flat_map(|row| row.iter()).collect();
+    let values = StructArray::try_new(Fields::from(fields), columns(&items)?, None)?;
+    list(rows.iter().map(|row| row.len()), Arc::new(values))
+}
+
+fn reaction_list(rows: &[&[MessageReaction]]) -> Result<ArrayRef, ArrowError> {
+    let fields = vec![
+        Field::new("reaction", DataType::Utf8, false),
+        Field::new("actor", DataType::Utf8, false),
+        Field::new("timestamp", timestamp_type(), true),
+    ];
+    struct_list(rows, fields, |items| {
+        Ok(vec![
+            strings(items.iter().map(|r| Some(r.reaction.as_str()))),
+            strings(items.iter().map(|r| Some(r.actor.as_str()))),
+            timestamps(items.iter().map(|r| r.timestamp)),
+        ])
+    })
+}
+
+fn attachment_list(rows: &[&[Attachment]]) -> Result<ArrayRef, ArrowError> {
+    let fields = vec![
+        Field::new("kind", DataType::Utf8, false),
+        Field::new("uri", DataType::Utf8, false),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("created_at", timestamp_type(), true),
+    ];
+    struct_list(rows, fields, |items| {
+        Ok(vec![
+            strings(items.iter().map(|a| Some(a.kind.as_str()))),
+            strings(items.iter().map(|a| Some(a.uri.as_str()))),
+            strings(items.iter().map(|a| a.name.as_deref())),
+            timestamps(items.iter().map(|a| a.created_at)),
+        ])
+    })
+}
+
+fn poll_option_list(rows: &[&[PollOption]]) -> Result<ArrayRef, ArrowError> {
+    let voters_type = DataType::List(Arc::new(Field::new_list_field(DataType::Utf8, false)));
+    let fields = vec![
+        Field::new("text", DataType::Utf8, false),
+        Field::new("voters", voters_type, false),
+    ];
+    struct_list(rows, fields, |items| {
+        let voters: Vec<&[String]> = items.iter().map(|o| o.voters.as_slice()).collect();
+        Ok(vec![
+            strings(items.iter().map(|o| Some(o.text.as_str()))),
+            string_list(&voters)?,
+        ])
+    })
+}
+
+/// Link and text of a shared message
+fn share(message: &ConversationMessage) -> Option<(Option<&str>, Option<&str>)> {
+    match &message.kind {
+        MessageKind::Share { link, text } => Some((link.as_deref(), text.as_deref())),
+        _ => None,
+    }
+}
+
+/// Duration and whether a call was missed
+fn call(message: &ConversationMessage) -> Option<(Option<u64>, bool)> {
+    match &message.kind {
+        MessageKind::Call {
+            duration_secs,
+            missed,
+        } => Some((*duration_secs, *missed)),
+        _ => None,
+    }
+}
@@ -0,0 +1,99 @@
+//! Archives that platforms ship as a single compressed file instead of a folder
+
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::{Component, Path, PathBuf},
+};
+
+use flate2::read::GzDecoder;
+use zip::ZipArchive;
+
+use super::ParseError;
+
+/// Whether `path` is a zip file, judging by its extension
+pub(crate) fn is_zip(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .and_then(|s| s.to_str())
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("zip"))
+}
+
+/// Whether `path` is a gzipped tarball, judging by its extension
+pub(crate) fn is_tar_gz(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .is_some_and(|name| {
+                let name = name.to_ascii_lowercase();
+                name.ends_with(".tar.gz") || name.ends_with(".tgz")
+            })
+}
+
+/// Call `visit` with every file in a zip archive, in archive order. Files are identified
+/// by their path inside the archive joined onto `path`, e.g. `export.zip/comments.csv`,
+/// along with their uncompressed size.
+pub(crate) fn for_each_zip_file<F>(path: &Path, mut visit: F) -> Result<(), ParseError>
+where
+    F: FnMut(&Path, &mut dyn Read, u64) -> Result<(), ParseError>,
+{
+    let file = File::open(path)?;
+    let mut archive =
+        ZipArchive::new(BufReader::new(file)).map_err(|e| ParseError::Zip(path.to_owned(), e))?;
+
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|e| ParseError::Zip(path.to_owned(), e))?;
+        if entry.is_dir() {
+            continue;
+        }
+        // Skips names that would escape the archive, e.g. `../file`
+        let Some(name) = entry.enclosed_name() else {
+            continue;
+        };
+
+        let size = entry.size();
+        visit(&path.join(name), &mut entry, size)?;
+    }
+
+    Ok(())
+}
+
+/// Call `visit` with every file in a gzipped tarball, in archive order. Files are
+/// identified the same way as by [`for_each_zip_file`]. The tarball is decompressed as it
+/// is read, so it is never held in memory.
+pub(crate) fn for_each_tar_gz_file<F>(path: &Path, mut visit: F) -> Result<(), ParseError>
+where
+    F: FnMut(&Path, &mut dyn Read, u64) -> Result<(), ParseError>,
+{
+    let file = File::open(path)?;
+    let mut archive = tar::Archive::new(GzDecoder::new(BufReader::new(file)));
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry.path()?.into_owned();
+        // Skips names that would escape the archive, e.g. `../file`
+        if name
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir))
+        {
+            continue;
+        }
+        // Tools differ on whether names start with `./`
+        let name: PathBuf = name
+            .components()
+            .filter(|c| *c != Component::CurDir)
+            .collect();
+
+        let size = entry.size();
+        visit(&path.join(name), &mut entry, size)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use serde::{Deserialize, Serialize};
+
+use super::{ArchiveTimestamp, Milliseconds};
+
+/// Chat thread normalized across platforms. Messages are sorted oldest first, with any
+/// whose time is unknown at the start.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Conversation {
+    /// Identifier of the thread within its archive, e.g. the inbox folder name
+    pub id: String,
+    pub title: Option<String>,
+    /// Display names of everyone in the thread
+    pub participants: Vec<String>,
+    pub messages: Vec<ConversationMessage>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ConversationMessage {
+    /// Identifier of the message, if the platform exports one
+    pub id: Option<String>,
+    pub sender: String,
+    /// Time the message was sent, if the archive has one that could be parsed
+    pub timestamp: Option<ArchiveTimestamp<Milliseconds>>,
+    pub content: Option<String>,
+    pub kind: MessageKind,
+    pub attachments: Vec<Attachment>,
+    pub reactions: Vec<MessageReaction>,
+    pub reply_to: Option<ReplyTo>,
+    pub is_unsent: bool,
+}
+
+impl ConversationMessage {
+    /// Plain text message with no attachments or reactions
+    pub fn new(sender: String, timestamp: Option<ArchiveTimestamp<Milliseconds>>) -> Self {
+        Self {
+            id: None,
+            sender,
+            timestamp,
+            content: None,
+            kind: MessageKind::Text,
+            attachments: vec![],
+            reactions: vec![],
+            reply_to: None,
+            is_unsent: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MessageKind {
+    Text,
+    /// Link or post shared into the conversation
+    Share {
+        link: Option<String>,
+        text: Option<String>,
+    },
+    Call {
+        duration_secs: Option<u64>,
+        missed: bool,
+    },
+    Poll(Poll),
+    /// Generated by the platform, e.g. someone joining or renaming the thread
+    System,
+}
+
+impl MessageKind {
+    /// Name of the kind, as used when serializing, e.g. `share`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MessageKind::Text => "text",
+            MessageKind::Share { .. } => "share",
+            MessageKind::Call { .. } => "call",
+            MessageKind::Poll(_) => "poll",
+            MessageKind::System => "system",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Poll {
+    pub question: String,
+    pub options: Vec<PollOption>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct PollOption {
+    pub text: String,
+    pub voters: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Attachment {
+    pub kind: AttachmentKind,
+    /// Path relative to the archive root, or a URL for remote files
+    pub uri: String,
+    pub name: Option<String>,
+    pub created_at: Option<ArchiveTimestamp<Milliseconds>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentKind {
+    Photo,
+    Video,
+    Audio,
+    Gif,
+    Sticker,
+    File,
+}
+
+impl AttachmentKind {
+    /// Name of the kind, as used when serializing, e.g. `photo`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttachmentKind::Photo => "photo",
+            AttachmentKind::Video => "video",
+            AttachmentKind::Audio => "audio",
+            AttachmentKind::Gif => "gif",
+            AttachmentKind::Sticker => "sticker",
+            AttachmentKind::File => "file",
+        }
+    }
+
+    /// Guess the kind of a file from its extension, for archives that don't label media
+    pub fn from_uri(uri: &str) -> AttachmentKind {
+        let extension = uri
+            .rsplit_once('.')
+            .map(|(_, extension)| extension.to_ascii_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "jpg" | "jpeg" | "png" | "webp" | "heic" | "bmp" => AttachmentKind::Photo,
+            "mp4" | "mov" | "webm" | "mkv" | "avi" => AttachmentKind::Video,
+            "mp3" | "m4a" | "aac" | "wav" | "ogg" | "opus" => AttachmentKind::Audio,
+            "gif" => AttachmentKind::Gif,
+            _ => AttachmentKind::File,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct MessageReaction {
+    pub reaction: String,
+    pub actor: String,
+    pub timestamp: Option<ArchiveTimestamp<Milliseconds>>,
+}
+
+/// Message being replied to. Platforms either reference it by id or quote it.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ReplyTo {
+    pub message_id: Option<String>,
+    pub sender: Option<String>,
+    pub content: Option<String>,
+}
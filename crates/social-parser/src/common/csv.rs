@@ -0,0 +1,330 @@
+//! Writing datasets as CSV files, one per collection, for opening in a spreadsheet.
+//!
+//! Columns are named like the fields of the common models, in snake case, and every file
+//! starts with a `platform` column. Timestamps are ISO 8601 in UTC, and missing values are
+//! empty cells. Lists nested in a row, like the reactions or attachments of a message, are
+//! joined into one cell with `; `.
+
+use std::{fs, path::Path};
+
+use csv::{Writer, WriterBuilder};
+
+use super::{
+    ArchiveTimestamp, Attachment, Dataset, FollowDirection, MessageKind, Milliseconds, WriteError,
+};
+
+/// Options controlling how CSV files are written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// Field delimiter. Defaults to `,`.
+    pub delimiter: u8,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self { delimiter: b',' }
+    }
+}
+
+impl CsvOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Separate fields with `delimiter`, e.g. `b';'` or `b'\t'`
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+}
+
+/// Write every collection of `dataset` to a CSV file in `dir`, creating the directory if
+/// needed and replacing existing files. Files are written even for empty collections, so
+/// every export has the same set of files:
+///
+/// - `followers.csv` and `following.csv`, with mutual follows in both
+/// - `conversations.csv` and `messages.csv`
+/// - `posts.csv` and `comments.csv`
+/// - `likes.csv`, `saved.csv` and `logins.csv`
+pub fn write(dataset: &Dataset, dir: &Path, options: &CsvOptions) -> Result<(), WriteError> {
+    fs::create_dir_all(dir)?;
+    let platform = dataset.platform.as_str();
+
+    for (name, direction) in [
+        ("followers", FollowDirection::Follower),
+        ("following", FollowDirection::Following),
+    ] {
+        let mut writer = create(
+            dir,
+            name,
+            options,
+            &["platform", "name", "url", "timestamp"],
+        )?;
+        for follow in dataset
+            .follows
+            .iter()
+            .filter(|f| f.direction == direction || f.direction == FollowDirection::Mutual)
+        {
+            writer.write_record([
+                platform,
+                &follow.name,
+                text(&follow.url),
+                &time(follow.timestamp),
+            ])?;
+        }
+        writer.flush()?;
+    }
+
+    let mut writer = create(
+        dir,
+        "conversations",
+        options,
+        &["platform", "id", "title", "participants", "message_count"],
+    )?;
+    for conversation in &dataset.conversations {
+        writer.write_record([
+            platform,
+            &conversation.id,
+            text(&conversation.title),
+            &conversation.participants.join("; "),
+            &conversation.messages.len().to_string(),
+        ])?;
+    }
+    writer.flush()?;
+
+    let mut writer = create(
+        dir,
+        "messages",
+        options,
+        &[
+            "platform",
+            "conversation_id",
+            "conversation_title",
+            "id",
+            "sender",
+            "timestamp",
+            "kind",
+            "content",
+            "share_link",
+            "share_text",
+            "call_duration_secs",
+            "call_missed",
+            "poll_question",
+            "poll_options",
+            "reply_to_id",
+            "reply_to_sender",
+            "reply_to_content",
+            "is_unsent",
+            "reactions",
+            "attachments",
+        ],
+    )?;
+    for conversation in &dataset.conversations {
+        for message in &conversation.messages {
+            let (share_link, share_text) = match &message.kind {
+                MessageKind::Share { link, text } => (link.clone(), text.clone()),
+                _ => (None, None),
+            };
+            let (call_duration_secs, call_missed) = match &message.kind {
+                MessageKind::Call {
+                    duration_secs,
+                    missed,
+                } => (
+                    duration_secs.map(|secs| secs.to_string()),
+                    missed.to_string(),
+                ),
+                _ => (None, String::new()),
+            };
+            let (poll_question, poll_options) =
+                match &message.kind {
+                    MessageKind::Poll(poll) => (
+                        Some(poll.question.clone()),
+                        join(poll.options.iter().map(|option| {
+                            format!("{} ({} votes)", option.text, option.voters.len())
+                        })),
+                    ),
+                    _ => (None, String::new()),
+                };
+            let reply_to = message.reply_to.as_ref();
+
+            writer.write_record([
+                platform,
+                &conversation.id,
+                text(&conversation.title),
+                text(&message.id),
+                &message.sender,
+                &time(message.timestamp),
+                message.kind.as_str(),
+                text(&message.content),
+                text(&share_link),
+                text(&share_text),
+                text(&call_duration_secs),
+                &call_missed,
+                text(&poll_question),
+                &poll_options,
+                text(&reply_to.and_then(|reply| reply.message_id.clone())),
+                text(&reply_to.and_then(|reply| reply.sender.clone())),
+                text(&reply_to.and_then(|reply| reply.content.clone())),
+                &message.is_unsent.to_string(),
+                &join(
+                    message
+                        .reactions
+                        .iter()
+                        .map(|reaction| format!("{}: {}", reaction.actor, reaction.reaction)),
+                ),
+                &attachments(&message.attachments),
+            ])?;
+        }
+    }
+    writer.flush()?;
+
+    let mut writer = create(
+        dir,
+        "posts",
+        options,
+        &[
+            "platform",
+            "id",
+            "author",
+            "timestamp",
+            "title",
+            "text",
+            "url",
+            "link",
+            "community",
+            "attachments",
+        ],
+    )?;
+    for post in &dataset.posts {
+        writer.write_record([
+            platform,
+            text(&post.id),
+            text(&post.author),
+            &time(post.timestamp),
+            text(&post.title),
+            text(&post.text),
+            text(&post.url),
+            text(&post.link),
+            text(&post.community),
+            &attachments(&post.attachments),
+        ])?;
+    }
+    writer.flush()?;
+
+    let mut writer = create(
+        dir,
+        "comments",
+        options,
+        &[
+            "platform",
+            "id",
+            "author",
+            "timestamp",
+            "text",
+            "url",
+            "post",
+            "parent",
+            "community",
+        ],
+    )?;
+    for comment in &dataset.comments {
+        writer.write_record([
+            platform,
+            text(&comment.id),
+            text(&comment.author),
+            &time(comment.timestamp),
+            &comment.text,
+            text(&comment.url),
+            text(&comment.post),
+            text(&comment.parent),
+            text(&comment.community),
+        ])?;
+    }
+    writer.flush()?;
+
+    let mut writer = create(
+        dir,
+        "likes",
+        options,
+        &["platform", "target", "title", "reaction", "timestamp"],
+    )?;
+    for like in &dataset.likes {
+        writer.write_record([
+            platform,
+            text(&like.target),
+            text(&like.title),
+            text(&like.reaction),
+            &time(like.timestamp),
+        ])?;
+    }
+    writer.flush()?;
+
+    let mut writer = create(
+        dir,
+        "saved",
+        options,
+        &["platform", "target", "title", "collection", "timestamp"],
+    )?;
+    for item in &dataset.saved_items {
+        writer.write_record([
+            platform,
+            text(&item.target),
+            text(&item.title),
+            text(&item.collection),
+            &time(item.timestamp),
+        ])?;
+    }
+    writer.flush()?;
+
+    let mut writer = create(
+        dir,
+        "logins",
+        options,
+        &["platform", "timestamp", "ip_address", "device", "location"],
+    )?;
+    for login in &dataset.logins {
+        writer.write_record([
+            platform,
+            &time(login.timestamp),
+            text(&login.ip_address),
+            text(&login.device),
+            text(&login.location),
+        ])?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Create `<name>.csv` in `dir` and write its header row
+fn create(
+    dir: &Path,
+    name: &str,
+    options: &CsvOptions,
+    header: &[&str],
+) -> Result<Writer<fs::File>, WriteError> {
+    let mut writer = WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .from_path(dir.join(format!("{}.csv", name)))?;
+    writer.write_record(header)?;
+    Ok(writer)
+}
+
+fn text(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or_default()
+}
+
+fn time(timestamp: Option<ArchiveTimestamp<Milliseconds>>) -> String {
+    timestamp
+        .map(|timestamp| timestamp.to_iso8601())
+        .unwrap_or_default()
+}
+
+fn join(values: impl Iterator<Item = String>) -> String {
+    values.collect::<Vec<_>>().join("; ")
+}
+
+/// URIs of attachments, relative to the archive for files it includes
+fn attachments(attachments: &[Attachment]) -> String {
+    join(attachments.iter().map(|attachment| attachment.uri.clone()))
+}
@@ -0,0 +1,113 @@
+//! Records normalized across platforms, gathered from a whole archive so they can be
+//! written out in one go, e.g. to a database
+
+use serde::{Deserialize, Serialize};
+
+use super::{ArchiveTimestamp, Comment, Conversation, Milliseconds, Post};
+
+/// Everything an archive holds that maps to the common models. Sections that weren't
+/// loaded, or that a platform doesn't export, are left empty.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Dataset {
+    /// Platform the archive is from, e.g. `instagram`
+    pub platform: String,
+    pub account: AccountInfo,
+    pub conversations: Vec<Conversation>,
+    pub posts: Vec<Post>,
+    pub comments: Vec<Comment>,
+    pub follows: Vec<Follow>,
+    pub likes: Vec<Like>,
+    pub saved_items: Vec<SavedItem>,
+    pub logins: Vec<Login>,
+}
+
+impl Dataset {
+    /// Empty dataset for a platform
+    pub fn new(platform: &str) -> Dataset {
+        Dataset {
+            platform: platform.to_owned(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Account the archive belongs to
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct AccountInfo {
+    pub username: Option<String>,
+    pub display_name: Option<String>,
+    pub email: Option<String>,
+}
+
+/// Account followed by or following the archive's account
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Follow {
+    pub direction: FollowDirection,
+    /// Username or display name, whichever the platform exports
+    pub name: String,
+    pub url: Option<String>,
+    /// When the follow started, if known
+    pub timestamp: Option<ArchiveTimestamp<Milliseconds>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum FollowDirection {
+    /// The account follows them
+    Following,
+    /// They follow the account
+    Follower,
+    /// Connected both ways, e.g. friends or LinkedIn connections
+    Mutual,
+}
+
+impl FollowDirection {
+    /// Name of the direction, as used when serializing, e.g. `following`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FollowDirection::Following => "following",
+            FollowDirection::Follower => "follower",
+            FollowDirection::Mutual => "mutual",
+        }
+    }
+}
+
+/// Like or other reaction the account left on a post, comment or video
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Like {
+    /// Address or id of what was liked
+    pub target: Option<String>,
+    /// Description of what was liked, e.g. its author or text
+    pub title: Option<String>,
+    /// Reaction used, for platforms that have more than likes
+    pub reaction: Option<String>,
+    pub timestamp: Option<ArchiveTimestamp<Milliseconds>>,
+}
+
+/// Post, comment or other item the account saved or bookmarked
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct SavedItem {
+    /// Address or id of what was saved
+    pub target: Option<String>,
+    pub title: Option<String>,
+    /// Collection the item was saved to
+    pub collection: Option<String>,
+    pub timestamp: Option<ArchiveTimestamp<Milliseconds>>,
+}
+
+/// Sign in to the account
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Login {
+    pub timestamp: Option<ArchiveTimestamp<Milliseconds>>,
+    pub ip_address: Option<String>,
+    pub device: Option<String>,
+    /// e.g. a country or city
+    pub location: Option<String>,
+}
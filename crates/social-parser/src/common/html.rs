@@ -0,0 +1,169 @@
+//! Just enough HTML handling for the fragments archives embed, e.g. Takeout pages or post
+//! bodies. Not a general HTML parser.
+
+/// Replace the entities Takeout uses with the characters they stand for
+pub(crate) fn decode_entities(s: &str) -> String {
+    let mut decoded = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(end) = rest.find(';').filter(|end| *end <= 10) else {
+            decoded.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let entity = &rest[1..end];
+        let character = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" | "emsp" | "ensp" => Some(' '),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|n| n.parse().ok()))
+                .and_then(char::from_u32),
+        };
+
+        match character {
+            Some(character) => {
+                decoded.push(character);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+/// Text content of an HTML fragment, with tags removed and entities decoded
+pub(crate) fn text(html: &str) -> String {
+    let mut stripped = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => stripped.push(c),
+            _ => {}
+        }
+    }
+
+    decode_entities(&stripped).trim().to_owned()
+}
+
+/// Target and text of every link in an HTML fragment
+pub(crate) fn links(html: &str) -> Vec<(String, String)> {
+    let mut links = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<a href=\"") {
+        rest = &rest[start + 9..];
+        let Some(end) = rest.find('"') else {
+            break;
+        };
+        let href = decode_entities(&rest[..end]);
+        rest = &rest[end..];
+
+        let Some(open_end) = rest.find('>') else {
+            break;
+        };
+        rest = &rest[open_end + 1..];
+        let close = rest.find("</a>").unwrap_or(rest.len());
+        links.push((href, text(&rest[..close])));
+        rest = &rest[close..];
+    }
+
+    links
+}
+
+/// Inner HTML of the first element opened by `marker`, up to the closing tag `close`
+pub(crate) fn inner<'a>(html: &'a str, marker: &str, close: &str) -> Option<&'a str> {
+    let start = html.find(marker)?;
+    let rest = &html[start..];
+    let rest = &rest[rest.find('>')? + 1..];
+    let end = rest.find(close).unwrap_or(rest.len());
+    Some(&rest[..end])
+}
+
+/// Text content of an HTML fragment like [`text`], keeping line breaks and separating
+/// paragraphs and other blocks with a blank line
+pub(crate) fn text_with_breaks(html: &str) -> String {
+    const BLOCKS: [&str; 11] = [
+        "p",
+        "div",
+        "blockquote",
+        "li",
+        "h1",
+        "h2",
+        "h3",
+        "h4",
+        "h5",
+        "h6",
+        "figure",
+    ];
+
+    let mut marked = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        // Whitespace in the source collapses, only tags break lines
+        marked.push_str(&rest[..start].replace(['\n', '\r', '\t'], " "));
+        rest = &rest[start..];
+        let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+        let tag = &rest[..end];
+
+        let name = tag
+            .trim_start_matches(['<', '/'])
+            .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if name == "br" {
+            marked.push('\n');
+        } else if tag.starts_with("</") && BLOCKS.contains(&name.as_str()) {
+            marked.push_str("\n\n");
+        } else {
+            marked.push_str(tag);
+        }
+        rest = &rest[end..];
+    }
+    marked.push_str(&rest.replace(['\n', '\r', '\t'], " "));
+
+    // Trim every line and keep at most one blank line in a row
+    let mut lines: Vec<&str> = vec![];
+    let text = text(&marked);
+    for line in text.lines().map(str::trim) {
+        if !line.is_empty() || lines.last().is_some_and(|last| !last.is_empty()) {
+            lines.push(line);
+        }
+    }
+    lines.join("\n").trim().to_owned()
+}
+
+/// Value of every `src` attribute in an HTML fragment, e.g. of images and videos
+pub(crate) fn sources(html: &str) -> Vec<String> {
+    let mut sources = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find(" src=\"") {
+        rest = &rest[start + 6..];
+        let Some(end) = rest.find('"') else {
+            break;
+        };
+        sources.push(decode_entities(&rest[..end]));
+        rest = &rest[end..];
+    }
+
+    sources
+}
@@ -0,0 +1,162 @@
+//! Writing records as newline-delimited JSON, one record per line, for exports too large to
+//! handle as a single document or for piping into tools like `jq`.
+//!
+//! Every line is an object tagging the record with its platform, collection and source
+//! path:
+//!
+//! ```json
+//! {"platform":"telegram","collection":"messages","source":"export/result.json","record":{...}}
+//! ```
+//!
+//! Archives that implement [`StreamingArchive`](super::StreamingArchive) (Telegram,
+//! Instagram, Messenger, Discord, Twitter, Reddit and Google) are written by
+//! [`stream_to_jsonl`](super::StreamingArchive::stream_to_jsonl) as each message file is
+//! parsed, and messages and conversations carry the path of the file they came from, e.g.
+//! `inbox/alice_123/message_1.json`. The rest of the dataset, and every record written by
+//! [`Archive::save_to_jsonl`](super::Archive::save_to_jsonl), carries the path of the
+//! archive, since [`Dataset`] doesn't keep track of the file behind each record.
+//!
+//! Collections are named like the fields of [`Dataset`]: `account`, `conversations`,
+//! `messages`, `posts`, `comments`, `follows`, `likes`, `saved_items` and `logins`.
+//! Records are the common models, except that conversations leave out their messages and
+//! messages carry the id and title of their conversation.
+
+use std::{
+    io::{self, Write},
+    path::Path,
+};
+
+use serde::Serialize;
+
+use super::{Conversation, ConversationMessage, Dataset};
+
+/// Writes tagged records to `W`, one per line. Wrap files in a `BufWriter`, since every
+/// record is written separately.
+#[derive(Debug)]
+pub struct JsonlWriter<W: Write> {
+    writer: W,
+}
+
+/// Conversation without its messages, which are written as separate records
+#[derive(Serialize, Debug, Clone, Copy)]
+pub struct ConversationRecord<'a> {
+    pub id: &'a str,
+    pub title: Option<&'a str>,
+    pub participants: &'a [String],
+}
+
+/// Message with the conversation it belongs to
+#[derive(Serialize, Debug, Clone, Copy)]
+pub struct MessageRecord<'a> {
+    pub conversation_id: &'a str,
+    pub conversation_title: Option<&'a str>,
+    #[serde(flatten)]
+    pub message: &'a ConversationMessage,
+}
+
+#[derive(Serialize)]
+struct Line<'a, T> {
+    platform: &'a str,
+    collection: &'a str,
+    source: &'a Path,
+    record: &'a T,
+}
+
+impl<W: Write> JsonlWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Write one record on its own line
+    pub fn write<T: Serialize>(
+        &mut self,
+        platform: &str,
+        collection: &str,
+        source: &Path,
+        record: &T,
+    ) -> io::Result<()> {
+        let line = Line {
+            platform,
+            collection,
+            source,
+            record,
+        };
+        serde_json::to_writer(&mut self.writer, &line)?;
+        self.writer.write_all(b"\n")
+    }
+
+    /// Write every record of `dataset`, all tagged with `source`, the path the archive was
+    /// loaded from
+    pub fn write_dataset(&mut self, dataset: &Dataset, source: &Path) -> io::Result<()> {
+        let platform = dataset.platform.as_str();
+
+        self.write(platform, "account", source, &dataset.account)?;
+        for conversation in &dataset.conversations {
+            self.write_conversation(platform, source, conversation)?;
+        }
+        for post in &dataset.posts {
+            self.write(platform, "posts", source, post)?;
+        }
+        for comment in &dataset.comments {
+            self.write(platform, "comments", source, comment)?;
+        }
+        for follow in &dataset.follows {
+            self.write(platform, "follows", source, follow)?;
+        }
+        for like in &dataset.likes {
+            self.write(platform, "likes", source, like)?;
+        }
+        for item in &dataset.saved_items {
+            self.write(platform, "saved_items", source, item)?;
+        }
+        for login in &dataset.logins {
+            self.write(platform, "logins", source, login)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write a conversation followed by each of its messages
+    pub fn write_conversation(
+        &mut self,
+        platform: &str,
+        source: &Path,
+        conversation: &Conversation,
+    ) -> io::Result<()> {
+        let record = ConversationRecord {
+            id: &conversation.id,
+            title: conversation.title.as_deref(),
+            participants: &conversation.participants,
+        };
+        self.write(platform, "conversations", source, &record)?;
+        self.write_messages(platform, source, conversation)
+    }
+
+    /// Write each message of a conversation, without the conversation itself
+    pub fn write_messages(
+        &mut self,
+        platform: &str,
+        source: &Path,
+        conversation: &Conversation,
+    ) -> io::Result<()> {
+        for message in &conversation.messages {
+            let record = MessageRecord {
+                conversation_id: &conversation.id,
+                conversation_title: conversation.title.as_deref(),
+                message,
+            };
+            self.write(platform, "messages", source, &record)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
@@ -1,12 +1,48 @@
-use std::path::PathBuf;
+pub mod archive;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub(crate) mod bundle;
+pub mod conversation;
+pub mod csv;
+pub mod dataset;
+pub(crate) mod html;
+pub mod jsonl;
+pub mod options;
+pub mod post;
+pub mod progress;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod timestamp;
 
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use serde::de::DeserializeOwned;
 use thiserror::Error;
 
+pub(crate) use archive::OnConversation;
+pub use archive::{Archive, StreamingArchive};
+pub use conversation::{
+    Attachment, AttachmentKind, Conversation, ConversationMessage, MessageKind, MessageReaction,
+    Poll, PollOption, ReplyTo,
+};
+pub use dataset::{AccountInfo, Dataset, Follow, FollowDirection, Like, Login, SavedItem};
+pub use options::{LoadOptions, Sections};
+pub use post::{Comment, Post};
+pub use progress::{CancellationToken, ItemCount, Progress, ProgressEvent};
+pub use timestamp::{ArchiveTimestamp, Milliseconds, Seconds, TimeUnit};
+
 #[derive(Error, Debug)]
 pub enum ParseError {
     Serde(PathBuf, serde_json::Error),
+    Csv(PathBuf, ::csv::Error),
+    Zip(PathBuf, zip::result::ZipError),
     Io(#[from] std::io::Error),
     UnexpectedFormat(String),
+    Cancelled,
 }
 
 impl std::fmt::Display for ParseError {
@@ -19,6 +55,16 @@ impl std::fmt::Display for ParseError {
 pub enum WriteError {
     Serde(#[from] serde_json::Error),
     Io(#[from] std::io::Error),
+    Csv(#[from] ::csv::Error),
+    #[cfg(feature = "sqlite")]
+    Sqlite(#[from] rusqlite::Error),
+    /// Database was created with another version of the schema
+    #[cfg(feature = "sqlite")]
+    SchemaVersion(i64),
+    #[cfg(feature = "arrow")]
+    Arrow(#[from] ::arrow::error::ArrowError),
+    #[cfg(feature = "arrow")]
+    Parquet(#[from] ::parquet::errors::ParquetError),
 }
 
 impl std::fmt::Display for WriteError {
@@ -26,3 +72,44 @@ impl std::fmt::Display for WriteError {
         write!(f, "{:?}", self)
     }
 }
+
+/// Read a JSON file
+pub(crate) fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, ParseError> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+
+    serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+}
+
+/// Read a file with one JSON document per line, skipping blank lines
+pub(crate) fn read_json_lines<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, ParseError> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+
+    let mut items = vec![];
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        items.push(serde_json::from_str(&line).map_err(|e| ParseError::Serde(path.to_owned(), e))?);
+    }
+    Ok(items)
+}
+
+/// Read every row of a CSV file with a header row
+pub(crate) fn read_csv<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, ParseError> {
+    read_csv_from(File::open(path)?, path)
+}
+
+/// Read every row of CSV with a header row, e.g. from an entry of a zip archive.
+/// `path` is only used in errors.
+pub(crate) fn read_csv_from<T: DeserializeOwned, R: Read>(
+    reader: R,
+    path: &Path,
+) -> Result<Vec<T>, ParseError> {
+    ::csv::Reader::from_reader(reader)
+        .deserialize()
+        .collect::<Result<Vec<T>, _>>()
+        .map_err(|e| ParseError::Csv(path.to_owned(), e))
+}
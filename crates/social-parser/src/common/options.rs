@@ -0,0 +1,183 @@
+use std::{fmt, path::Path, sync::Arc};
+
+use bitflags::bitflags;
+use log::{debug, info, trace, warn};
+
+use super::{CancellationToken, ItemCount, ParseError, Progress, ProgressEvent};
+
+bitflags! {
+    /// Sections of an archive to load. Unselected sections are skipped
+    /// entirely and are left as `None` in the parsed archive.
+    #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+    pub struct Sections: u32 {
+        const CONTACTS = 1 << 0;
+        const FOLLOWERS_AND_FOLLOWING = 1 << 1;
+        const MEDIA = 1 << 2;
+        const COMMENTS = 1 << 3;
+        const LIKES = 1 << 4;
+        const MESSAGES = 1 << 5;
+        const SAVED = 1 << 6;
+        /// Threads data, from a standalone Threads export or the
+        /// `your_instagram_activity/threads` folder of an Instagram export
+        const THREADS = 1 << 7;
+        const POSTS = 1 << 8;
+        const GROUPS = 1 << 9;
+        const EVENTS = 1 << 10;
+        const PAGES = 1 << 11;
+        const MARKETPLACE = 1 << 12;
+        const PROFILE = 1 << 13;
+        const SEARCH_HISTORY = 1 << 14;
+        const ANALYTICS = 1 << 15;
+        const WATCH_HISTORY = 1 << 16;
+
+        const CONNECTIONS = Self::CONTACTS.bits() | Self::FOLLOWERS_AND_FOLLOWING.bits();
+        const ACTIVITY = Self::COMMENTS.bits()
+            | Self::LIKES.bits()
+            | Self::MESSAGES.bits()
+            | Self::SAVED.bits()
+            | Self::THREADS.bits();
+    }
+}
+
+impl Default for Sections {
+    fn default() -> Self {
+        Sections::all()
+    }
+}
+
+/// Options controlling how an archive is loaded
+#[derive(Default, Clone)]
+pub struct LoadOptions {
+    /// Sections to load. Defaults to all sections.
+    pub sections: Sections,
+    /// Receives progress events, if set
+    pub progress: Option<Arc<dyn Progress>>,
+    /// Checked between files to stop loading early
+    pub cancellation: CancellationToken,
+}
+
+impl fmt::Debug for LoadOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoadOptions")
+            .field("sections", &self.sections)
+            .field("progress", &self.progress.is_some())
+            .field("cancellation", &self.cancellation)
+            .finish()
+    }
+}
+
+impl LoadOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only load the given sections
+    pub fn with_sections(mut self, sections: Sections) -> Self {
+        self.sections = sections;
+        self
+    }
+
+    /// Report progress events to `progress`
+    pub fn with_progress<P: Progress + 'static>(mut self, progress: P) -> Self {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Stop loading once `cancellation` is cancelled
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Whether any of the given sections are selected
+    pub fn includes(&self, sections: Sections) -> bool {
+        self.sections.intersects(sections)
+    }
+
+    /// Whether any of the given sections are selected, logging when they are skipped
+    pub(crate) fn should_load(&self, sections: Sections, path: &Path) -> bool {
+        let selected = self.includes(sections);
+        if !selected {
+            debug!("Skipping unselected section: {:?}", path);
+        }
+        selected
+    }
+
+    fn emit(&self, event: ProgressEvent) {
+        if let Some(progress) = &self.progress {
+            progress.on_event(&event);
+        }
+    }
+
+    /// Fail with [`ParseError::Cancelled`] if loading was cancelled
+    pub(crate) fn check_cancelled(&self) -> Result<(), ParseError> {
+        if self.cancellation.is_cancelled() {
+            info!("Loading cancelled");
+            Err(ParseError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Report that a section is about to be loaded
+    pub(crate) fn enter_section(&self, name: &str, path: &Path) -> Result<(), ParseError> {
+        self.check_cancelled()?;
+        debug!("Loading {} from {:?}", name, path);
+        self.emit(ProgressEvent::EnterSection {
+            name: name.to_owned(),
+            path: path.to_owned(),
+        });
+        Ok(())
+    }
+
+    /// Note a known part of the archive that isn't parsed. Only logged at debug level,
+    /// since complete archives are full of them; use [`warn`](Self::warn) for anything
+    /// unexpected.
+    pub(crate) fn skip(&self, path: &Path, message: &str) {
+        debug!("{}: {:?}", message, path);
+    }
+
+    /// Report something in the archive that was skipped or not understood
+    pub(crate) fn warn<M: Into<String>>(&self, path: &Path, message: M) {
+        let message = message.into();
+        warn!("{}: {:?}", message, path);
+        self.emit(ProgressEvent::Warning {
+            path: path.to_owned(),
+            message,
+        });
+    }
+
+    /// Parse a single file, reporting progress around it
+    pub(crate) fn load_file<T>(&self, path: &Path) -> Result<T, ParseError>
+    where
+        T: for<'a> TryFrom<&'a Path, Error = ParseError> + ItemCount,
+    {
+        let bytes = path.metadata().map(|m| m.len()).unwrap_or(0);
+        self.load_with(path, bytes, || T::try_from(path))
+    }
+
+    /// Parse a file with `load`, reporting progress around it. Used for files that aren't
+    /// read from disk directly, like entries of a zip archive.
+    pub(crate) fn load_with<T, F>(&self, path: &Path, bytes: u64, load: F) -> Result<T, ParseError>
+    where
+        T: ItemCount,
+        F: FnOnce() -> Result<T, ParseError>,
+    {
+        self.check_cancelled()?;
+        trace!("Parsing {:?}", path);
+        self.emit(ProgressEvent::FileStarted {
+            path: path.to_owned(),
+        });
+
+        let data = load()?;
+
+        let items = data.item_count();
+        debug!("Parsed {:?} ({} items, {} bytes)", path, items, bytes);
+        self.emit(ProgressEvent::FileFinished {
+            path: path.to_owned(),
+            bytes,
+            items,
+        });
+        Ok(data)
+    }
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use super::{ArchiveTimestamp, Attachment, Milliseconds};
+
+/// Post normalized across platforms
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Post {
+    /// Identifier of the post, if the platform exports one
+    pub id: Option<String>,
+    pub author: Option<String>,
+    pub timestamp: Option<ArchiveTimestamp<Milliseconds>>,
+    pub title: Option<String>,
+    pub text: Option<String>,
+    /// Address of the post itself
+    pub url: Option<String>,
+    /// External link the post shares, e.g. for link posts on Reddit
+    pub link: Option<String>,
+    /// Community the post was made in, e.g. a subreddit or group
+    pub community: Option<String>,
+    pub attachments: Vec<Attachment>,
+}
+
+/// Comment normalized across platforms
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Comment {
+    /// Identifier of the comment, if the platform exports one
+    pub id: Option<String>,
+    pub author: Option<String>,
+    pub timestamp: Option<ArchiveTimestamp<Milliseconds>>,
+    pub text: String,
+    /// Address of the comment itself
+    pub url: Option<String>,
+    /// Post the comment was made on, as an id or URL
+    pub post: Option<String>,
+    /// Comment being replied to, as an id or URL
+    pub parent: Option<String>,
+    /// Community the post was made in, e.g. a subreddit or group
+    pub community: Option<String>,
+}
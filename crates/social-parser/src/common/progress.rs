@@ -0,0 +1,74 @@
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use serde::Serialize;
+
+/// Event reported while an archive is being loaded
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS), ts(export))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    /// Started loading a section (directory) of the archive
+    EnterSection { name: String, path: PathBuf },
+    /// Started parsing a file
+    FileStarted { path: PathBuf },
+    /// Finished parsing a file of `bytes` bytes containing `items` records
+    FileFinished {
+        path: PathBuf,
+        bytes: u64,
+        items: usize,
+    },
+    /// Something unexpected in the archive was skipped or could not be fully understood.
+    /// Known sections that aren't parsed aren't reported, only logged at debug level.
+    Warning { path: PathBuf, message: String },
+}
+
+/// Receives progress events while an archive is being loaded
+pub trait Progress: Send + Sync {
+    fn on_event(&self, event: &ProgressEvent);
+}
+
+impl<F> Progress for F
+where
+    F: Fn(&ProgressEvent) + Send + Sync,
+{
+    fn on_event(&self, event: &ProgressEvent) {
+        self(event)
+    }
+}
+
+/// Token used to cancel an in-progress load. Cancellation is checked between files,
+/// after which loading stops with [`ParseError::Cancelled`](super::ParseError::Cancelled).
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation. Affects every clone of this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Number of records in a parsed file, reported in [`ProgressEvent::FileFinished`]
+pub trait ItemCount {
+    fn item_count(&self) -> usize;
+}
+
+impl<T> ItemCount for Vec<T> {
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+}
@@ -0,0 +1,311 @@
+//! Writing datasets to a SQLite database, with the schema in [`SCHEMA`]. Each archive
+//! written to a database is added as a new account, so several archives, even from
+//! different platforms, can share one file.
+
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+
+use super::{
+    ArchiveTimestamp, Attachment, Conversation, Dataset, MessageKind, Milliseconds, WriteError,
+};
+
+/// Version of [`SCHEMA`], stored in the `schema_version` table. Bumped whenever the
+/// schema changes.
+pub const SCHEMA_VERSION: i64 = 2;
+
+/// Tables and indexes created in new databases
+pub const SCHEMA: &str = include_str!("schema.sql");
+
+/// Add `dataset` to the database at `path`, creating the file and schema if needed.
+/// Everything is written in one transaction. Returns the id of the new row in `accounts`.
+///
+/// Fails with [`WriteError::SchemaVersion`] if the database was created with another
+/// version of the schema.
+pub fn write(dataset: &Dataset, path: &Path) -> Result<i64, WriteError> {
+    let mut connection = Connection::open(path)?;
+    connection.pragma_update(None, "foreign_keys", true)?;
+
+    let transaction = connection.transaction()?;
+    create_schema(&transaction)?;
+    let account_id = insert_dataset(&transaction, dataset)?;
+    transaction.commit()?;
+
+    Ok(account_id)
+}
+
+/// Create the schema in an empty database, or check the version of an existing one
+fn create_schema(transaction: &Transaction) -> Result<(), WriteError> {
+    let exists = transaction
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_version'",
+            [],
+            |_| Ok(()),
+        )
+        .optional()?
+        .is_some();
+
+    if exists {
+        let version: i64 =
+            transaction.query_row("SELECT version FROM schema_version", [], |row| row.get(0))?;
+        if version != SCHEMA_VERSION {
+            return Err(WriteError::SchemaVersion(version));
+        }
+        return Ok(());
+    }
+
+    transaction.execute_batch(SCHEMA)?;
+    transaction.execute(
+        "INSERT INTO schema_version (version) VALUES (?1)",
+        [SCHEMA_VERSION],
+    )?;
+    Ok(())
+}
+
+fn insert_dataset(transaction: &Transaction, dataset: &Dataset) -> Result<i64, WriteError> {
+    let account = &dataset.account;
+    transaction.execute(
+        "INSERT INTO accounts (platform, username, display_name, email) VALUES (?1, ?2, ?3, ?4)",
+        params![
+            dataset.platform,
+            account.username,
+            account.display_name,
+            account.email
+        ],
+    )?;
+    let account_id = transaction.last_insert_rowid();
+
+    for conversation in &dataset.conversations {
+        insert_conversation(transaction, account_id, conversation)?;
+    }
+
+    for post in &dataset.posts {
+        transaction
+            .prepare_cached(
+                "INSERT INTO posts (account_id, source_id, author, timestamp, title, text, url, \
+                 link, community) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            )?
+            .execute(params![
+                account_id,
+                post.id,
+                post.author,
+                millis(post.timestamp),
+                post.title,
+                post.text,
+                post.url,
+                post.link,
+                post.community
+            ])?;
+        let post_id = transaction.last_insert_rowid();
+        for attachment in &post.attachments {
+            insert_attachment(transaction, None, Some(post_id), attachment)?;
+        }
+    }
+
+    for comment in &dataset.comments {
+        transaction
+            .prepare_cached(
+                "INSERT INTO comments (account_id, source_id, author, timestamp, text, url, post, \
+                 parent, community) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            )?
+            .execute(params![
+                account_id,
+                comment.id,
+                comment.author,
+                millis(comment.timestamp),
+                comment.text,
+                comment.url,
+                comment.post,
+                comment.parent,
+                comment.community
+            ])?;
+    }
+
+    for follow in &dataset.follows {
+        transaction
+            .prepare_cached(
+                "INSERT INTO follows (account_id, direction, name, url, timestamp) \
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?
+            .execute(params![
+                account_id,
+                follow.direction.as_str(),
+                follow.name,
+                follow.url,
+                millis(follow.timestamp)
+            ])?;
+    }
+
+    for like in &dataset.likes {
+        transaction
+            .prepare_cached(
+                "INSERT INTO likes (account_id, target, title, reaction, timestamp) \
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?
+            .execute(params![
+                account_id,
+                like.target,
+                like.title,
+                like.reaction,
+                millis(like.timestamp)
+            ])?;
+    }
+
+    for item in &dataset.saved_items {
+        transaction
+            .prepare_cached(
+                "INSERT INTO saved_items (account_id, target, title, collection, timestamp) \
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?
+            .execute(params![
+                account_id,
+                item.target,
+                item.title,
+                item.collection,
+                millis(item.timestamp)
+            ])?;
+    }
+
+    for login in &dataset.logins {
+        transaction
+            .prepare_cached(
+                "INSERT INTO logins (account_id, timestamp, ip_address, device, location) \
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?
+            .execute(params![
+                account_id,
+                millis(login.timestamp),
+                login.ip_address,
+                login.device,
+                login.location
+            ])?;
+    }
+
+    Ok(account_id)
+}
+
+fn insert_conversation(
+    transaction: &Transaction,
+    account_id: i64,
+    conversation: &Conversation,
+) -> Result<(), WriteError> {
+    transaction
+        .prepare_cached(
+            "INSERT INTO conversations (account_id, source_id, title) VALUES (?1, ?2, ?3)",
+        )?
+        .execute(params![account_id, conversation.id, conversation.title])?;
+    let conversation_id = transaction.last_insert_rowid();
+
+    for participant in &conversation.participants {
+        transaction
+            .prepare_cached(
+                "INSERT OR IGNORE INTO participants (conversation_id, name) VALUES (?1, ?2)",
+            )?
+            .execute(params![conversation_id, participant])?;
+    }
+
+    for message in &conversation.messages {
+        let (share_link, share_text) = match &message.kind {
+            MessageKind::Share { link, text } => (link.as_deref(), text.as_deref()),
+            _ => (None, None),
+        };
+        let (call_duration_secs, call_missed) = match &message.kind {
+            MessageKind::Call {
+                duration_secs,
+                missed,
+            } => (duration_secs.map(|secs| secs as i64), Some(*missed)),
+            _ => (None, None),
+        };
+        let poll = match &message.kind {
+            MessageKind::Poll(poll) => Some(poll),
+            _ => None,
+        };
+        let reply_to = message.reply_to.as_ref();
+
+        transaction
+            .prepare_cached(
+                "INSERT INTO messages (conversation_id, source_id, sender, timestamp, content, \
+                 kind, share_link, share_text, call_duration_secs, call_missed, poll_question, \
+                 reply_to_source_id, reply_to_sender, reply_to_content, is_unsent) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+            )?
+            .execute(params![
+                conversation_id,
+                message.id,
+                message.sender,
+                millis(message.timestamp),
+                message.content,
+                message.kind.as_str(),
+                share_link,
+                share_text,
+                call_duration_secs,
+                call_missed,
+                poll.map(|poll| &poll.question),
+                reply_to.and_then(|reply| reply.message_id.as_deref()),
+                reply_to.and_then(|reply| reply.sender.as_deref()),
+                reply_to.and_then(|reply| reply.content.as_deref()),
+                message.is_unsent
+            ])?;
+        let message_id = transaction.last_insert_rowid();
+
+        for (position, option) in poll.iter().flat_map(|poll| &poll.options).enumerate() {
+            transaction
+                .prepare_cached(
+                    "INSERT INTO poll_options (message_id, position, text) VALUES (?1, ?2, ?3)",
+                )?
+                .execute(params![message_id, position as i64, option.text])?;
+            let option_id = transaction.last_insert_rowid();
+            for voter in &option.voters {
+                transaction
+                    .prepare_cached("INSERT INTO poll_votes (option_id, voter) VALUES (?1, ?2)")?
+                    .execute(params![option_id, voter])?;
+            }
+        }
+
+        for reaction in &message.reactions {
+            transaction
+                .prepare_cached(
+                    "INSERT INTO reactions (message_id, reaction, actor, timestamp) \
+                     VALUES (?1, ?2, ?3, ?4)",
+                )?
+                .execute(params![
+                    message_id,
+                    reaction.reaction,
+                    reaction.actor,
+                    millis(reaction.timestamp)
+                ])?;
+        }
+
+        for attachment in &message.attachments {
+            insert_attachment(transaction, Some(message_id), None, attachment)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn insert_attachment(
+    transaction: &Transaction,
+    message_id: Option<i64>,
+    post_id: Option<i64>,
+    attachment: &Attachment,
+) -> Result<(), WriteError> {
+    transaction
+        .prepare_cached(
+            "INSERT INTO attachments (message_id, post_id, kind, uri, name, created_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?
+        .execute(params![
+            message_id,
+            post_id,
+            attachment.kind.as_str(),
+            attachment.uri,
+            attachment.name,
+            millis(attachment.created_at)
+        ])?;
+    Ok(())
+}
+
+fn millis(timestamp: Option<ArchiveTimestamp<Milliseconds>>) -> Option<i64> {
+    timestamp.map(|timestamp| timestamp.as_millis())
+}
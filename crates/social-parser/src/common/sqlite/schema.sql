@@ -0,0 +1,176 @@
+-- Schema of the databases written by the `sqlite` feature.
+--
+-- Each archive written to a database adds a row to `accounts`, and every other row
+-- belongs to one of them. Timestamps are milliseconds since the Unix epoch, in UTC.
+-- `source_id` columns hold the identifier the platform gave a record, if any.
+
+CREATE TABLE schema_version (
+    version INTEGER NOT NULL
+);
+
+CREATE TABLE accounts (
+    id INTEGER PRIMARY KEY,
+    -- e.g. `instagram` or `bluesky`
+    platform TEXT NOT NULL,
+    username TEXT,
+    display_name TEXT,
+    email TEXT
+);
+
+CREATE TABLE conversations (
+    id INTEGER PRIMARY KEY,
+    account_id INTEGER NOT NULL REFERENCES accounts (id) ON DELETE CASCADE,
+    source_id TEXT NOT NULL,
+    title TEXT
+);
+CREATE INDEX conversations_account ON conversations (account_id);
+
+CREATE TABLE participants (
+    conversation_id INTEGER NOT NULL REFERENCES conversations (id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    PRIMARY KEY (conversation_id, name)
+);
+
+CREATE TABLE messages (
+    id INTEGER PRIMARY KEY,
+    conversation_id INTEGER NOT NULL REFERENCES conversations (id) ON DELETE CASCADE,
+    source_id TEXT,
+    sender TEXT NOT NULL,
+    timestamp INTEGER,
+    content TEXT,
+    -- `text`, `share`, `call`, `poll` or `system`
+    kind TEXT NOT NULL,
+    -- Set for shares
+    share_link TEXT,
+    share_text TEXT,
+    -- Set for calls
+    call_duration_secs INTEGER,
+    call_missed INTEGER,
+    -- Set for polls, with the options in `poll_options`
+    poll_question TEXT,
+    -- Message replied to, as far as the platform identifies it
+    reply_to_source_id TEXT,
+    reply_to_sender TEXT,
+    reply_to_content TEXT,
+    is_unsent INTEGER NOT NULL
+);
+CREATE INDEX messages_conversation ON messages (conversation_id, timestamp);
+CREATE INDEX messages_timestamp ON messages (timestamp);
+
+CREATE TABLE poll_options (
+    id INTEGER PRIMARY KEY,
+    message_id INTEGER NOT NULL REFERENCES messages (id) ON DELETE CASCADE,
+    -- Order of the option in the poll, from 0
+    position INTEGER NOT NULL,
+    text TEXT NOT NULL
+);
+CREATE INDEX poll_options_message ON poll_options (message_id);
+
+CREATE TABLE poll_votes (
+    option_id INTEGER NOT NULL REFERENCES poll_options (id) ON DELETE CASCADE,
+    voter TEXT NOT NULL
+);
+CREATE INDEX poll_votes_option ON poll_votes (option_id);
+
+CREATE TABLE reactions (
+    id INTEGER PRIMARY KEY,
+    message_id INTEGER NOT NULL REFERENCES messages (id) ON DELETE CASCADE,
+    reaction TEXT NOT NULL,
+    actor TEXT NOT NULL,
+    timestamp INTEGER
+);
+CREATE INDEX reactions_message ON reactions (message_id);
+
+CREATE TABLE posts (
+    id INTEGER PRIMARY KEY,
+    account_id INTEGER NOT NULL REFERENCES accounts (id) ON DELETE CASCADE,
+    source_id TEXT,
+    author TEXT,
+    timestamp INTEGER,
+    title TEXT,
+    text TEXT,
+    -- Address of the post itself
+    url TEXT,
+    -- External link the post shares
+    link TEXT,
+    -- e.g. a subreddit or group
+    community TEXT
+);
+CREATE INDEX posts_account ON posts (account_id, timestamp);
+
+-- Files attached to a message or a post
+CREATE TABLE attachments (
+    id INTEGER PRIMARY KEY,
+    message_id INTEGER REFERENCES messages (id) ON DELETE CASCADE,
+    post_id INTEGER REFERENCES posts (id) ON DELETE CASCADE,
+    -- `photo`, `video`, `audio`, `gif`, `sticker` or `file`
+    kind TEXT NOT NULL,
+    -- Path inside the archive or address, as the platform gives it
+    uri TEXT NOT NULL,
+    name TEXT,
+    created_at INTEGER,
+    CHECK ((message_id IS NULL) <> (post_id IS NULL))
+);
+CREATE INDEX attachments_message ON attachments (message_id);
+CREATE INDEX attachments_post ON attachments (post_id);
+
+CREATE TABLE comments (
+    id INTEGER PRIMARY KEY,
+    account_id INTEGER NOT NULL REFERENCES accounts (id) ON DELETE CASCADE,
+    source_id TEXT,
+    author TEXT,
+    timestamp INTEGER,
+    text TEXT NOT NULL,
+    url TEXT,
+    -- Post commented on, as an id or address
+    post TEXT,
+    -- Comment replied to, as an id or address
+    parent TEXT,
+    community TEXT
+);
+CREATE INDEX comments_account ON comments (account_id, timestamp);
+
+CREATE TABLE follows (
+    id INTEGER PRIMARY KEY,
+    account_id INTEGER NOT NULL REFERENCES accounts (id) ON DELETE CASCADE,
+    -- `following` when the account follows them, `follower` when they follow the
+    -- account, `mutual` for friends and connections
+    direction TEXT NOT NULL CHECK (direction IN ('following', 'follower', 'mutual')),
+    name TEXT NOT NULL,
+    url TEXT,
+    timestamp INTEGER
+);
+CREATE INDEX follows_account ON follows (account_id, direction);
+
+CREATE TABLE likes (
+    id INTEGER PRIMARY KEY,
+    account_id INTEGER NOT NULL REFERENCES accounts (id) ON DELETE CASCADE,
+    -- Address or id of what was liked
+    target TEXT,
+    title TEXT,
+    -- Reaction used, for platforms with more than likes
+    reaction TEXT,
+    timestamp INTEGER
+);
+CREATE INDEX likes_account ON likes (account_id, timestamp);
+
+CREATE TABLE saved_items (
+    id INTEGER PRIMARY KEY,
+    account_id INTEGER NOT NULL REFERENCES accounts (id) ON DELETE CASCADE,
+    -- Address or id of what was saved
+    target TEXT,
+    title TEXT,
+    collection TEXT,
+    timestamp INTEGER
+);
+CREATE INDEX saved_items_account ON saved_items (account_id, timestamp);
+
+CREATE TABLE logins (
+    id INTEGER PRIMARY KEY,
+    account_id INTEGER NOT NULL REFERENCES accounts (id) ON DELETE CASCADE,
+    timestamp INTEGER,
+    ip_address TEXT,
+    device TEXT,
+    location TEXT
+);
+CREATE INDEX logins_account ON logins (account_id, timestamp);
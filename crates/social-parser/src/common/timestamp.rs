@@ -0,0 +1,332 @@
+use std::{
+    fmt,
+    marker::PhantomData,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Unit a timestamp is stored in by the archive
+pub trait TimeUnit {
+    /// Number of milliseconds in one unit
+    const MILLIS: i64;
+}
+
+/// Unix timestamp in seconds
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct Seconds;
+
+impl TimeUnit for Seconds {
+    const MILLIS: i64 = 1000;
+}
+
+/// Unix timestamp in milliseconds
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct Milliseconds;
+
+impl TimeUnit for Milliseconds {
+    const MILLIS: i64 = 1;
+}
+
+/// Unix timestamp as found in an archive, stored in the unit `U` it was exported in.
+/// Serializes back to the same integer so saved archives keep their original shape.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct ArchiveTimestamp<U = Seconds> {
+    value: i64,
+    unit: PhantomData<U>,
+}
+
+impl<U: TimeUnit> ArchiveTimestamp<U> {
+    pub fn new(value: i64) -> Self {
+        Self {
+            value,
+            unit: PhantomData,
+        }
+    }
+
+    /// Raw value in the unit the archive stored it in
+    pub fn value(&self) -> i64 {
+        self.value
+    }
+
+    /// Milliseconds since the Unix epoch
+    pub fn as_millis(&self) -> i64 {
+        self.value.saturating_mul(U::MILLIS)
+    }
+
+    /// Whole seconds since the Unix epoch
+    pub fn as_secs(&self) -> i64 {
+        self.as_millis().div_euclid(1000)
+    }
+
+    /// Same instant in another unit, rounding down when the unit is coarser
+    pub fn convert<V: TimeUnit>(&self) -> ArchiveTimestamp<V> {
+        ArchiveTimestamp::new(self.as_millis().div_euclid(V::MILLIS))
+    }
+
+    /// Format as an ISO 8601 UTC date time like `2024-01-31T11:00:00Z`, with milliseconds
+    /// only when they aren't zero, e.g. `2024-01-31T11:00:00.123Z`
+    pub fn to_iso8601(&self) -> String {
+        let millis = self.as_millis();
+        let (year, month, day) = civil_from_days(millis.div_euclid(86_400_000));
+        let time = millis.rem_euclid(86_400_000);
+        let (seconds, fraction) = (time / 1000, time % 1000);
+
+        let mut s = format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year,
+            month,
+            day,
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        );
+        if fraction != 0 {
+            s.push_str(&format!(".{:03}", fraction));
+        }
+        s.push('Z');
+        s
+    }
+
+    pub fn to_system_time(&self) -> SystemTime {
+        let millis = self.as_millis();
+        let offset = Duration::from_millis(millis.unsigned_abs());
+        if millis >= 0 {
+            UNIX_EPOCH + offset
+        } else {
+            UNIX_EPOCH - offset
+        }
+    }
+
+    /// Convert to a UTC `chrono` date time. Returns `None` if out of range.
+    #[cfg(feature = "chrono")]
+    pub fn to_chrono(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::from_timestamp_millis(self.as_millis())
+    }
+
+    /// Convert to a UTC `time` date time. Returns `None` if out of range.
+    #[cfg(feature = "time")]
+    pub fn to_offset_date_time(&self) -> Option<time::OffsetDateTime> {
+        time::OffsetDateTime::from_unix_timestamp_nanos(i128::from(self.as_millis()) * 1_000_000)
+            .ok()
+    }
+}
+
+impl ArchiveTimestamp<Milliseconds> {
+    /// Parse an ISO 8601 date time like `2024-01-31T12:00:00.123+01:00`, as used by archives
+    /// that store timestamps as strings. A space may replace the `T`, the time may be left out,
+    /// and `Z`, ` UTC` or no suffix at all are read as UTC.
+    pub fn parse_iso8601(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let s = s.strip_suffix(" UTC").unwrap_or(s);
+        if s.len() < 10 || !s.is_char_boundary(10) {
+            return None;
+        }
+        let (date, rest) = s.split_at(10);
+
+        let mut date_parts = date.split('-');
+        let year: i64 = date_parts.next()?.parse().ok()?;
+        let month: i64 = date_parts.next()?.parse().ok()?;
+        let day: i64 = date_parts.next()?.parse().ok()?;
+        if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+            return None;
+        }
+        let mut millis = days_from_civil(year, month, day) * 86_400_000;
+
+        if rest.is_empty() {
+            return Some(Self::new(millis));
+        }
+        let rest = rest.strip_prefix(['T', ' '])?;
+
+        let (time, offset) = match rest.find(['Z', 'z', '+', '-']) {
+            Some(index) => rest.split_at(index),
+            None => (rest, ""),
+        };
+
+        let mut time_parts = time.trim_end().split(':');
+        let hours: i64 = time_parts.next()?.parse().ok()?;
+        let minutes: i64 = time_parts.next()?.parse().ok()?;
+        let (seconds, fraction) = match time_parts.next() {
+            Some(seconds) => seconds.split_once('.').unwrap_or((seconds, "")),
+            None => ("0", ""),
+        };
+        let seconds: i64 = seconds.parse().ok()?;
+        if hours > 23 || minutes > 59 || seconds > 60 || time_parts.next().is_some() {
+            return None;
+        }
+        let fraction: String = fraction.chars().chain("000".chars()).take(3).collect();
+        let fraction: i64 = fraction.parse().ok()?;
+        millis += ((hours * 60 + minutes) * 60 + seconds) * 1000 + fraction;
+
+        match offset {
+            "" | "Z" | "z" => {}
+            offset => {
+                let sign = if offset.starts_with('-') { -1 } else { 1 };
+                let digits: String = offset[1..].chars().filter(|c| *c != ':').collect();
+                if (digits.len() != 2 && digits.len() != 4)
+                    || !digits.chars().all(|c| c.is_ascii_digit())
+                {
+                    return None;
+                }
+                let offset_hours: i64 = digits.get(..2)?.parse().ok()?;
+                let offset_minutes: i64 = digits.get(2..).unwrap_or("0").parse().ok()?;
+                millis -= sign * (offset_hours * 60 + offset_minutes) * 60_000;
+            }
+        }
+
+        Some(Self::new(millis))
+    }
+}
+
+/// Number of days in a month of the proleptic Gregorian calendar
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since the Unix epoch for a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Proleptic Gregorian date of a number of days since the Unix epoch, the inverse of
+/// [`days_from_civil`]
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (year_of_era * 365 + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+impl<U: TimeUnit> From<ArchiveTimestamp<U>> for SystemTime {
+    fn from(timestamp: ArchiveTimestamp<U>) -> Self {
+        timestamp.to_system_time()
+    }
+}
+
+impl<U: TimeUnit> fmt::Debug for ArchiveTimestamp<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ArchiveTimestamp({}ms)", self.as_millis())
+    }
+}
+
+impl<U: TimeUnit> Serialize for ArchiveTimestamp<U> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.value)
+    }
+}
+
+impl<'de, U: TimeUnit> Deserialize<'de> for ArchiveTimestamp<U> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        i64::deserialize(deserializer).map(Self::new)
+    }
+}
+
+/// Timestamps are plain numbers in JS, in the unit the archive stored them in
+#[cfg(feature = "typescript")]
+impl<U> ts_rs::TS for ArchiveTimestamp<U> {
+    type WithoutGenerics = ArchiveTimestamp;
+    type OptionInnerType = Self;
+
+    fn name(_: &ts_rs::Config) -> String {
+        String::from("number")
+    }
+
+    fn inline(cfg: &ts_rs::Config) -> String {
+        Self::name(cfg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Option<i64> {
+        ArchiveTimestamp::parse_iso8601(s).map(|t| t.as_millis())
+    }
+
+    #[test]
+    fn parses_iso8601() {
+        assert_eq!(parse("1970-01-01"), Some(0));
+        assert_eq!(parse("2020-09-13T12:26:40Z"), Some(1_600_000_000_000));
+        assert_eq!(
+            parse("2020-09-13 12:26:40.123 UTC"),
+            Some(1_600_000_000_123)
+        );
+        assert_eq!(parse("2020-09-13T14:26:40+02:00"), Some(1_600_000_000_000));
+        assert_eq!(parse("2020-09-13T07:26:40-0500"), Some(1_600_000_000_000));
+        assert_eq!(parse("2020-09-13T12:26"), Some(1_599_999_960_000));
+        assert_eq!(parse("2024-02-29T00:00:00Z"), Some(1_709_164_800_000));
+    }
+
+    #[test]
+    fn rejects_invalid_iso8601() {
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("2024-13-01"), None);
+        assert_eq!(parse("2024-02-30"), None);
+        assert_eq!(parse("2023-02-29"), None);
+        assert_eq!(parse("2024-04-31"), None);
+        assert_eq!(parse("2024-01-31T24:00:00Z"), None);
+        assert_eq!(parse("2024-01-31T12:00:00+aéb"), None);
+        assert_eq!(parse("2024-01-31T12:00:00+é"), None);
+        assert_eq!(parse("2024-01-31T12:00:00+1"), None);
+        assert_eq!(parse("2024-01-3é"), None);
+    }
+
+    #[test]
+    fn converts_civil_dates() {
+        for (date, days) in [
+            ((1970, 1, 1), 0),
+            ((1969, 12, 31), -1),
+            ((2000, 2, 29), 11_016),
+            ((2000, 3, 1), 11_017),
+            ((1900, 3, 1), -25_508),
+            ((1, 1, 1), -719_162),
+            ((9999, 12, 31), 2_932_896),
+        ] {
+            assert_eq!(days_from_civil(date.0, date.1, date.2), days);
+            assert_eq!(civil_from_days(days), date);
+        }
+        for days in -800_000..800_000 {
+            let (year, month, day) = civil_from_days(days);
+            assert!((1..=days_in_month(year, month)).contains(&day));
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn formats_iso8601() {
+        let format = |millis| ArchiveTimestamp::<Milliseconds>::new(millis).to_iso8601();
+        assert_eq!(format(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format(1_600_000_000_123), "2020-09-13T12:26:40.123Z");
+        assert_eq!(format(-1), "1969-12-31T23:59:59.999Z");
+        assert_eq!(format(951_782_400_000), "2000-02-29T00:00:00Z");
+        assert_eq!(
+            ArchiveTimestamp::<Seconds>::new(1_600_000_000).to_iso8601(),
+            "2020-09-13T12:26:40Z"
+        );
+        for millis in [0, 1_600_000_000_123, -1, 253_402_300_799_000] {
+            assert_eq!(parse(&format(millis)), Some(millis));
+        }
+    }
+}
@@ -7,7 +7,7 @@ pub mod platforms;
 
 #[cfg(feature = "typescript")]
 mod typescript {
-    use bindings::typescript;
+    use crate::bindings::typescript;
     use neon::prelude::*;
 
     #[neon::main]
@@ -23,18 +23,11 @@ mod typescript {
 mod python {
     use pyo3::prelude::*;
 
-    /// Formats the sum of two numbers as string.
-    #[pyfunction]
-    #[pyo3(text_signature = "(a, b) -> str")]
-    fn sum_as_string(a: usize, b: usize) -> PyResult<String> {
-        Ok((a + b).to_string())
-    }
+    use crate::bindings::python;
 
-    /// A Python module implemented in Rust.
+    /// Library to process archive bundles from social platforms
     #[pymodule]
     fn social_parser(m: &Bound<'_, PyModule>) -> PyResult<()> {
-        m.add_function(wrap_pyfunction!(sum_as_string, m)?)?;
-        // TODO: Add the rest of the functions here
-        Ok(())
+        python::init_module(m)
     }
 }
@@ -0,0 +1,421 @@
+//! Just enough DAG-CBOR and CID handling to read a repository export. DAG-CBOR is the
+//! deterministic subset of CBOR used by IPLD: no indefinite lengths, string map keys, and
+//! links to other blocks stored as tag 42.
+
+use std::fmt;
+
+use serde_json::{Map, Number, Value};
+
+/// Deepest nesting accepted, so a malformed block can't overflow the stack
+const MAX_DEPTH: usize = 128;
+
+/// Content identifier of a block, kept as its binary form
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub(crate) struct Cid(Vec<u8>);
+
+impl Cid {
+    /// Read a binary CIDv1 from the start of `bytes`, returning it with the rest. atproto
+    /// doesn't use CIDv0.
+    pub(crate) fn read(bytes: &[u8]) -> Option<(Cid, &[u8])> {
+        let (version, rest) = read_varint(bytes)?;
+        if version != 1 {
+            return None;
+        }
+        let (_codec, rest) = read_varint(rest)?;
+        let (_hash, rest) = read_varint(rest)?;
+        let (length, rest) = read_varint(rest)?;
+        let (_digest, rest) = rest.split_at_checked(usize::try_from(length).ok()?)?;
+
+        let length = bytes.len() - rest.len();
+        Some((Cid(bytes[..length].to_vec()), rest))
+    }
+}
+
+impl fmt::Display for Cid {
+    /// Lowercase base32 with the `b` multibase prefix, e.g. `bafyrei...`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+        let mut text = String::from("b");
+        let mut buffer = 0u32;
+        let mut bits = 0;
+        for byte in &self.0 {
+            buffer = (buffer << 8) | u32::from(*byte);
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                text.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+            }
+        }
+        if bits > 0 {
+            text.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+        }
+        f.write_str(&text)
+    }
+}
+
+/// Decoded DAG-CBOR value
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) enum Cbor {
+    Null,
+    Bool(bool),
+    Integer(i128),
+    Float(f64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<Cbor>),
+    Map(Vec<(String, Cbor)>),
+    Link(Cid),
+}
+
+impl Cbor {
+    /// Decode a whole block, which holds exactly one value
+    pub(crate) fn decode(bytes: &[u8]) -> Option<Cbor> {
+        let (value, rest) = decode_value(bytes, 0)?;
+        rest.is_empty().then_some(value)
+    }
+
+    /// Value of a map entry
+    pub(crate) fn get(&self, key: &str) -> Option<&Cbor> {
+        match self {
+            Cbor::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Convert to JSON following the atproto conventions: links become `{"$link": cid}`
+    /// and bytes become `{"$bytes": base64}`
+    pub(crate) fn to_json(&self) -> Value {
+        match self {
+            Cbor::Null => Value::Null,
+            Cbor::Bool(b) => Value::Bool(*b),
+            Cbor::Integer(i) => i64::try_from(*i)
+                .map(Number::from)
+                .or_else(|_| u64::try_from(*i).map(Number::from))
+                .map_or(Value::Null, Value::Number),
+            Cbor::Float(f) => Number::from_f64(*f).map_or(Value::Null, Value::Number),
+            Cbor::Bytes(bytes) => tagged("$bytes", base64(bytes)),
+            Cbor::Text(s) => Value::String(s.clone()),
+            Cbor::Array(items) => Value::Array(items.iter().map(Cbor::to_json).collect()),
+            Cbor::Map(entries) => Value::Object(
+                entries
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.to_json()))
+                    .collect(),
+            ),
+            Cbor::Link(cid) => tagged("$link", cid.to_string()),
+        }
+    }
+}
+
+fn tagged(key: &str, value: String) -> Value {
+    let mut map = Map::new();
+    map.insert(key.to_owned(), Value::String(value));
+    Value::Object(map)
+}
+
+/// Unsigned LEB128 integer, as used by CAR files and CIDs
+pub(crate) fn read_varint(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let mut value = 0u64;
+    for (index, byte) in bytes.iter().enumerate().take(10) {
+        value |= u64::from(byte & 0x7f) << (7 * index);
+        if byte & 0x80 == 0 {
+            return Some((value, &bytes[index + 1..]));
+        }
+    }
+    None
+}
+
+fn decode_value(bytes: &[u8], depth: usize) -> Option<(Cbor, &[u8])> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+
+    let (&initial, rest) = bytes.split_first()?;
+    let major = initial >> 5;
+    let info = initial & 0x1f;
+
+    if major == 7 {
+        return match info {
+            20 => Some((Cbor::Bool(false), rest)),
+            21 => Some((Cbor::Bool(true), rest)),
+            22 | 23 => Some((Cbor::Null, rest)),
+            26 => {
+                let (float, rest) = rest.split_first_chunk::<4>()?;
+                Some((Cbor::Float(f32::from_be_bytes(*float).into()), rest))
+            }
+            27 => {
+                let (float, rest) = rest.split_first_chunk::<8>()?;
+                Some((Cbor::Float(f64::from_be_bytes(*float)), rest))
+            }
+            _ => None,
+        };
+    }
+
+    let (argument, rest) = match info {
+        0..=23 => (u64::from(info), rest),
+        24 => {
+            let (n, rest) = rest.split_first_chunk::<1>()?;
+            (u64::from(n[0]), rest)
+        }
+        25 => {
+            let (n, rest) = rest.split_first_chunk::<2>()?;
+            (u64::from(u16::from_be_bytes(*n)), rest)
+        }
+        26 => {
+            let (n, rest) = rest.split_first_chunk::<4>()?;
+            (u64::from(u32::from_be_bytes(*n)), rest)
+        }
+        27 => {
+            let (n, rest) = rest.split_first_chunk::<8>()?;
+            (u64::from_be_bytes(*n), rest)
+        }
+        // Indefinite lengths aren't allowed in DAG-CBOR
+        _ => return None,
+    };
+
+    match major {
+        0 => Some((Cbor::Integer(argument.into()), rest)),
+        1 => Some((Cbor::Integer(-1 - i128::from(argument)), rest)),
+        2 => {
+            let (data, rest) = rest.split_at_checked(usize::try_from(argument).ok()?)?;
+            Some((Cbor::Bytes(data.to_vec()), rest))
+        }
+        3 => {
+            let (data, rest) = rest.split_at_checked(usize::try_from(argument).ok()?)?;
+            Some((Cbor::Text(String::from_utf8(data.to_vec()).ok()?), rest))
+        }
+        4 => {
+            // Every item takes at least a byte, which bounds the allocation
+            let length = usize::try_from(argument).ok()?;
+            let mut items = Vec::with_capacity(length.min(rest.len()));
+            let mut rest = rest;
+            for _ in 0..length {
+                let (item, remaining) = decode_value(rest, depth + 1)?;
+                items.push(item);
+                rest = remaining;
+            }
+            Some((Cbor::Array(items), rest))
+        }
+        5 => {
+            let length = usize::try_from(argument).ok()?;
+            let mut entries = Vec::with_capacity(length.min(rest.len()));
+            let mut rest = rest;
+            for _ in 0..length {
+                let (Cbor::Text(key), remaining) = decode_value(rest, depth + 1)? else {
+                    return None;
+                };
+                let (value, remaining) = decode_value(remaining, depth + 1)?;
+                entries.push((key, value));
+                rest = remaining;
+            }
+            Some((Cbor::Map(entries), rest))
+        }
+        // Links are byte strings prefixed with the identity multibase, 0x00
+        6 if argument == 42 => match decode_value(rest, depth + 1)? {
+            (Cbor::Bytes(bytes), rest) => {
+                let (cid, remaining) = Cid::read(bytes.strip_prefix(&[0])?)?;
+                remaining.is_empty().then_some((Cbor::Link(cid), rest))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Standard base64 without padding, as atproto writes bytes in JSON
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let buffer = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |buffer, (index, byte)| {
+                buffer | u32::from(*byte) << (16 - 8 * index)
+            });
+        for index in 0..=chunk.len() {
+            text.push(ALPHABET[((buffer >> (18 - 6 * index)) & 63) as usize] as char);
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    /// Major type and argument, in the shortest form
+    fn head(major: u8, argument: u64) -> Vec<u8> {
+        let major = major << 5;
+        match argument {
+            0..=23 => vec![major | argument as u8],
+            24..=0xff => vec![major | 24, argument as u8],
+            0x100..=0xffff => [vec![major | 25], (argument as u16).to_be_bytes().to_vec()].concat(),
+            _ => [vec![major | 26], (argument as u32).to_be_bytes().to_vec()].concat(),
+        }
+    }
+
+    pub(crate) fn int(value: u64) -> Vec<u8> {
+        head(0, value)
+    }
+
+    pub(crate) fn bytes(value: &[u8]) -> Vec<u8> {
+        [head(2, value.len() as u64), value.to_vec()].concat()
+    }
+
+    pub(crate) fn text(value: &str) -> Vec<u8> {
+        [head(3, value.len() as u64), value.as_bytes().to_vec()].concat()
+    }
+
+    pub(crate) fn array(items: &[Vec<u8>]) -> Vec<u8> {
+        [head(4, items.len() as u64), items.concat()].concat()
+    }
+
+    pub(crate) fn map(entries: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let mut encoded = head(5, entries.len() as u64);
+        for (key, value) in entries {
+            encoded.extend(text(key));
+            encoded.extend(value);
+        }
+        encoded
+    }
+
+    /// Link to a binary CID
+    pub(crate) fn link(cid: &[u8]) -> Vec<u8> {
+        [vec![0xd8, 42], bytes(&[&[0], cid].concat())].concat()
+    }
+
+    /// Binary DAG-CBOR CIDv1 with a SHA-256 digest filled with `byte`
+    pub(crate) fn cid(byte: u8) -> Vec<u8> {
+        [vec![0x01, 0x71, 0x12, 0x20], vec![byte; 32]].concat()
+    }
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn formats_cids_as_base32() {
+        for (binary, text) in [
+            (
+                "01711220c7d01489080858c500065836c658f847a6ca67c4864619212be4f8200e4bbace",
+                "bafyreigh2akiscaildcqabsyg3dfr6chu3fgpregiymsck7e7aqa4s52zy",
+            ),
+            (
+                "01701220c3c4733ec8affd06cf9e9ff50ffc6bcd2ec85a6170004bb709669c31de94391a",
+                "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+            ),
+        ] {
+            let binary = hex(binary);
+            let (cid, rest) = Cid::read(&binary).unwrap();
+            assert!(rest.is_empty());
+            assert_eq!(cid.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_cids() {
+        let binary = cid(7);
+        for length in 0..binary.len() {
+            assert_eq!(Cid::read(&binary[..length]), None);
+        }
+        // CIDv0 is a bare multihash
+        assert_eq!(Cid::read(&binary[2..]), None);
+    }
+
+    #[test]
+    fn reads_varints() {
+        assert_eq!(read_varint(&[0x05, 0xff]), Some((5, &[0xff][..])));
+        assert_eq!(read_varint(&[0xac, 0x02]), Some((300, &[][..])));
+        assert_eq!(read_varint(&[0xac]), None);
+        assert_eq!(read_varint(&[0x80; 11]), None);
+    }
+
+    #[test]
+    fn decodes_values() {
+        let block = map(&[
+            ("text", text("héllo")),
+            ("count", int(300)),
+            ("negative", vec![0x38, 0x63]),
+            ("flags", array(&[vec![0xf4], vec![0xf5], vec![0xf6]])),
+            (
+                "float",
+                [vec![0xfb], 1.5f64.to_be_bytes().to_vec()].concat(),
+            ),
+            ("data", bytes(b"foobar!")),
+            ("ref", link(&cid(1))),
+        ]);
+        let value = Cbor::decode(&block).unwrap();
+        assert_eq!(value.get("count"), Some(&Cbor::Integer(300)));
+        assert_eq!(
+            value.to_json(),
+            json!({
+                "text": "héllo",
+                "count": 300,
+                "negative": -100,
+                "flags": [false, true, null],
+                "float": 1.5,
+                "data": {"$bytes": "Zm9vYmFyIQ"},
+                "ref": {"$link": Cid(cid(1)).to_string()},
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_input() {
+        let block = map(&[
+            ("text", text("hello")),
+            ("items", array(&[int(1), int(1000), bytes(b"abc")])),
+            ("ref", link(&cid(1))),
+        ]);
+        assert!(Cbor::decode(&block).is_some());
+        for length in 0..block.len() {
+            assert_eq!(Cbor::decode(&block[..length]), None, "length {}", length);
+        }
+        // Trailing bytes after the value
+        assert_eq!(Cbor::decode(&[block, vec![0]].concat()), None);
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        // Indefinite-length array
+        assert_eq!(Cbor::decode(&[0x9f, 0x01, 0xff]), None);
+        // Map with an integer key
+        assert_eq!(Cbor::decode(&[0xa1, 0x01, 0x02]), None);
+        // Invalid UTF-8 text
+        assert_eq!(Cbor::decode(&[0x62, 0xc3, 0x28]), None);
+        // Tag other than 42, and a link that isn't bytes
+        assert_eq!(Cbor::decode(&[0xc1, 0x01]), None);
+        assert_eq!(Cbor::decode(&[0xd8, 42, 0x01]), None);
+        // Huge lengths with little data
+        assert_eq!(
+            Cbor::decode(&[0x9b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]),
+            None
+        );
+        assert_eq!(Cbor::decode(&[0x5a, 0xff, 0xff, 0xff, 0xff]), None);
+    }
+
+    #[test]
+    fn limits_nesting_depth() {
+        let nested = |depth: usize| [vec![0x81; depth], vec![0x01]].concat();
+        assert!(Cbor::decode(&nested(MAX_DEPTH)).is_some());
+        assert_eq!(Cbor::decode(&nested(MAX_DEPTH + 1)), None);
+        assert_eq!(Cbor::decode(&nested(100_000)), None);
+    }
+
+    #[test]
+    fn encodes_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg");
+        assert_eq!(base64(b"fo"), "Zm8");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(&[0xfb, 0xff]), "+/8");
+    }
+}
@@ -0,0 +1,270 @@
+//! Bluesky repository export, downloaded from Settings > Account > Export my data.
+//!
+//! The export is a single CAR file holding the account's atproto repository: a signed
+//! commit pointing at a Merkle Search Tree whose leaves are DAG-CBOR records. It is
+//! decoded offline, without checking signatures or hashes. Posts, likes, reposts, follows,
+//! blocks and the profile are read into typed records keeping their AT-URIs and CIDs.
+//! Images and videos are blobs that aren't part of the export, so attachments refer to
+//! them by CID. Records that don't match their schema are skipped with a warning.
+
+pub mod records;
+
+mod cbor;
+mod repo;
+
+use std::{
+    fs,
+    path::{absolute, Path},
+};
+
+use cbor::Cid;
+use records::{Account, FeedPost, Profile, Record, Subject};
+use repo::Repository;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::common::{
+    AccountInfo, Archive, Comment, Dataset, Follow, FollowDirection, Like, LoadOptions, ParseError,
+    Post, Sections,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct BlueskyArchive {
+    /// DID of the account, e.g. `did:plc:abc`
+    pub did: Option<String>,
+    /// Revision of the repository at the time of the export
+    pub rev: Option<String>,
+    pub profile: Option<Record<Profile>>,
+    pub posts: Option<Vec<Record<FeedPost>>>,
+    pub likes: Option<Vec<Record<Subject>>>,
+    pub reposts: Option<Vec<Record<Subject>>>,
+    pub follows: Option<Vec<Record<Account>>>,
+    pub blocks: Option<Vec<Record<Account>>>,
+}
+
+impl Archive for BlueskyArchive {
+    /// Load from the exported CAR file, only parsing the sections selected in `options`
+    fn load<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Expected a CAR file, found: {:?}",
+                absolute(path)
+            )));
+        }
+
+        options.enter_section("BlueskyArchive", path)?;
+
+        let bytes = path.metadata().map(|m| m.len()).unwrap_or(0);
+        let repository = options.load_with(path, bytes, || {
+            Repository::read(&fs::read(path)?).map_err(|e| match e {
+                ParseError::UnexpectedFormat(message) => {
+                    ParseError::UnexpectedFormat(format!("{}: {:?}", message, absolute(path)))
+                }
+                e => e,
+            })
+        })?;
+
+        let load_profile = options.should_load(Sections::PROFILE, path);
+        let mut archive = BlueskyArchive {
+            did: repository.did.clone(),
+            rev: repository.rev.clone(),
+            profile: None,
+            posts: options.should_load(Sections::POSTS, path).then(Vec::new),
+            likes: options.should_load(Sections::LIKES, path).then(Vec::new),
+            reposts: options.should_load(Sections::POSTS, path).then(Vec::new),
+            follows: options
+                .should_load(Sections::FOLLOWERS_AND_FOLLOWING, path)
+                .then(Vec::new),
+            blocks: options
+                .should_load(Sections::CONNECTIONS, path)
+                .then(Vec::new),
+        };
+        let reader = RecordReader {
+            repository: &repository,
+            options,
+            path,
+            did: archive.did.as_deref().unwrap_or_default(),
+        };
+
+        let records = repository.records(|cid| {
+            options.warn(&path.join(cid.to_string()), "Skipped missing tree node");
+        });
+        let mut skipped: Vec<&str> = vec![];
+        for (key, cid) in &records {
+            options.check_cancelled()?;
+
+            let collection = key.split_once('/').map_or(key.as_str(), |(c, _)| c);
+            match collection {
+                "app.bsky.actor.profile" => {
+                    if load_profile {
+                        archive.profile = reader.read(key, cid);
+                    }
+                }
+                "app.bsky.feed.post" => extend(&mut archive.posts, || reader.read(key, cid)),
+                "app.bsky.feed.like" => extend(&mut archive.likes, || reader.read(key, cid)),
+                "app.bsky.feed.repost" => extend(&mut archive.reposts, || reader.read(key, cid)),
+                "app.bsky.graph.follow" => extend(&mut archive.follows, || reader.read(key, cid)),
+                "app.bsky.graph.block" => extend(&mut archive.blocks, || reader.read(key, cid)),
+                _ => {
+                    // Logged once per collection, e.g. `app.bsky.graph.list`
+                    if !skipped.contains(&collection) {
+                        skipped.push(collection);
+                        options.skip(&path.join(collection), "Skipped unsupported collection");
+                    }
+                }
+            }
+        }
+
+        Ok(archive)
+    }
+
+    fn to_dataset(&self) -> Dataset {
+        Dataset {
+            account: AccountInfo {
+                username: self.did.clone(),
+                display_name: self.display_name().map(str::to_owned),
+                email: None,
+            },
+            posts: self.normalized_posts(),
+            comments: self.normalized_comments(),
+            follows: self
+                .follows
+                .iter()
+                .flatten()
+                .map(|follow| Follow {
+                    direction: FollowDirection::Following,
+                    name: follow.value.subject.clone(),
+                    url: Some(format!("https://bsky.app/profile/{}", follow.value.subject)),
+                    timestamp: follow.value.timestamp(),
+                })
+                .collect(),
+            likes: self
+                .likes
+                .iter()
+                .flatten()
+                .map(|like| Like {
+                    target: Some(like.value.subject.uri.clone()),
+                    title: None,
+                    reaction: None,
+                    timestamp: like.value.timestamp(),
+                })
+                .collect(),
+            ..Dataset::new("bluesky")
+        }
+    }
+}
+
+impl BlueskyArchive {
+    /// Display name of the account, from its profile
+    pub fn display_name(&self) -> Option<&str> {
+        self.profile.as_ref()?.value.display_name.as_deref()
+    }
+
+    /// Posts made by the account, normalized. Replies are left out, see
+    /// [`BlueskyArchive::normalized_comments`]. Posts are attributed to the account's DID
+    /// and attachments refer to blobs by CID.
+    pub fn normalized_posts(&self) -> Vec<Post> {
+        self.posts
+            .iter()
+            .flatten()
+            .filter(|post| post.value.reply.is_none())
+            .map(|post| Post {
+                id: Some(post.uri.clone()),
+                author: self.did.clone(),
+                timestamp: post.value.timestamp(),
+                title: None,
+                text: Some(post.value.text.clone()).filter(|text| !text.is_empty()),
+                url: self.web_url(post),
+                link: post
+                    .value
+                    .embed
+                    .as_ref()
+                    .and_then(|embed| embed.link())
+                    .map(str::to_owned),
+                community: None,
+                attachments: post.value.attachments(),
+            })
+            .collect()
+    }
+
+    /// Replies made by the account, normalized, including replies in the account's own
+    /// threads
+    pub fn normalized_comments(&self) -> Vec<Comment> {
+        self.posts
+            .iter()
+            .flatten()
+            .filter_map(|post| {
+                let reply = post.value.reply.as_ref()?;
+                Some(Comment {
+                    id: Some(post.uri.clone()),
+                    author: self.did.clone(),
+                    timestamp: post.value.timestamp(),
+                    text: post.value.text.clone(),
+                    url: self.web_url(post),
+                    post: Some(reply.root.uri.clone()),
+                    parent: Some(reply.parent.uri.clone()),
+                    community: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Address of a post on the Bluesky web app
+    fn web_url(&self, post: &Record<FeedPost>) -> Option<String> {
+        let did = self.did.as_deref()?;
+        Some(format!(
+            "https://bsky.app/profile/{}/post/{}",
+            did,
+            post.rkey()
+        ))
+    }
+}
+
+impl TryFrom<&Path> for BlueskyArchive {
+    type Error = ParseError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        BlueskyArchive::load(path, &LoadOptions::default())
+    }
+}
+
+/// Decodes records of a repository into their typed models
+struct RecordReader<'a> {
+    repository: &'a Repository,
+    options: &'a LoadOptions,
+    path: &'a Path,
+    did: &'a str,
+}
+
+impl RecordReader<'_> {
+    /// Record stored under `key`, or `None` with a warning if its block is missing or
+    /// doesn't match the model
+    fn read<T: DeserializeOwned>(&self, key: &str, cid: &Cid) -> Option<Record<T>> {
+        let path = self.path.join(key);
+        let Some(block) = self.repository.block(cid) else {
+            self.options.warn(&path, "Skipped missing record");
+            return None;
+        };
+
+        match serde_json::from_value(block.to_json()) {
+            Ok(value) => Some(Record {
+                uri: format!("at://{}/{}", self.did, key),
+                cid: cid.to_string(),
+                value,
+            }),
+            Err(e) => {
+                self.options
+                    .warn(&path, format!("Skipped invalid record: {}", e));
+                None
+            }
+        }
+    }
+}
+
+/// Add a record to a section, if the section is selected
+fn extend<T>(section: &mut Option<Vec<T>>, read: impl FnOnce() -> Option<T>) {
+    if let Some(records) = section {
+        records.extend(read());
+    }
+}
@@ -0,0 +1,253 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::{ArchiveTimestamp, Attachment, AttachmentKind, Milliseconds};
+
+/// Record stored in the repository, with its address and content hash
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Record<T> {
+    /// AT-URI, e.g. `at://did:plc:abc/app.bsky.feed.post/3k2a...`
+    pub uri: String,
+    /// CID of the record's block, e.g. `bafyrei...`
+    pub cid: String,
+    pub value: T,
+}
+
+impl<T> Record<T> {
+    /// Record key, the last part of the AT-URI
+    pub fn rkey(&self) -> &str {
+        self.uri.rsplit('/').next().unwrap_or_default()
+    }
+}
+
+/// Reference to a specific version of another record
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct StrongRef {
+    pub uri: String,
+    pub cid: String,
+}
+
+/// Uploaded file. Blobs aren't part of the repository export and are fetched separately
+/// by CID.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct Blob {
+    #[serde(rename = "ref")]
+    pub reference: Option<Link>,
+    /// CID of blobs written in the legacy format
+    pub cid: Option<String>,
+    pub mime_type: Option<String>,
+    pub size: Option<u64>,
+}
+
+impl Blob {
+    pub fn cid(&self) -> Option<&str> {
+        self.reference
+            .as_ref()
+            .map(|link| link.link.as_str())
+            .or(self.cid.as_deref())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Link {
+    #[serde(rename = "$link")]
+    pub link: String,
+}
+
+/// `app.bsky.feed.post`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct FeedPost {
+    #[serde(default)]
+    pub text: String,
+    /// e.g. `2023-05-01T12:00:00.000Z`
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub langs: Vec<String>,
+    pub reply: Option<ReplyRef>,
+    pub embed: Option<Embed>,
+    /// Mentions, links and hashtags in the text
+    #[serde(default)]
+    pub facets: Vec<Facet>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl FeedPost {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_created_at(&self.created_at)
+    }
+
+    /// Images and video embedded in the post. Their URIs are blob CIDs.
+    pub fn attachments(&self) -> Vec<Attachment> {
+        self.embed
+            .as_ref()
+            .map(Embed::attachments)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ReplyRef {
+    /// First post of the thread
+    pub root: StrongRef,
+    /// Post being replied to
+    pub parent: StrongRef,
+}
+
+/// Media or link attached to a post. Which fields are set depends on `embed_type`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct Embed {
+    /// e.g. `app.bsky.embed.images` or `app.bsky.embed.recordWithMedia`
+    #[serde(rename = "$type")]
+    pub embed_type: Option<String>,
+    #[serde(default)]
+    pub images: Vec<EmbedImage>,
+    pub external: Option<External>,
+    pub video: Option<Blob>,
+    /// Alt text of the video
+    pub alt: Option<String>,
+    /// Quoted record, a strong reference or, with media, an object holding one
+    pub record: Option<serde_json::Value>,
+    /// Media of a quote post
+    pub media: Option<Box<Embed>>,
+}
+
+impl Embed {
+    fn attachments(&self) -> Vec<Attachment> {
+        let images = self.images.iter().filter_map(|image| {
+            Some(Attachment {
+                kind: AttachmentKind::Photo,
+                uri: image.image.cid()?.to_owned(),
+                name: Some(image.alt.clone()).filter(|alt| !alt.is_empty()),
+                created_at: None,
+            })
+        });
+        let video = self.video.iter().filter_map(|video| {
+            Some(Attachment {
+                kind: AttachmentKind::Video,
+                uri: video.cid()?.to_owned(),
+                name: self.alt.clone().filter(|alt| !alt.is_empty()),
+                created_at: None,
+            })
+        });
+
+        let mut attachments: Vec<Attachment> = images.chain(video).collect();
+        if let Some(media) = &self.media {
+            attachments.extend(media.attachments());
+        }
+        attachments
+    }
+
+    /// External link the post shares
+    pub fn link(&self) -> Option<&str> {
+        self.external
+            .as_ref()
+            .map(|external| external.uri.as_str())
+            .or_else(|| self.media.as_ref()?.link())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct EmbedImage {
+    #[serde(default)]
+    pub alt: String,
+    pub image: Blob,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct External {
+    pub uri: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub thumb: Option<Blob>,
+}
+
+/// Rich text annotation over a range of the post's UTF-8 bytes
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Facet {
+    pub index: ByteSlice,
+    #[serde(default)]
+    pub features: Vec<FacetFeature>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct ByteSlice {
+    pub byte_start: u64,
+    pub byte_end: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct FacetFeature {
+    /// `app.bsky.richtext.facet#mention`, `#link` or `#tag`
+    #[serde(rename = "$type")]
+    pub feature_type: Option<String>,
+    /// DID of a mentioned account
+    pub did: Option<String>,
+    pub uri: Option<String>,
+    pub tag: Option<String>,
+}
+
+/// `app.bsky.feed.like` or `app.bsky.feed.repost`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct Subject {
+    pub subject: StrongRef,
+    pub created_at: Option<String>,
+}
+
+impl Subject {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_created_at(&self.created_at)
+    }
+}
+
+/// `app.bsky.graph.follow` or `app.bsky.graph.block`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct Account {
+    /// DID of the followed or blocked account
+    pub subject: String,
+    pub created_at: Option<String>,
+}
+
+impl Account {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_created_at(&self.created_at)
+    }
+}
+
+/// `app.bsky.actor.profile`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub avatar: Option<Blob>,
+    pub banner: Option<Blob>,
+    pub pinned_post: Option<StrongRef>,
+    pub created_at: Option<String>,
+}
+
+fn parse_created_at(created_at: &Option<String>) -> Option<ArchiveTimestamp<Milliseconds>> {
+    created_at
+        .as_deref()
+        .and_then(ArchiveTimestamp::parse_iso8601)
+}
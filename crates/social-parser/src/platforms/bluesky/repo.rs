@@ -0,0 +1,289 @@
+//! Reading the blocks of a CAR file and walking the Merkle Search Tree (MST) that indexes
+//! the records of a repository
+
+use std::collections::HashMap;
+
+use super::cbor::{read_varint, Cbor, Cid};
+use crate::common::{ItemCount, ParseError};
+
+/// Deepest tree accepted, well beyond what any real repository reaches
+const MAX_DEPTH: usize = 64;
+
+/// Blocks of a repository export, keyed by their CID
+pub(crate) struct Repository {
+    pub(crate) did: Option<String>,
+    pub(crate) rev: Option<String>,
+    /// Root of the MST, from the signed commit
+    data: Option<Cid>,
+    blocks: HashMap<Cid, Vec<u8>>,
+}
+
+impl Repository {
+    /// Read a CAR v1 file: a length-prefixed header naming the root commit, followed by
+    /// length-prefixed blocks of a CID and its data
+    pub(crate) fn read(bytes: &[u8]) -> Result<Repository, ParseError> {
+        let invalid = || ParseError::UnexpectedFormat("Invalid CAR file".to_owned());
+
+        let (header, mut rest) = read_section(bytes).ok_or_else(invalid)?;
+        let header = Cbor::decode(header).ok_or_else(invalid)?;
+        if !matches!(header.get("version"), Some(Cbor::Integer(1))) {
+            return Err(ParseError::UnexpectedFormat(
+                "Only CAR version 1 is supported".to_owned(),
+            ));
+        }
+        let root = match header.get("roots") {
+            Some(Cbor::Array(roots)) => match roots.first() {
+                Some(Cbor::Link(cid)) => Some(cid.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let mut blocks = HashMap::new();
+        while !rest.is_empty() {
+            let (block, remaining) = read_section(rest).ok_or_else(invalid)?;
+            let (cid, data) = Cid::read(block).ok_or_else(invalid)?;
+            blocks.insert(cid, data.to_vec());
+            rest = remaining;
+        }
+
+        let commit = root
+            .and_then(|root| blocks.get(&root))
+            .and_then(|block| Cbor::decode(block))
+            .ok_or_else(|| ParseError::UnexpectedFormat("Missing repository commit".to_owned()))?;
+        let text = |key| match commit.get(key) {
+            Some(Cbor::Text(s)) => Some(s.clone()),
+            _ => None,
+        };
+
+        Ok(Repository {
+            did: text("did"),
+            rev: text("rev"),
+            data: match commit.get("data") {
+                Some(Cbor::Link(cid)) => Some(cid.clone()),
+                _ => None,
+            },
+            blocks,
+        })
+    }
+
+    /// Every record in the repository as its key, e.g. `app.bsky.feed.post/3k2a...`, and
+    /// the CID of its block, in key order. Subtrees missing from the file are passed to
+    /// `on_missing` and skipped.
+    pub(crate) fn records(&self, mut on_missing: impl FnMut(&Cid)) -> Vec<(String, Cid)> {
+        let mut records = vec![];
+        if let Some(data) = &self.data {
+            self.walk(data, 0, &mut records, &mut on_missing);
+        }
+        records
+    }
+
+    /// Decoded block of a record
+    pub(crate) fn block(&self, cid: &Cid) -> Option<Cbor> {
+        Cbor::decode(self.blocks.get(cid)?)
+    }
+
+    /// Visit a tree node: its left subtree, then each entry followed by the subtree to its
+    /// right. Keys are compressed against the previous entry of the node, sharing `p`
+    /// bytes with it and adding `k`.
+    fn walk(
+        &self,
+        node: &Cid,
+        depth: usize,
+        records: &mut Vec<(String, Cid)>,
+        on_missing: &mut impl FnMut(&Cid),
+    ) {
+        let Some(block) = self.block(node).filter(|_| depth < MAX_DEPTH) else {
+            on_missing(node);
+            return;
+        };
+
+        if let Some(Cbor::Link(left)) = block.get("l") {
+            self.walk(left, depth + 1, records, on_missing);
+        }
+
+        let Some(Cbor::Array(entries)) = block.get("e") else {
+            return;
+        };
+        let mut key: Vec<u8> = vec![];
+        for entry in entries {
+            let (Some(Cbor::Integer(prefix)), Some(Cbor::Bytes(suffix)), Some(Cbor::Link(value))) =
+                (entry.get("p"), entry.get("k"), entry.get("v"))
+            else {
+                continue;
+            };
+
+            key.truncate(usize::try_from(*prefix).unwrap_or(0));
+            key.extend_from_slice(suffix);
+            records.push((String::from_utf8_lossy(&key).into_owned(), value.clone()));
+
+            if let Some(Cbor::Link(right)) = entry.get("t") {
+                self.walk(right, depth + 1, records, on_missing);
+            }
+        }
+    }
+}
+
+impl ItemCount for Repository {
+    fn item_count(&self) -> usize {
+        self.blocks.len()
+    }
+}
+
+/// Varint length followed by that many bytes
+fn read_section(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    let (length, rest) = read_varint(bytes)?;
+    rest.split_at_checked(usize::try_from(length).ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::bluesky::cbor::tests::{array, bytes, cid, int, link, map, text};
+
+    fn varint(mut value: usize) -> Vec<u8> {
+        let mut encoded = vec![];
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                encoded.push(byte);
+                return encoded;
+            }
+            encoded.push(byte | 0x80);
+        }
+    }
+
+    /// CAR file with the commit as its root, followed by `blocks`
+    fn car(blocks: &[(Vec<u8>, Vec<u8>)]) -> Vec<u8> {
+        let commit = cid(0);
+        let header = map(&[("version", int(1)), ("roots", array(&[link(&commit)]))]);
+        let commit_block = map(&[
+            ("did", text("did:plc:abc")),
+            ("rev", text("3k2a")),
+            ("data", link(&cid(1))),
+        ]);
+
+        let mut file = [varint(header.len()), header].concat();
+        for (cid, data) in [(commit, commit_block)].iter().chain(blocks) {
+            file.extend(varint(cid.len() + data.len()));
+            file.extend(cid);
+            file.extend(data);
+        }
+        file
+    }
+
+    fn entry(prefix: u64, suffix: &str, value: u8, right: Option<u8>) -> Vec<u8> {
+        let mut entries = vec![
+            ("p", int(prefix)),
+            ("k", bytes(suffix.as_bytes())),
+            ("v", link(&cid(value))),
+        ];
+        if let Some(right) = right {
+            entries.push(("t", link(&cid(right))));
+        }
+        map(&entries)
+    }
+
+    fn node(left: Option<u8>, entries: &[Vec<u8>]) -> Vec<u8> {
+        let mut node = vec![("e", array(entries))];
+        if let Some(left) = left {
+            node.push(("l", link(&cid(left))));
+        }
+        map(&node)
+    }
+
+    fn keys(repository: &Repository) -> (Vec<String>, usize) {
+        let mut missing = 0;
+        let records = repository.records(|_| missing += 1);
+        (records.into_iter().map(|(key, _)| key).collect(), missing)
+    }
+
+    #[test]
+    fn walks_tree_in_key_order() {
+        let file = car(&[
+            (
+                cid(1),
+                node(
+                    Some(2),
+                    &[
+                        entry(0, "app.bsky.feed.post/b", 10, Some(3)),
+                        entry(19, "d", 11, None),
+                    ],
+                ),
+            ),
+            (
+                cid(2),
+                node(None, &[entry(0, "app.bsky.feed.like/a", 12, None)]),
+            ),
+            (
+                cid(3),
+                node(None, &[entry(0, "app.bsky.feed.post/c", 13, None)]),
+            ),
+        ]);
+        let repository = Repository::read(&file).unwrap();
+        assert_eq!(repository.did.as_deref(), Some("did:plc:abc"));
+        assert_eq!(repository.rev.as_deref(), Some("3k2a"));
+        assert_eq!(
+            keys(&repository),
+            (
+                vec![
+                    "app.bsky.feed.like/a".to_owned(),
+                    "app.bsky.feed.post/b".to_owned(),
+                    "app.bsky.feed.post/c".to_owned(),
+                    "app.bsky.feed.post/d".to_owned(),
+                ],
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn skips_missing_subtrees() {
+        let file = car(&[(
+            cid(1),
+            node(Some(2), &[entry(0, "app.bsky.feed.post/b", 10, None)]),
+        )]);
+        let repository = Repository::read(&file).unwrap();
+        assert_eq!(
+            keys(&repository),
+            (vec!["app.bsky.feed.post/b".to_owned()], 1)
+        );
+    }
+
+    #[test]
+    fn limits_tree_depth() {
+        // A node that is its own left subtree
+        let file = car(&[(cid(1), node(Some(1), &[]))]);
+        let repository = Repository::read(&file).unwrap();
+        assert_eq!(keys(&repository), (vec![], 1));
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        let tree = node(None, &[]);
+        let file = car(&[(cid(1), tree.clone())]);
+        assert!(Repository::read(&file).is_ok());
+
+        // Cut right before the tree block, the file is valid but has no tree
+        let section = cid(1).len() + tree.len();
+        let boundary = file.len() - varint(section).len() - section;
+        let repository = Repository::read(&file[..boundary]).unwrap();
+        assert_eq!(keys(&repository), (vec![], 1));
+
+        for length in (0..file.len()).filter(|length| *length != boundary) {
+            assert!(
+                Repository::read(&file[..length]).is_err(),
+                "length {}",
+                length
+            );
+        }
+
+        let header = map(&[("version", int(2)), ("roots", array(&[]))]);
+        let file = [varint(header.len()), header].concat();
+        assert!(matches!(
+            Repository::read(&file),
+            Err(ParseError::UnexpectedFormat(message)) if message.contains("version 1")
+        ));
+    }
+}
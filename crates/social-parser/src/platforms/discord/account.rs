@@ -0,0 +1,130 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{read_json, ItemCount, LoadOptions, ParseError};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Account {
+    pub user: Option<User>,
+}
+
+impl Account {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Account, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in Account: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("Account", path)?;
+
+        let mut user = None;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("user.json") => {
+                    user = Some(options.load_file::<User>(&path)?);
+                }
+                Some(name) if name.starts_with("avatar.") => {}
+                _ => {
+                    options.skip(&path, "Skipped unsupported file");
+                }
+            }
+        }
+
+        Ok(Account { user })
+    }
+}
+
+impl TryFrom<&Path> for Account {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Account, Self::Error> {
+        Account::load(path, &LoadOptions::default())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct User {
+    pub id: String,
+    pub username: String,
+    /// `"0"` for accounts migrated to unique usernames
+    pub discriminator: Option<serde_json::Value>,
+    pub global_name: Option<String>,
+    pub email: Option<String>,
+    pub verified: Option<bool>,
+    pub phone: Option<String>,
+    pub premium_until: Option<String>,
+    /// Accounts linked from other services, e.g. Spotify or GitHub
+    #[serde(default)]
+    pub connections: Vec<ConnectedAccount>,
+    /// Friends, blocked users and pending friend requests
+    #[serde(default)]
+    pub relationships: Vec<Relationship>,
+}
+
+impl TryFrom<&Path> for User {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<User, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in User: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for User {
+    fn item_count(&self) -> usize {
+        1
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ConnectedAccount {
+    /// Service name, e.g. `spotify`
+    #[serde(rename = "type")]
+    pub service: String,
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub revoked: bool,
+    #[serde(default)]
+    pub verified: bool,
+    pub visibility: Option<u8>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Relationship {
+    pub id: String,
+    /// 1 for friends, 2 for blocked, 3 and 4 for incoming and outgoing requests
+    #[serde(rename = "type")]
+    pub relationship_type: u8,
+    pub nickname: Option<String>,
+    pub user: RelationshipUser,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct RelationshipUser {
+    pub id: String,
+    pub username: String,
+    pub global_name: Option<String>,
+}
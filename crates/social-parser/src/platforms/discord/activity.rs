@@ -0,0 +1,148 @@
+use std::{collections::BTreeMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{
+    read_json_lines, ArchiveTimestamp, ItemCount, LoadOptions, Milliseconds, ParseError,
+};
+
+/// Event streams Discord collected about the account, one folder per purpose
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Activity {
+    pub analytics: Option<EventStream>,
+    pub modeling: Option<EventStream>,
+    pub reporting: Option<EventStream>,
+    pub tns: Option<EventStream>,
+}
+
+impl Activity {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Activity, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in Activity: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("Activity", path)?;
+
+        let mut analytics = None;
+        let mut modeling = None;
+        let mut reporting = None;
+        let mut tns = None;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            let stream = match path.file_name().and_then(|s| s.to_str()) {
+                Some("analytics") => &mut analytics,
+                Some("modeling") => &mut modeling,
+                Some("reporting") => &mut reporting,
+                Some("tns") => &mut tns,
+                _ => {
+                    options.warn(&path, "Skipped unsupported section");
+                    continue;
+                }
+            };
+            *stream = Some(EventStream::load(&path, options)?);
+        }
+
+        Ok(Activity {
+            analytics,
+            modeling,
+            reporting,
+            tns,
+        })
+    }
+}
+
+impl TryFrom<&Path> for Activity {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Activity, Self::Error> {
+        Activity::load(path, &LoadOptions::default())
+    }
+}
+
+/// Events from every `events-*.json` file in a folder, in file order
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct EventStream(pub Vec<AnalyticsEvent>);
+
+impl EventStream {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<EventStream, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in EventStream: {:?}",
+                path
+            )));
+        }
+
+        let mut files = vec![];
+        for entry in path.read_dir()? {
+            let path = entry?.path();
+            if path.extension().and_then(|s| s.to_str()) == Some("json") {
+                files.push(path);
+            } else {
+                options.warn(&path, "Skipped unsupported file");
+            }
+        }
+        // Split into `events-2024-00000-of-00002.json`, ...
+        files.sort();
+
+        let mut events = EventStream::default();
+        for file in files {
+            events.0.extend(options.load_file::<EventStream>(&file)?.0);
+        }
+        Ok(events)
+    }
+}
+
+impl TryFrom<&Path> for EventStream {
+    type Error = ParseError;
+
+    /// Load from a file with one event per line. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<EventStream, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in EventStream: {:?}",
+                path
+            )));
+        }
+
+        read_json_lines(path).map(EventStream)
+    }
+}
+
+impl ItemCount for EventStream {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct AnalyticsEvent {
+    /// e.g. `app_opened` or `message_reaction_added`
+    pub event_type: String,
+    pub event_id: Option<String>,
+    /// ISO 8601, wrapped in an extra pair of quotes
+    pub timestamp: Option<String>,
+    /// Event specific fields, which vary by `event_type`
+    #[serde(flatten)]
+    pub properties: BTreeMap<String, serde_json::Value>,
+}
+
+impl AnalyticsEvent {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        let timestamp = self.timestamp.as_deref()?.trim_matches('"');
+        ArchiveTimestamp::parse_iso8601(timestamp)
+    }
+}
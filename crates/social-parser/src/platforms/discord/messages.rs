@@ -0,0 +1,361 @@
+use std::{
+    collections::HashMap,
+    path::{absolute, Path},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{
+    read_csv, read_json, ArchiveTimestamp, Attachment, AttachmentKind, Conversation,
+    ConversationMessage, ItemCount, LoadOptions, Milliseconds, ParseError,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Messages {
+    pub index: Option<ChannelIndex>,
+    /// Channels keyed by id
+    pub channels: HashMap<String, Channel>,
+}
+
+impl Messages {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Messages, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in Messages: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("Messages", path)?;
+
+        let mut index = None;
+        let mut channels = HashMap::new();
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if path.is_dir() {
+                let channel = Channel::load(&path, options)?;
+                channels.insert(channel.channel.id.clone(), channel);
+                continue;
+            }
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("index.json") => {
+                    index = Some(options.load_file::<ChannelIndex>(&path)?);
+                }
+                _ => {
+                    options.warn(&path, "Skipped unsupported file");
+                }
+            }
+        }
+
+        Ok(Messages { index, channels })
+    }
+
+    /// Load from a directory, passing each channel to `on_conversation` with the path of
+    /// its messages file instead of keeping it. Messages are attributed to `sender`, like in
+    /// [`Messages::conversations`]. Assumes path is a directory.
+    pub fn stream<F>(
+        path: &Path,
+        options: &LoadOptions,
+        sender: &str,
+        mut on_conversation: F,
+    ) -> Result<(), ParseError>
+    where
+        F: FnMut(&Path, Conversation) -> Result<(), ParseError>,
+    {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in Messages: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("Messages", path)?;
+
+        // Read first, for the channel names
+        let index_path = path.join("index.json");
+        let index = if index_path.is_file() {
+            Some(options.load_file::<ChannelIndex>(&index_path)?)
+        } else {
+            None
+        };
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_dir() {
+                if path != index_path {
+                    options.warn(&path, "Skipped unsupported file");
+                }
+                continue;
+            }
+
+            let channel = Channel::load(&path, options)?;
+            let source = ["messages.json", "messages.csv"]
+                .into_iter()
+                .map(|name| path.join(name))
+                .find(|file| file.is_file())
+                .unwrap_or(path);
+            let title = title(index.as_ref(), &channel);
+            on_conversation(&source, channel.to_conversation(title, sender))?;
+        }
+
+        Ok(())
+    }
+
+    /// Every channel as a normalized conversation. The package only contains messages
+    /// sent by its owner, so they are all attributed to `sender`.
+    pub fn conversations(&self, sender: &str) -> Vec<Conversation> {
+        self.channels
+            .values()
+            .map(|channel| channel.to_conversation(title(self.index.as_ref(), channel), sender))
+            .collect()
+    }
+}
+
+impl TryFrom<&Path> for Messages {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Messages, Self::Error> {
+        Messages::load(path, &LoadOptions::default())
+    }
+}
+
+/// Name of a channel from the index, falling back to the name in its `channel.json`
+fn title(index: Option<&ChannelIndex>, channel: &Channel) -> Option<String> {
+    index
+        .and_then(|index| index.0.get(&channel.channel.id).cloned().flatten())
+        .or_else(|| channel.channel.name.clone())
+}
+
+/// Channel names keyed by id, e.g. "Direct Message with jane" or "general in Rustaceans".
+/// Names of deleted channels are `null`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChannelIndex(pub HashMap<String, Option<String>>);
+
+impl TryFrom<&Path> for ChannelIndex {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<ChannelIndex, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in ChannelIndex: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for ChannelIndex {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Channel {
+    pub channel: ChannelInfo,
+    pub messages: ChannelMessages,
+}
+
+impl Channel {
+    /// Load from a channel directory (`c<id>` or `<id>`), reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Channel, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in Channel: {:?}",
+                path
+            )));
+        }
+
+        options.check_cancelled()?;
+
+        let mut channel = None;
+        let mut messages = None;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("channel.json") => {
+                    channel = Some(options.load_file::<ChannelInfo>(&path)?);
+                }
+                // Newer packages export JSON, older ones CSV with the same columns
+                Some("messages.json") | Some("messages.csv") => {
+                    messages = Some(options.load_file::<ChannelMessages>(&path)?);
+                }
+                _ => {
+                    options.warn(&path, "Skipped unsupported file");
+                }
+            }
+        }
+
+        let channel = channel.ok_or_else(|| {
+            ParseError::UnexpectedFormat(format!(
+                "Expected channel.json in channel: {:?}",
+                absolute(path)
+            ))
+        })?;
+
+        Ok(Channel {
+            channel,
+            messages: messages.unwrap_or_default(),
+        })
+    }
+
+    /// Normalize into a conversation with every message sent by `sender`
+    pub fn to_conversation(&self, title: Option<String>, sender: &str) -> Conversation {
+        let mut messages: Vec<ConversationMessage> = self
+            .messages
+            .0
+            .iter()
+            .map(|message| message.to_message(sender))
+            .collect();
+        messages.sort_by_key(|message| message.timestamp);
+
+        Conversation {
+            id: self.channel.id.clone(),
+            title,
+            participants: self.channel.recipients.clone(),
+            messages,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChannelInfo {
+    pub id: String,
+    /// `DM`, `GROUP_DM`, `GUILD_TEXT`, ... or the numeric type in older packages
+    #[serde(rename = "type")]
+    pub channel_type: serde_json::Value,
+    pub name: Option<String>,
+    pub guild: Option<GuildRef>,
+    /// User ids of the people in a direct message
+    #[serde(default)]
+    pub recipients: Vec<String>,
+}
+
+impl TryFrom<&Path> for ChannelInfo {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<ChannelInfo, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in ChannelInfo: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for ChannelInfo {
+    fn item_count(&self) -> usize {
+        1
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct GuildRef {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChannelMessages(pub Vec<ChannelMessage>);
+
+impl TryFrom<&Path> for ChannelMessages {
+    type Error = ParseError;
+
+    /// Load from a JSON or CSV file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<ChannelMessages, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in ChannelMessages: {:?}",
+                path
+            )));
+        }
+
+        match path.extension().and_then(|s| s.to_str()) {
+            Some("csv") => read_csv(path).map(ChannelMessages),
+            _ => read_json(path).map(ChannelMessages),
+        }
+    }
+}
+
+impl ItemCount for ChannelMessages {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChannelMessage {
+    #[serde(rename = "ID")]
+    pub id: u64,
+    /// e.g. `2024-01-31 12:00:00` or `2021-01-31 12:00:00.123000+00:00` in UTC
+    #[serde(rename = "Timestamp")]
+    pub timestamp: String,
+    #[serde(rename = "Contents")]
+    pub contents: String,
+    /// Space separated attachment URLs
+    #[serde(rename = "Attachments")]
+    pub attachments: String,
+}
+
+impl ChannelMessage {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        ArchiveTimestamp::parse_iso8601(&self.timestamp)
+    }
+
+    /// Attachments resolved from their CDN URLs
+    pub fn attachments(&self) -> Vec<Attachment> {
+        self.attachments
+            .split_whitespace()
+            .map(|url| {
+                let name = url
+                    .split(['?', '#'])
+                    .next()
+                    .and_then(|url| url.rsplit('/').next())
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_owned);
+                Attachment {
+                    kind: AttachmentKind::from_uri(name.as_deref().unwrap_or(url)),
+                    uri: url.to_owned(),
+                    name,
+                    created_at: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Normalize into a conversation message sent by `sender`
+    pub fn to_message(&self, sender: &str) -> ConversationMessage {
+        let mut message = ConversationMessage::new(sender.to_owned(), self.timestamp());
+        message.id = Some(self.id.to_string());
+        message.content = Some(self.contents.clone()).filter(|contents| !contents.is_empty());
+        message.attachments = self.attachments();
+        message
+    }
+}
@@ -0,0 +1,187 @@
+//! Discord data package, requested from Settings > Data & Privacy.
+//!
+//! Discord adds fields to its package regularly, so these models accept unknown fields
+//! and unknown files are reported as warnings.
+
+pub mod account;
+pub mod activity;
+pub mod messages;
+pub mod servers;
+
+use std::path::{absolute, Path};
+
+use account::Account;
+use activity::Activity;
+use messages::Messages;
+use serde::{Deserialize, Serialize};
+use servers::Servers;
+
+use crate::common::{
+    AccountInfo, Archive, Conversation, Dataset, Follow, FollowDirection, LoadOptions, ParseError,
+    Sections, StreamingArchive,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct DiscordArchive {
+    pub account: Option<Account>,
+    pub messages: Option<Messages>,
+    pub servers: Option<Servers>,
+    pub activity: Option<Activity>,
+}
+
+impl Archive for DiscordArchive {
+    /// Load from a directory, only parsing the sections selected in `options`
+    fn load<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Expected a directory, found: {:?}",
+                absolute(path)
+            )));
+        }
+
+        options.enter_section("DiscordArchive", path)?;
+
+        let mut account = None;
+        let mut messages = None;
+        let mut servers = None;
+        let mut activity = None;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_dir() {
+                // The package ships a README.txt describing its contents
+                if path.ends_with("README.txt") {
+                    options.skip(&path, "Skipped unsupported file");
+                } else {
+                    options.warn(&path, "Skipped unexpected file");
+                }
+                continue;
+            }
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("account") => {
+                    if options.should_load(Sections::PROFILE, &path) {
+                        account = Some(Account::load(&path, options)?);
+                    }
+                }
+                Some("messages") => {
+                    if options.should_load(Sections::MESSAGES, &path) {
+                        messages = Some(Messages::load(&path, options)?);
+                    }
+                }
+                Some("servers") => {
+                    if options.should_load(Sections::GROUPS, &path) {
+                        servers = Some(Servers::load(&path, options)?);
+                    }
+                }
+                Some("activity") => {
+                    if options.should_load(Sections::ANALYTICS, &path) {
+                        activity = Some(Activity::load(&path, options)?);
+                    }
+                }
+                _ => {
+                    options.skip(&path, "Skipped unsupported section");
+                }
+            }
+        }
+
+        Ok(Self {
+            account,
+            messages,
+            servers,
+            activity,
+        })
+    }
+
+    fn to_dataset(&self) -> Dataset {
+        let mut dataset = Dataset::new("discord");
+        if let Some(user) = self
+            .account
+            .as_ref()
+            .and_then(|account| account.user.as_ref())
+        {
+            dataset.account = AccountInfo {
+                username: Some(user.username.clone()),
+                display_name: user.global_name.clone(),
+                email: user.email.clone(),
+            };
+            dataset.follows = user
+                .relationships
+                .iter()
+                .filter(|relationship| relationship.relationship_type == 1)
+                .map(|relationship| Follow {
+                    direction: FollowDirection::Mutual,
+                    name: relationship.user.username.clone(),
+                    url: None,
+                    timestamp: None,
+                })
+                .collect();
+        }
+        dataset.conversations = self.conversations();
+        dataset
+    }
+}
+
+impl StreamingArchive for DiscordArchive {
+    const PLATFORM: &'static str = "discord";
+
+    /// Load from a directory, passing the messages of each channel to `on_conversation`
+    /// instead of keeping them. Every other section is loaded first, so messages are
+    /// attributed to the account's username.
+    fn stream_conversations<P, F>(
+        path: P,
+        options: &LoadOptions,
+        on_conversation: F,
+    ) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&Path, Conversation) -> Result<(), ParseError>,
+    {
+        let path = path.as_ref();
+        let archive = DiscordArchive::load(
+            path,
+            &options
+                .clone()
+                .with_sections(options.sections - Sections::MESSAGES),
+        )?;
+
+        let messages = path.join("messages");
+        if messages.is_dir() && options.should_load(Sections::MESSAGES, &messages) {
+            Messages::stream(&messages, options, archive.sender(), on_conversation)?;
+        }
+
+        Ok(archive)
+    }
+}
+
+impl DiscordArchive {
+    /// Every channel as a normalized conversation, with messages attributed to the
+    /// account's username when the account section was loaded
+    pub fn conversations(&self) -> Vec<Conversation> {
+        self.messages
+            .as_ref()
+            .map(|messages| messages.conversations(self.sender()))
+            .unwrap_or_default()
+    }
+
+    /// Username of the account, which sent every message in the package
+    fn sender(&self) -> &str {
+        self.account
+            .as_ref()
+            .and_then(|account| account.user.as_ref())
+            .map(|user| user.username.as_str())
+            .unwrap_or("You")
+    }
+}
+
+impl TryFrom<&Path> for DiscordArchive {
+    type Error = ParseError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        DiscordArchive::load(path, &LoadOptions::default())
+    }
+}
@@ -0,0 +1,124 @@
+use std::{collections::HashMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{read_json, ItemCount, LoadOptions, ParseError};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Servers {
+    pub index: Option<ServerIndex>,
+    /// Servers keyed by id
+    pub guilds: HashMap<String, Guild>,
+}
+
+impl Servers {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Servers, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in Servers: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("Servers", path)?;
+
+        let mut index = None;
+        let mut guilds = HashMap::new();
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if path.is_dir() {
+                // Also contains the server's audit log, which isn't parsed
+                let guild_path = path.join("guild.json");
+                if guild_path.is_file() {
+                    let guild = options.load_file::<Guild>(&guild_path)?;
+                    guilds.insert(guild.id.clone(), guild);
+                } else {
+                    options.warn(&path, "Skipped server without guild.json");
+                }
+                continue;
+            }
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("index.json") => {
+                    index = Some(options.load_file::<ServerIndex>(&path)?);
+                }
+                _ => {
+                    options.warn(&path, "Skipped unsupported file");
+                }
+            }
+        }
+
+        Ok(Servers { index, guilds })
+    }
+}
+
+impl TryFrom<&Path> for Servers {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Servers, Self::Error> {
+        Servers::load(path, &LoadOptions::default())
+    }
+}
+
+/// Server names keyed by id
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ServerIndex(pub HashMap<String, String>);
+
+impl TryFrom<&Path> for ServerIndex {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<ServerIndex, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in ServerIndex: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for ServerIndex {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Guild {
+    pub id: String,
+    pub name: String,
+}
+
+impl TryFrom<&Path> for Guild {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<Guild, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in Guild: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for Guild {
+    fn item_count(&self) -> usize {
+        1
+    }
+}
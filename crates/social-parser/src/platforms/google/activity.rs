@@ -0,0 +1,135 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use super::parse_takeout_date;
+use crate::common::{html, read_json, ArchiveTimestamp, ItemCount, Milliseconds, ParseError};
+
+/// History in the "My Activity" format shared by Google products, e.g. YouTube watch and
+/// search history. Exported as `.json` or `.html` depending on the Takeout settings.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct MyActivity(pub Vec<ActivityRecord>);
+
+impl TryFrom<&Path> for MyActivity {
+    type Error = ParseError;
+
+    /// Load from a JSON or HTML file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<MyActivity, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in MyActivity: {:?}",
+                path
+            )));
+        }
+
+        match path.extension().and_then(|s| s.to_str()) {
+            Some("json") => read_json(path).map(MyActivity),
+            Some("html") => Ok(MyActivity::from_html(&fs::read_to_string(path)?)),
+            _ => Err(ParseError::UnexpectedFormat(format!(
+                "Expected a JSON or HTML file in MyActivity: {:?}",
+                path
+            ))),
+        }
+    }
+}
+
+impl ItemCount for MyActivity {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl MyActivity {
+    /// Parse the HTML version, where each record is an `outer-cell` with a header, a
+    /// content cell of `<br>` separated lines and a caption listing the products.
+    /// The HTML version doesn't include details, so those are left empty.
+    pub fn from_html(page: &str) -> MyActivity {
+        let records = page
+            .split("<div class=\"outer-cell")
+            .skip(1)
+            .filter_map(|cell| {
+                let header = html::inner(cell, "mdl-typography--title", "</p>").map(html::text);
+                let content = html::inner(cell, "mdl-typography--body-1", "</div>")?;
+
+                let lines: Vec<&str> = content
+                    .split("<br>")
+                    .filter(|line| !html::text(line).is_empty())
+                    .collect();
+                let (time, lines) = lines.split_last()?;
+                let (title, subtitles) = lines.split_first()?;
+
+                let products = html::inner(cell, "Products:</b>", "</div>")
+                    .map(|products| {
+                        products
+                            .split("<br>")
+                            .map(html::text)
+                            .filter(|product| !product.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                Some(ActivityRecord {
+                    header: header.unwrap_or_default(),
+                    title: html::text(title),
+                    title_url: html::links(title).into_iter().next().map(|(href, _)| href),
+                    subtitles: subtitles
+                        .iter()
+                        .map(|line| {
+                            let link = html::links(line).into_iter().next();
+                            Subtitle {
+                                name: html::text(line),
+                                url: link.map(|(href, _)| href),
+                            }
+                        })
+                        .collect(),
+                    time: html::text(time),
+                    products,
+                    details: vec![],
+                })
+            })
+            .collect();
+
+        MyActivity(records)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityRecord {
+    /// Product the record comes from, e.g. `YouTube`
+    pub header: String,
+    /// Action and its subject, e.g. `Watched Some video`
+    pub title: String,
+    pub title_url: Option<String>,
+    /// Related links, e.g. the channel of a watched video
+    #[serde(default)]
+    pub subtitles: Vec<Subtitle>,
+    /// ISO 8601 in JSON exports, a localized date in HTML exports
+    pub time: String,
+    #[serde(default)]
+    pub products: Vec<String>,
+    /// Extra context, e.g. `From Google Ads` for ads that were watched
+    #[serde(default)]
+    pub details: Vec<Detail>,
+}
+
+impl ActivityRecord {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        ArchiveTimestamp::parse_iso8601(&self.time).or_else(|| parse_takeout_date(&self.time))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Subtitle {
+    pub name: String,
+    pub url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Detail {
+    pub name: String,
+}
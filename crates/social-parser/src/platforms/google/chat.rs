@@ -0,0 +1,365 @@
+use std::{collections::HashMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use super::parse_takeout_date;
+use crate::common::{
+    read_json, Attachment, AttachmentKind, Conversation, ConversationMessage, ItemCount,
+    LoadOptions, MessageReaction, ParseError, ReplyTo,
+};
+
+/// Google Chat spaces and direct messages
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct GoogleChat {
+    /// Keyed by folder name, e.g. `DM abc123` or `Space xyz789`
+    pub groups: Option<HashMap<String, ChatGroup>>,
+}
+
+impl GoogleChat {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<GoogleChat, ParseError> {
+        let mut groups = None;
+        GoogleChat::read(path, options, |name, _, group| {
+            groups
+                .get_or_insert_with(HashMap::new)
+                .insert(name.to_owned(), group);
+            Ok(())
+        })?;
+
+        Ok(GoogleChat { groups })
+    }
+
+    /// Load from a directory one group at a time, passing each group's conversation to
+    /// `on_conversation` along with its `messages.json` file instead of keeping it
+    pub fn stream<F>(
+        path: &Path,
+        options: &LoadOptions,
+        mut on_conversation: F,
+    ) -> Result<(), ParseError>
+    where
+        F: FnMut(&Path, Conversation) -> Result<(), ParseError>,
+    {
+        GoogleChat::read(path, options, |name, path, group| {
+            let messages = path.join("messages.json");
+            let source = if messages.is_file() { &messages } else { path };
+            on_conversation(source, group.to_conversation(name))
+        })
+    }
+
+    /// Load each group in turn, calling `on_group` with its folder name and path
+    fn read<F>(path: &Path, options: &LoadOptions, mut on_group: F) -> Result<(), ParseError>
+    where
+        F: FnMut(&str, &Path, ChatGroup) -> Result<(), ParseError>,
+    {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in GoogleChat: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("GoogleChat", path)?;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("Groups") if path.is_dir() => {
+                    for entry in path.read_dir()? {
+                        let path = entry?.path();
+                        if !path.is_dir() {
+                            options.warn(&path, "Skipped unexpected file");
+                            continue;
+                        }
+                        if let Some(name) = path.file_name().and_then(|s| s.to_str()) {
+                            on_group(name, &path, ChatGroup::load(&path, options)?)?;
+                        }
+                    }
+                }
+                _ => {
+                    // `Users` only lists memberships, which the groups already include
+                    options.skip(&path, "Skipped unsupported section");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Every group as a normalized conversation. Attachment paths are relative to the
+    /// `Takeout` folder.
+    pub fn conversations(&self) -> Vec<Conversation> {
+        self.groups
+            .iter()
+            .flatten()
+            .map(|(id, group)| group.to_conversation(id))
+            .collect()
+    }
+}
+
+impl TryFrom<&Path> for GoogleChat {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<GoogleChat, Self::Error> {
+        GoogleChat::load(path, &LoadOptions::default())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChatGroup {
+    pub info: Option<GroupInfo>,
+    pub messages: Option<ChatMessages>,
+}
+
+impl ChatGroup {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<ChatGroup, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in ChatGroup: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("ChatGroup", path)?;
+
+        let mut info = None;
+        let mut messages = None;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("group_info.json") => {
+                    info = Some(options.load_file::<GroupInfo>(&path)?);
+                }
+                Some("messages.json") => {
+                    messages = Some(options.load_file::<ChatMessages>(&path)?);
+                }
+                _ => {
+                    // Everything else is a file attached to a message
+                }
+            }
+        }
+
+        Ok(ChatGroup { info, messages })
+    }
+
+    /// Normalize into a conversation identified by the group's folder name
+    pub fn to_conversation(&self, id: &str) -> Conversation {
+        let mut messages: Vec<ConversationMessage> = self
+            .messages
+            .iter()
+            .flat_map(|messages| &messages.messages)
+            .map(|message| message.to_message(id))
+            .collect();
+        messages.sort_by_key(|message| message.timestamp);
+
+        Conversation {
+            id: id.to_owned(),
+            title: self.info.as_ref().and_then(|info| info.name.clone()),
+            participants: self
+                .info
+                .iter()
+                .flat_map(|info| &info.members)
+                .map(ChatUser::display_name)
+                .collect(),
+            messages,
+        }
+    }
+}
+
+impl TryFrom<&Path> for ChatGroup {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<ChatGroup, Self::Error> {
+        ChatGroup::load(path, &LoadOptions::default())
+    }
+}
+
+/// `group_info.json`. Direct messages have no name.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct GroupInfo {
+    pub name: Option<String>,
+    #[serde(default)]
+    pub members: Vec<ChatUser>,
+}
+
+impl TryFrom<&Path> for GroupInfo {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<GroupInfo, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in GroupInfo: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for GroupInfo {
+    fn item_count(&self) -> usize {
+        self.members.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChatUser {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    /// `Human` or `Bot`
+    pub user_type: Option<String>,
+}
+
+impl ChatUser {
+    /// Name, falling back to the email address for deleted accounts
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .or_else(|| self.email.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// `messages.json`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChatMessages {
+    #[serde(default)]
+    pub messages: Vec<ChatMessage>,
+}
+
+impl TryFrom<&Path> for ChatMessages {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<ChatMessages, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in ChatMessages: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for ChatMessages {
+    fn item_count(&self) -> usize {
+        self.messages.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChatMessage {
+    pub creator: Option<ChatUser>,
+    /// e.g. `Monday, January 1, 2023 at 10:00:00 AM UTC`
+    pub created_date: Option<String>,
+    pub updated_date: Option<String>,
+    pub text: Option<String>,
+    pub message_id: Option<String>,
+    /// Thread within a space
+    pub topic_id: Option<String>,
+    #[serde(default)]
+    pub attached_files: Vec<AttachedFile>,
+    #[serde(default)]
+    pub reactions: Vec<ChatReaction>,
+    pub quoted_message_metadata: Option<QuotedMessage>,
+    /// Set on messages that were deleted
+    pub deletion_metadata: Option<serde_json::Value>,
+}
+
+impl ChatMessage {
+    fn to_message(&self, group: &str) -> ConversationMessage {
+        let timestamp = self.created_date.as_deref().and_then(parse_takeout_date);
+        let sender = self
+            .creator
+            .as_ref()
+            .map(ChatUser::display_name)
+            .unwrap_or_default();
+
+        let mut message = ConversationMessage::new(sender, timestamp);
+        message.id = self.message_id.clone();
+        message.content = self.text.clone().filter(|text| !text.is_empty());
+        message.is_unsent = self.deletion_metadata.is_some();
+        message.attachments = self
+            .attached_files
+            .iter()
+            .map(|file| Attachment {
+                kind: AttachmentKind::from_uri(&file.export_name),
+                uri: format!("Google Chat/Groups/{}/{}", group, file.export_name),
+                name: file.original_name.clone(),
+                created_at: None,
+            })
+            .collect();
+        message.reactions = self
+            .reactions
+            .iter()
+            .flat_map(|reaction| {
+                let emoji = reaction.emoji.unicode.clone().unwrap_or_default();
+                reaction
+                    .reactor_emails
+                    .iter()
+                    .map(move |actor| MessageReaction {
+                        reaction: emoji.clone(),
+                        actor: actor.clone(),
+                        timestamp: None,
+                    })
+            })
+            .collect();
+        message.reply_to = self.quoted_message_metadata.as_ref().map(|quoted| ReplyTo {
+            message_id: quoted.message_id.clone(),
+            sender: quoted.creator.as_ref().map(ChatUser::display_name),
+            content: quoted.text.clone(),
+        });
+
+        message
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct AttachedFile {
+    pub original_name: Option<String>,
+    /// Name of the file in the group's folder
+    pub export_name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChatReaction {
+    pub emoji: Emoji,
+    #[serde(default)]
+    pub reactor_emails: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Emoji {
+    /// Missing for custom emoji
+    pub unicode: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct QuotedMessage {
+    pub creator: Option<ChatUser>,
+    pub text: Option<String>,
+    pub message_id: Option<String>,
+}
@@ -0,0 +1,316 @@
+use std::{collections::HashMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{
+    read_json, ArchiveTimestamp, Attachment, AttachmentKind, Conversation, ConversationMessage,
+    ItemCount, LoadOptions, MessageKind, Milliseconds, ParseError,
+};
+
+/// Legacy Hangouts history, exported as a single `Hangouts.json`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Hangouts {
+    #[serde(default)]
+    pub conversations: Vec<HangoutsConversation>,
+}
+
+impl Hangouts {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Hangouts, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in Hangouts: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("Hangouts", path)?;
+
+        let mut hangouts = Hangouts::default();
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("Hangouts.json") => {
+                    hangouts = options.load_file(&path)?;
+                }
+                _ => {
+                    options.warn(&path, "Skipped unsupported file");
+                }
+            }
+        }
+
+        Ok(hangouts)
+    }
+
+    /// Load from a directory, passing each conversation to `on_conversation` along with
+    /// `Hangouts.json` instead of keeping them. The file is a single JSON document, so it
+    /// is still parsed in one go.
+    pub fn stream<F>(
+        path: &Path,
+        options: &LoadOptions,
+        mut on_conversation: F,
+    ) -> Result<(), ParseError>
+    where
+        F: FnMut(&Path, Conversation) -> Result<(), ParseError>,
+    {
+        let hangouts = Hangouts::load(path, options)?;
+        let source = path.join("Hangouts.json");
+        for conversation in &hangouts.conversations {
+            on_conversation(&source, conversation.to_conversation())?;
+        }
+        Ok(())
+    }
+
+    /// Every conversation in the history, normalized
+    pub fn conversations(&self) -> Vec<Conversation> {
+        self.conversations
+            .iter()
+            .map(HangoutsConversation::to_conversation)
+            .collect()
+    }
+}
+
+impl TryFrom<&Path> for Hangouts {
+    type Error = ParseError;
+
+    /// Load from a `Hangouts.json` file
+    fn try_from(path: &Path) -> Result<Hangouts, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in Hangouts: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for Hangouts {
+    fn item_count(&self) -> usize {
+        self.conversations
+            .iter()
+            .map(|conversation| conversation.events.len())
+            .sum()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct HangoutsConversation {
+    pub conversation: ConversationState,
+    #[serde(default)]
+    pub events: Vec<HangoutsEvent>,
+}
+
+impl HangoutsConversation {
+    pub fn to_conversation(&self) -> Conversation {
+        let details = &self.conversation.conversation;
+        let names: HashMap<&str, &str> = details
+            .participant_data
+            .iter()
+            .filter_map(|participant| {
+                Some((
+                    participant.id.gaia_id.as_str(),
+                    participant.fallback_name.as_deref()?,
+                ))
+            })
+            .collect();
+
+        let mut messages: Vec<ConversationMessage> = self
+            .events
+            .iter()
+            .map(|event| event.to_message(&names))
+            .collect();
+        messages.sort_by_key(|message| message.timestamp);
+
+        Conversation {
+            id: self.conversation.conversation_id.id.clone(),
+            title: details.name.clone(),
+            participants: details
+                .participant_data
+                .iter()
+                .map(|participant| {
+                    participant
+                        .fallback_name
+                        .clone()
+                        .unwrap_or_else(|| participant.id.gaia_id.clone())
+                })
+                .collect(),
+            messages,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ConversationState {
+    pub conversation_id: ConversationId,
+    pub conversation: ConversationDetails,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ConversationId {
+    pub id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ConversationDetails {
+    /// Only set for named group conversations
+    pub name: Option<String>,
+    #[serde(default)]
+    pub participant_data: Vec<Participant>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Participant {
+    pub id: ParticipantId,
+    pub fallback_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ParticipantId {
+    pub gaia_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct HangoutsEvent {
+    pub sender_id: Option<ParticipantId>,
+    /// Microseconds since the Unix epoch, as a string
+    pub timestamp: String,
+    pub event_id: Option<String>,
+    /// e.g. `REGULAR_CHAT_MESSAGE`, `HANGOUT_EVENT` or `RENAME_CONVERSATION`
+    pub event_type: Option<String>,
+    pub chat_message: Option<ChatMessage>,
+    pub hangout_event: Option<HangoutEvent>,
+}
+
+impl HangoutsEvent {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        let micros: i64 = self.timestamp.parse().ok()?;
+        Some(ArchiveTimestamp::new(micros / 1000))
+    }
+
+    fn to_message(&self, names: &HashMap<&str, &str>) -> ConversationMessage {
+        let sender = self
+            .sender_id
+            .as_ref()
+            .map(|id| {
+                names
+                    .get(id.gaia_id.as_str())
+                    .map(|name| name.to_string())
+                    .unwrap_or_else(|| id.gaia_id.clone())
+            })
+            .unwrap_or_default();
+
+        let mut message = ConversationMessage::new(sender, self.timestamp());
+        message.id = self.event_id.clone();
+
+        if let Some(content) = self.chat_message.as_ref().map(|chat| &chat.message_content) {
+            let text: String = content
+                .segment
+                .iter()
+                .map(|segment| match segment.segment_type.as_str() {
+                    "LINE_BREAK" => "\n",
+                    _ => segment.text.as_deref().unwrap_or_default(),
+                })
+                .collect();
+            message.content = Some(text).filter(|text| !text.is_empty());
+            message.attachments = content
+                .attachment
+                .iter()
+                .filter_map(|attachment| attachment.embed_item.plus_photo.as_ref())
+                .map(|photo| Attachment {
+                    kind: match photo.media_type.as_deref() {
+                        Some("VIDEO") => AttachmentKind::Video,
+                        Some("ANIMATED_PHOTO") => AttachmentKind::Gif,
+                        _ => AttachmentKind::Photo,
+                    },
+                    uri: photo.url.clone(),
+                    name: None,
+                    created_at: None,
+                })
+                .collect();
+        }
+
+        message.kind = match (&self.hangout_event, &self.chat_message) {
+            (Some(call), _) if call.event_type == "END_HANGOUT" => {
+                let duration_secs = call
+                    .hangout_duration_secs
+                    .as_deref()
+                    .and_then(|secs| secs.parse().ok());
+                MessageKind::Call {
+                    duration_secs,
+                    missed: duration_secs.is_none_or(|secs| secs == 0),
+                }
+            }
+            (_, Some(_)) => MessageKind::Text,
+            _ => MessageKind::System,
+        };
+
+        message
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChatMessage {
+    pub message_content: MessageContent,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct MessageContent {
+    #[serde(default)]
+    pub segment: Vec<Segment>,
+    #[serde(default)]
+    pub attachment: Vec<HangoutsAttachment>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Segment {
+    /// `TEXT`, `LINK` or `LINE_BREAK`
+    #[serde(rename = "type")]
+    pub segment_type: String,
+    pub text: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct HangoutsAttachment {
+    pub embed_item: EmbedItem,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct EmbedItem {
+    pub plus_photo: Option<PlusPhoto>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct PlusPhoto {
+    pub url: String,
+    /// `PHOTO`, `ANIMATED_PHOTO` or `VIDEO`
+    pub media_type: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct HangoutEvent {
+    /// `START_HANGOUT` or `END_HANGOUT`
+    pub event_type: String,
+    /// Seconds, as a string
+    pub hangout_duration_secs: Option<String>,
+}
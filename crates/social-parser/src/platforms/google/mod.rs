@@ -0,0 +1,300 @@
+//! Google Takeout export, limited to YouTube and Google Chat/Hangouts.
+//!
+//! Takeout mixes formats: activity history is JSON or HTML depending on the option chosen
+//! when requesting the export, YouTube lists are CSV, and chats are JSON. Like the other
+//! newer platforms, these models accept unknown fields and unknown files are reported as
+//! warnings.
+
+pub mod activity;
+pub mod chat;
+pub mod hangouts;
+pub mod youtube;
+
+use std::path::{absolute, Path, PathBuf};
+
+use chat::GoogleChat;
+use hangouts::Hangouts;
+use serde::{Deserialize, Serialize};
+use youtube::YouTube;
+
+use crate::common::{
+    Archive, ArchiveTimestamp, Comment, Conversation, Dataset, Follow, FollowDirection,
+    LoadOptions, Milliseconds, ParseError, SavedItem, Sections, StreamingArchive,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct GoogleArchive {
+    pub youtube: Option<YouTube>,
+    pub chat: Option<GoogleChat>,
+    pub hangouts: Option<Hangouts>,
+}
+
+impl Archive for GoogleArchive {
+    /// Load from the extracted `Takeout` directory or the directory containing it, only
+    /// parsing the sections selected in `options`
+    fn load<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Expected a directory, found: {:?}",
+                absolute(path)
+            )));
+        }
+
+        options.enter_section("GoogleArchive", path)?;
+
+        let takeout = takeout_dir(path);
+        let path = takeout.as_path();
+
+        let mut archive = GoogleArchive::default();
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_dir() {
+                // Takeout ships an `archive_browser.html` index
+                if path.ends_with("archive_browser.html") {
+                    options.skip(&path, "Skipped unsupported file");
+                } else {
+                    options.warn(&path, "Skipped unexpected file");
+                }
+                continue;
+            }
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("YouTube and YouTube Music") | Some("YouTube") => {
+                    archive.youtube = Some(YouTube::load(&path, options)?);
+                }
+                Some("Google Chat") => {
+                    if options.should_load(Sections::MESSAGES, &path) {
+                        archive.chat = Some(GoogleChat::load(&path, options)?);
+                    }
+                }
+                Some("Hangouts") => {
+                    if options.should_load(Sections::MESSAGES, &path) {
+                        archive.hangouts = Some(Hangouts::load(&path, options)?);
+                    }
+                }
+                _ => {
+                    options.skip(&path, "Skipped unsupported section");
+                }
+            }
+        }
+
+        Ok(archive)
+    }
+
+    fn to_dataset(&self) -> Dataset {
+        let mut dataset = Dataset::new("google");
+        dataset.conversations = self.conversations();
+
+        let Some(youtube) = &self.youtube else {
+            return dataset;
+        };
+        dataset.comments = youtube
+            .comments
+            .iter()
+            .flat_map(|comments| &comments.0)
+            .map(|comment| Comment {
+                id: Some(comment.comment_id.clone()),
+                author: comment.channel_id.clone(),
+                timestamp: comment.created_at(),
+                text: comment.text(),
+                url: None,
+                post: comment.video_id.clone().or_else(|| comment.post_id.clone()),
+                parent: comment.parent_comment_id.clone(),
+                community: None,
+            })
+            .collect();
+        dataset.follows = youtube
+            .subscriptions
+            .iter()
+            .flat_map(|subscriptions| &subscriptions.0)
+            .map(|subscription| Follow {
+                direction: FollowDirection::Following,
+                name: subscription.channel_title.clone(),
+                url: Some(subscription.channel_url.clone()),
+                timestamp: None,
+            })
+            .collect();
+        dataset.saved_items = youtube
+            .playlists
+            .iter()
+            .flat_map(|playlists| &playlists.0)
+            .flat_map(|(name, playlist)| {
+                let collection = playlist.info.as_ref().map_or(name, |info| &info.title);
+                playlist.videos.iter().map(move |video| SavedItem {
+                    target: Some(format!(
+                        "https://www.youtube.com/watch?v={}",
+                        video.video_id
+                    )),
+                    title: None,
+                    collection: Some(collection.clone()),
+                    timestamp: video.added_at(),
+                })
+            })
+            .collect();
+        dataset
+    }
+}
+
+impl StreamingArchive for GoogleArchive {
+    const PLATFORM: &'static str = "google";
+
+    /// Load from the extracted `Takeout` directory or the directory containing it, passing
+    /// each Google Chat group and Hangouts conversation to `on_conversation` instead of
+    /// keeping them
+    fn stream_conversations<P, F>(
+        path: P,
+        options: &LoadOptions,
+        mut on_conversation: F,
+    ) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&Path, Conversation) -> Result<(), ParseError>,
+    {
+        let path = path.as_ref();
+        let archive = GoogleArchive::load(
+            path,
+            &options
+                .clone()
+                .with_sections(options.sections - Sections::MESSAGES),
+        )?;
+
+        let takeout = takeout_dir(path);
+        let chat = takeout.join("Google Chat");
+        if chat.is_dir() && options.should_load(Sections::MESSAGES, &chat) {
+            GoogleChat::stream(&chat, options, &mut on_conversation)?;
+        }
+        let hangouts = takeout.join("Hangouts");
+        if hangouts.is_dir() && options.should_load(Sections::MESSAGES, &hangouts) {
+            Hangouts::stream(&hangouts, options, &mut on_conversation)?;
+        }
+
+        Ok(archive)
+    }
+}
+
+impl GoogleArchive {
+    /// Every Google Chat group and Hangouts conversation as a normalized conversation
+    pub fn conversations(&self) -> Vec<Conversation> {
+        let chat = self.chat.iter().flat_map(GoogleChat::conversations);
+        let hangouts = self.hangouts.iter().flat_map(Hangouts::conversations);
+
+        chat.chain(hangouts).collect()
+    }
+}
+
+impl TryFrom<&Path> for GoogleArchive {
+    type Error = ParseError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        GoogleArchive::load(path, &LoadOptions::default())
+    }
+}
+
+/// The `Takeout` directory inside `path`, or `path` itself when it was passed directly
+fn takeout_dir(path: &Path) -> PathBuf {
+    match path.join("Takeout") {
+        takeout if takeout.is_dir() => takeout,
+        _ => path.to_owned(),
+    }
+}
+
+/// Parse the English dates Takeout writes into HTML pages and chat exports, e.g.
+/// `Jan 1, 2023, 10:00:00 AM UTC` or `Monday, January 1, 2023 at 10:00:00 AM GMT+01:00`.
+/// Returns `None` for other languages and unknown time zone names.
+pub fn parse_takeout_date(s: &str) -> Option<ArchiveTimestamp<Milliseconds>> {
+    const MONTHS: [&str; 12] = [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ];
+
+    let mut year = None;
+    let mut month = None;
+    let mut day = None;
+    let mut time = None;
+    let mut pm = None;
+    let mut offset = None;
+
+    let s = s.replace([',', '\u{202f}', '\u{a0}'], " ");
+    for token in s.split_whitespace() {
+        if let Some(index) = MONTHS
+            .iter()
+            .position(|m| *m == token || (token.len() >= 3 && m.starts_with(token)))
+        {
+            month = Some(index + 1);
+        } else if token.contains(':') && time.is_none() {
+            time = Some(token);
+        } else if token.chars().all(|c| c.is_ascii_digit()) {
+            match token.len() {
+                4 => year = Some(token),
+                1 | 2 => day = Some(token.parse::<u32>().ok()?),
+                _ => return None,
+            }
+        } else if token.eq_ignore_ascii_case("AM") || token.eq_ignore_ascii_case("PM") {
+            pm = Some(token.eq_ignore_ascii_case("PM"));
+        } else if let Some(zone) = parse_zone(token) {
+            offset = Some(zone);
+        }
+    }
+
+    let mut time_parts = time?.split(':');
+    let mut hours: u32 = time_parts.next()?.parse().ok()?;
+    let minutes = time_parts.next()?;
+    let seconds = time_parts.next().unwrap_or("00");
+    match pm {
+        Some(true) if hours < 12 => hours += 12,
+        Some(false) if hours == 12 => hours = 0,
+        _ => {}
+    }
+
+    ArchiveTimestamp::parse_iso8601(&format!(
+        "{}-{:02}-{:02}T{:02}:{}:{}{}",
+        year?, month?, day?, hours, minutes, seconds, offset?
+    ))
+}
+
+/// UTC offset of a time zone token like `UTC`, `GMT+1` or `PST`, as `±HH:MM`
+fn parse_zone(token: &str) -> Option<String> {
+    const ZONES: [(&str, &str); 11] = [
+        ("UTC", "+00:00"),
+        ("GMT", "+00:00"),
+        ("BST", "+01:00"),
+        ("CET", "+01:00"),
+        ("CEST", "+02:00"),
+        ("EST", "-05:00"),
+        ("EDT", "-04:00"),
+        ("CST", "-06:00"),
+        ("CDT", "-05:00"),
+        ("PST", "-08:00"),
+        ("PDT", "-07:00"),
+    ];
+
+    if let Some((_, offset)) = ZONES.iter().find(|(zone, _)| *zone == token) {
+        return Some((*offset).to_owned());
+    }
+
+    let offset = token
+        .strip_prefix("GMT")
+        .or_else(|| token.strip_prefix("UTC"))?;
+    let sign = offset
+        .get(..1)
+        .filter(|sign| *sign == "+" || *sign == "-")?;
+    let (hours, minutes) = offset[1..].split_once(':').unwrap_or((&offset[1..], "00"));
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    Some(format!("{}{:02}:{:02}", sign, hours, minutes))
+}
@@ -0,0 +1,449 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::activity::MyActivity;
+use crate::common::{
+    read_csv, ArchiveTimestamp, ItemCount, LoadOptions, Milliseconds, ParseError, Sections,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct YouTube {
+    pub watch_history: Option<MyActivity>,
+    pub search_history: Option<MyActivity>,
+    pub comments: Option<Comments>,
+    pub subscriptions: Option<Subscriptions>,
+    pub playlists: Option<Playlists>,
+}
+
+impl YouTube {
+    /// Load from a directory, only parsing the sections selected in `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<YouTube, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in YouTube: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("YouTube", path)?;
+
+        let mut youtube = YouTube::default();
+        youtube.load_folder(path, options)?;
+        Ok(youtube)
+    }
+
+    fn load_folder(&mut self, path: &Path, options: &LoadOptions) -> Result<(), ParseError> {
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+            let name = path.file_name().and_then(|s| s.to_str());
+
+            if path.is_dir() {
+                match name {
+                    Some("history") | Some("comments") | Some("subscriptions") => {
+                        self.load_folder(&path, options)?;
+                    }
+                    Some("playlists") => {
+                        if options.should_load(Sections::SAVED, &path) {
+                            self.playlists = Some(Playlists::load(&path, options)?);
+                        }
+                    }
+                    _ => {
+                        options.skip(&path, "Skipped unsupported section");
+                    }
+                }
+                continue;
+            }
+
+            match name {
+                Some("watch-history.json") | Some("watch-history.html") => {
+                    if options.should_load(Sections::WATCH_HISTORY, &path) {
+                        self.watch_history = Some(options.load_file(&path)?);
+                    }
+                }
+                Some("search-history.json") | Some("search-history.html") => {
+                    if options.should_load(Sections::SEARCH_HISTORY, &path) {
+                        self.search_history = Some(options.load_file(&path)?);
+                    }
+                }
+                Some("comments.csv") => {
+                    if options.should_load(Sections::COMMENTS, &path) {
+                        self.comments = Some(options.load_file(&path)?);
+                    }
+                }
+                Some("subscriptions.csv") => {
+                    if options.should_load(Sections::FOLLOWERS_AND_FOLLOWING, &path) {
+                        self.subscriptions = Some(options.load_file(&path)?);
+                    }
+                }
+                _ => {
+                    options.skip(&path, "Skipped unsupported file");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<&Path> for YouTube {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<YouTube, Self::Error> {
+        YouTube::load(path, &LoadOptions::default())
+    }
+}
+
+/// Comments from `comments.csv`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Comments(pub Vec<Comment>);
+
+impl TryFrom<&Path> for Comments {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<Comments, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in Comments: {:?}",
+                path
+            )));
+        }
+
+        read_csv(path).map(Comments)
+    }
+}
+
+impl ItemCount for Comments {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Comment {
+    #[serde(rename = "Comment ID")]
+    pub comment_id: String,
+    #[serde(rename = "Channel ID", default)]
+    pub channel_id: Option<String>,
+    /// ISO 8601
+    #[serde(rename = "Comment Create Timestamp", default)]
+    pub created_at: Option<String>,
+    #[serde(rename = "Parent Comment ID", default)]
+    pub parent_comment_id: Option<String>,
+    /// Set for comments on community posts
+    #[serde(rename = "Post ID", default)]
+    pub post_id: Option<String>,
+    #[serde(rename = "Video ID", default)]
+    pub video_id: Option<String>,
+    /// Comma separated JSON segments, e.g. `{"text":"Great "},{"text":"video"}`
+    #[serde(rename = "Comment Text", default)]
+    pub content: String,
+}
+
+impl Comment {
+    pub fn created_at(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        self.created_at
+            .as_deref()
+            .and_then(ArchiveTimestamp::parse_iso8601)
+    }
+
+    /// Plain text of the comment, joining its segments
+    pub fn text(&self) -> String {
+        #[derive(Deserialize)]
+        struct Segment {
+            text: Option<String>,
+        }
+
+        match serde_json::from_str::<Vec<Segment>>(&format!("[{}]", self.content)) {
+            Ok(segments) => segments.into_iter().filter_map(|s| s.text).collect(),
+            Err(_) => self.content.clone(),
+        }
+    }
+}
+
+/// Channels subscribed to, from `subscriptions.csv`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Subscriptions(pub Vec<Subscription>);
+
+impl TryFrom<&Path> for Subscriptions {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<Subscriptions, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in Subscriptions: {:?}",
+                path
+            )));
+        }
+
+        read_csv(path).map(Subscriptions)
+    }
+}
+
+impl ItemCount for Subscriptions {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Subscription {
+    #[serde(rename = "Channel Id")]
+    pub channel_id: String,
+    #[serde(rename = "Channel Url")]
+    pub channel_url: String,
+    #[serde(rename = "Channel Title")]
+    pub channel_title: String,
+}
+
+/// Playlists keyed by title. Newer exports list playlists in `playlists.csv` with their
+/// videos in `<title>-videos.csv`, older ones have a single `<title>.csv` per playlist.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Playlists(pub BTreeMap<String, Playlist>);
+
+impl Playlists {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Playlists, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in Playlists: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("Playlists", path)?;
+
+        let mut playlists: BTreeMap<String, Playlist> = BTreeMap::new();
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            let Some(stem) = path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .and_then(|name| name.strip_suffix(".csv"))
+                .filter(|_| path.is_file())
+            else {
+                options.warn(&path, "Skipped unsupported file");
+                continue;
+            };
+
+            if stem == "playlists" {
+                let index = options.load_file::<PlaylistIndex>(&path)?;
+                for info in index.0 {
+                    let playlist = playlists.entry(info.title.clone()).or_default();
+                    playlist.info = Some(info);
+                }
+                continue;
+            }
+
+            let file = options.load_file::<PlaylistFile>(&path)?;
+            let title = stem.strip_suffix("-videos").unwrap_or(stem);
+            let playlist = playlists.entry(title.to_owned()).or_default();
+            if file.info.is_some() {
+                playlist.info = file.info;
+            }
+            playlist.videos.extend(file.videos);
+        }
+
+        Ok(Playlists(playlists))
+    }
+}
+
+impl TryFrom<&Path> for Playlists {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Playlists, Self::Error> {
+        Playlists::load(path, &LoadOptions::default())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Playlist {
+    pub info: Option<PlaylistInfo>,
+    pub videos: Vec<PlaylistVideo>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct PlaylistInfo {
+    pub id: String,
+    pub title: String,
+    pub visibility: Option<String>,
+    /// ISO 8601, or `YYYY-MM-DD HH:MM:SS UTC` in older exports
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct PlaylistVideo {
+    pub video_id: String,
+    /// ISO 8601, or `YYYY-MM-DD HH:MM:SS UTC` in older exports
+    pub added_at: Option<String>,
+}
+
+impl PlaylistVideo {
+    pub fn added_at(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        self.added_at
+            .as_deref()
+            .and_then(ArchiveTimestamp::parse_iso8601)
+    }
+}
+
+/// Rows of `playlists.csv`
+#[derive(Debug, Default)]
+struct PlaylistIndex(Vec<PlaylistInfo>);
+
+impl TryFrom<&Path> for PlaylistIndex {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<PlaylistIndex, Self::Error> {
+        #[derive(Deserialize)]
+        struct Row {
+            #[serde(rename = "Playlist ID")]
+            id: String,
+            #[serde(rename = "Playlist Title (Original)")]
+            title: String,
+            #[serde(rename = "Playlist Visibility", default)]
+            visibility: Option<String>,
+            #[serde(rename = "Playlist Create Timestamp", default)]
+            created_at: Option<String>,
+            #[serde(rename = "Playlist Update Timestamp", default)]
+            updated_at: Option<String>,
+        }
+
+        let rows: Vec<Row> = read_csv(path)?;
+        Ok(PlaylistIndex(
+            rows.into_iter()
+                .map(|row| PlaylistInfo {
+                    id: row.id,
+                    title: row.title,
+                    visibility: row.visibility,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                })
+                .collect(),
+        ))
+    }
+}
+
+impl ItemCount for PlaylistIndex {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Videos of a single playlist. Older exports start with a table describing the
+/// playlist, separated from the videos by a blank line.
+#[derive(Debug, Default)]
+struct PlaylistFile {
+    info: Option<PlaylistInfo>,
+    videos: Vec<PlaylistVideo>,
+}
+
+impl TryFrom<&Path> for PlaylistFile {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<PlaylistFile, Self::Error> {
+        #[derive(Deserialize)]
+        struct LegacyInfo {
+            #[serde(rename = "Playlist Id")]
+            id: String,
+            #[serde(rename = "Title")]
+            title: String,
+            #[serde(rename = "Visibility", default)]
+            visibility: Option<String>,
+            #[serde(rename = "Time Created", default)]
+            created_at: Option<String>,
+            #[serde(rename = "Time Updated", default)]
+            updated_at: Option<String>,
+        }
+
+        #[derive(Deserialize)]
+        struct LegacyVideo {
+            #[serde(rename = "Video Id")]
+            video_id: String,
+            #[serde(rename = "Time Added", default)]
+            added_at: Option<String>,
+        }
+
+        #[derive(Deserialize)]
+        struct Video {
+            #[serde(rename = "Video ID")]
+            video_id: String,
+            #[serde(rename = "Playlist Video Creation Timestamp", default)]
+            added_at: Option<String>,
+        }
+
+        let contents = fs::read_to_string(path)?;
+        let contents = contents.trim_start_matches('\u{feff}');
+
+        if !contents.starts_with("Playlist Id") {
+            let videos: Vec<Video> = read_csv_str(path, contents)?;
+            return Ok(PlaylistFile {
+                info: None,
+                videos: videos
+                    .into_iter()
+                    .map(|video| PlaylistVideo {
+                        video_id: video.video_id,
+                        added_at: video.added_at,
+                    })
+                    .collect(),
+            });
+        }
+
+        let contents = contents.replace("\r\n", "\n");
+        let (header, videos) = contents.split_once("\n\n").unwrap_or((&contents, ""));
+        let info: Vec<LegacyInfo> = read_csv_str(path, header)?;
+        let videos: Vec<LegacyVideo> = read_csv_str(path, videos.trim_start())?;
+
+        Ok(PlaylistFile {
+            info: info.into_iter().next().map(|info| PlaylistInfo {
+                id: info.id,
+                title: info.title,
+                visibility: info.visibility,
+                created_at: info.created_at,
+                updated_at: info.updated_at,
+            }),
+            videos: videos
+                .into_iter()
+                .map(|video| PlaylistVideo {
+                    video_id: video.video_id,
+                    added_at: video.added_at,
+                })
+                .collect(),
+        })
+    }
+}
+
+impl ItemCount for PlaylistFile {
+    fn item_count(&self) -> usize {
+        self.videos.len()
+    }
+}
+
+/// Read CSV with a header row from part of a file
+fn read_csv_str<T: DeserializeOwned>(path: &Path, contents: &str) -> Result<Vec<T>, ParseError> {
+    csv::Reader::from_reader(contents.as_bytes())
+        .deserialize()
+        .collect::<Result<Vec<T>, _>>()
+        .map_err(|e| ParseError::Csv(path.to_owned(), e))
+}
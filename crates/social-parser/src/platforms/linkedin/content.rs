@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+use super::parse_date;
+use crate::common::{ArchiveTimestamp, Attachment, AttachmentKind, Comment, Milliseconds, Post};
+
+/// Row of `Shares.csv`, posts made by the account
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Share {
+    /// e.g. `2023-01-01 10:00:00`
+    #[serde(rename = "Date")]
+    pub date: String,
+    #[serde(rename = "ShareLink")]
+    pub share_link: String,
+    #[serde(rename = "ShareCommentary", default)]
+    pub share_commentary: Option<String>,
+    /// Link shared in the post
+    #[serde(rename = "SharedUrl", default)]
+    pub shared_url: Option<String>,
+    #[serde(rename = "MediaUrl", default)]
+    pub media_url: Option<String>,
+    /// e.g. `MEMBER_NETWORK` or `PUBLIC`
+    #[serde(rename = "Visibility", default)]
+    pub visibility: Option<String>,
+}
+
+impl Share {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_date(&self.date)
+    }
+
+    /// Normalize into a post written by `author`
+    pub fn to_post(&self, author: Option<&str>) -> Post {
+        Post {
+            id: None,
+            author: author.map(str::to_owned),
+            timestamp: self.timestamp(),
+            title: None,
+            text: self.share_commentary.clone(),
+            url: Some(self.share_link.clone()),
+            link: self.shared_url.clone(),
+            community: None,
+            attachments: self
+                .media_url
+                .iter()
+                .map(|url| Attachment {
+                    kind: AttachmentKind::from_uri(url),
+                    uri: url.clone(),
+                    name: None,
+                    created_at: None,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Row of `Comments.csv`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct LinkedInComment {
+    #[serde(rename = "Date")]
+    pub date: String,
+    /// Post the comment was made on
+    #[serde(rename = "Link")]
+    pub link: String,
+    #[serde(rename = "Message")]
+    pub message: String,
+}
+
+impl LinkedInComment {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_date(&self.date)
+    }
+
+    /// Normalize into a comment written by `author`
+    pub fn to_comment(&self, author: Option<&str>) -> Comment {
+        Comment {
+            id: None,
+            author: author.map(str::to_owned),
+            timestamp: self.timestamp(),
+            text: self.message.clone(),
+            url: None,
+            post: Some(self.link.clone()),
+            parent: None,
+            community: None,
+        }
+    }
+}
+
+/// Row of `Reactions.csv`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Reaction {
+    #[serde(rename = "Date")]
+    pub date: String,
+    /// e.g. `LIKE`, `PRAISE`, `EMPATHY` or `INTEREST`
+    #[serde(rename = "Type")]
+    pub reaction_type: String,
+    /// Post reacted to
+    #[serde(rename = "Link")]
+    pub link: String,
+}
+
+impl Reaction {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_date(&self.date)
+    }
+}
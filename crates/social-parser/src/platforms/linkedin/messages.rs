@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::parse_date;
+use crate::common::{ArchiveTimestamp, Conversation, ConversationMessage, Milliseconds};
+
+/// Row of `messages.csv`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct LinkedInMessage {
+    #[serde(rename = "CONVERSATION ID")]
+    pub conversation_id: String,
+    #[serde(rename = "CONVERSATION TITLE", default)]
+    pub conversation_title: Option<String>,
+    #[serde(rename = "FROM")]
+    pub from: String,
+    #[serde(rename = "SENDER PROFILE URL", default)]
+    pub sender_profile_url: Option<String>,
+    /// Names of the recipients, separated by commas
+    #[serde(rename = "TO", default)]
+    pub to: Option<String>,
+    #[serde(rename = "RECIPIENT PROFILE URLS", default)]
+    pub recipient_profile_urls: Option<String>,
+    /// e.g. `2023-01-01 10:00:00 UTC`
+    #[serde(rename = "DATE")]
+    pub date: String,
+    #[serde(rename = "SUBJECT", default)]
+    pub subject: Option<String>,
+    #[serde(rename = "CONTENT", default)]
+    pub content: Option<String>,
+    /// e.g. `INBOX`, `SENT` or `ARCHIVE`
+    #[serde(rename = "FOLDER", default)]
+    pub folder: Option<String>,
+}
+
+impl LinkedInMessage {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_date(&self.date)
+    }
+
+    fn to_message(&self) -> ConversationMessage {
+        let mut message = ConversationMessage::new(self.from.clone(), self.timestamp());
+        message.content = self.content.clone();
+        message
+    }
+}
+
+/// Group messages into conversations by conversation id
+pub fn conversations(messages: &[LinkedInMessage]) -> Vec<Conversation> {
+    let mut threads: BTreeMap<&str, Vec<&LinkedInMessage>> = BTreeMap::new();
+    for message in messages {
+        threads
+            .entry(message.conversation_id.as_str())
+            .or_default()
+            .push(message);
+    }
+
+    threads
+        .into_iter()
+        .map(|(id, messages)| {
+            let mut participants: Vec<String> = vec![];
+            let names = messages.iter().flat_map(|message| {
+                let recipients = message.to.iter().flat_map(|to| to.split(','));
+                std::iter::once(message.from.as_str()).chain(recipients)
+            });
+            for name in names.map(str::trim).filter(|name| !name.is_empty()) {
+                if !participants.iter().any(|p| p == name) {
+                    participants.push(name.to_owned());
+                }
+            }
+
+            let mut converted: Vec<ConversationMessage> = messages
+                .iter()
+                .map(|message| message.to_message())
+                .collect();
+            converted.sort_by_key(|message| message.timestamp);
+
+            Conversation {
+                id: id.to_owned(),
+                title: messages
+                    .iter()
+                    .find_map(|message| message.conversation_title.clone()),
+                participants,
+                messages: converted,
+            }
+        })
+        .collect()
+}
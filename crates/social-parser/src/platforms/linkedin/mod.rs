@@ -0,0 +1,320 @@
+//! LinkedIn data export, requested from Settings > Data privacy > Get a copy of your data.
+//!
+//! The export is a zip of CSV files, some of them in subfolders. Both the zip and the
+//! extracted folder can be loaded. Some files start with a `Notes:` preamble before the
+//! header row, which is skipped. Unknown files are reported as warnings, and columns that
+//! aren't in every export version are optional.
+
+pub mod content;
+pub mod messages;
+pub mod network;
+pub mod profile;
+
+use std::{
+    fs::File,
+    io::Read,
+    path::{absolute, Path},
+};
+
+use content::{LinkedInComment, Reaction, Share};
+use messages::LinkedInMessage;
+use network::{Connection, Invitation};
+use profile::{Education, Position, Profile, Skill};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::common::{
+    bundle::{for_each_zip_file, is_zip},
+    read_csv_from, AccountInfo, Archive, ArchiveTimestamp, Comment, Conversation, Dataset, Follow,
+    FollowDirection, Like, LoadOptions, Milliseconds, ParseError, Post, Sections,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct LinkedInArchive {
+    pub profile: Option<Vec<Profile>>,
+    pub positions: Option<Vec<Position>>,
+    pub education: Option<Vec<Education>>,
+    pub skills: Option<Vec<Skill>>,
+    pub connections: Option<Vec<Connection>>,
+    pub invitations: Option<Vec<Invitation>>,
+    pub messages: Option<Vec<LinkedInMessage>>,
+    pub shares: Option<Vec<Share>>,
+    pub comments: Option<Vec<LinkedInComment>>,
+    pub reactions: Option<Vec<Reaction>>,
+}
+
+impl Archive for LinkedInArchive {
+    /// Load from a zip file or an extracted directory, only parsing the sections selected
+    /// in `options`
+    fn load<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        let mut archive = LinkedInArchive::default();
+
+        if is_zip(path) {
+            options.enter_section("LinkedInArchive", path)?;
+            for_each_zip_file(path, |path, reader, bytes| {
+                archive.load_csv(path, reader, bytes, options)
+            })?;
+            return Ok(archive);
+        }
+
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Expected a directory or zip file, found: {:?}",
+                absolute(path)
+            )));
+        }
+
+        options.enter_section("LinkedInArchive", path)?;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_file() {
+                // Folders hold optional data, e.g. `Jobs` or `Verifications`
+                options.skip(&path, "Skipped unsupported section");
+                continue;
+            }
+
+            let bytes = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            archive.load_csv(&path, File::open(&path)?, bytes, options)?;
+        }
+
+        Ok(archive)
+    }
+
+    fn to_dataset(&self) -> Dataset {
+        Dataset {
+            account: AccountInfo {
+                username: None,
+                display_name: self.full_name(),
+                email: None,
+            },
+            conversations: self.conversations(),
+            posts: self.normalized_posts(),
+            comments: self.normalized_comments(),
+            follows: self
+                .connections
+                .iter()
+                .flatten()
+                .map(|connection| Follow {
+                    direction: FollowDirection::Mutual,
+                    name: [&connection.first_name, &connection.last_name]
+                        .into_iter()
+                        .flatten()
+                        .map(String::as_str)
+                        .filter(|name| !name.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    url: connection.url.clone(),
+                    timestamp: connection.timestamp(),
+                })
+                .collect(),
+            likes: self
+                .reactions
+                .iter()
+                .flatten()
+                .map(|reaction| Like {
+                    target: Some(reaction.link.clone()),
+                    title: None,
+                    reaction: Some(reaction.reaction_type.clone()),
+                    timestamp: reaction.timestamp(),
+                })
+                .collect(),
+            ..Dataset::new("linkedin")
+        }
+    }
+}
+
+impl LinkedInArchive {
+    /// Parse one CSV file into the matching section, if that section is selected
+    fn load_csv<R: Read>(
+        &mut self,
+        path: &Path,
+        reader: R,
+        bytes: u64,
+        options: &LoadOptions,
+    ) -> Result<(), ParseError> {
+        match path.file_name().and_then(|s| s.to_str()) {
+            Some("Profile.csv") => {
+                if options.should_load(Sections::PROFILE, path) {
+                    self.profile = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("Positions.csv") => {
+                if options.should_load(Sections::PROFILE, path) {
+                    self.positions = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("Education.csv") => {
+                if options.should_load(Sections::PROFILE, path) {
+                    self.education = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("Skills.csv") => {
+                if options.should_load(Sections::PROFILE, path) {
+                    self.skills = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("Connections.csv") => {
+                if options.should_load(Sections::CONNECTIONS, path) {
+                    self.connections = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("Invitations.csv") => {
+                if options.should_load(Sections::CONNECTIONS, path) {
+                    self.invitations = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("messages.csv") => {
+                if options.should_load(Sections::MESSAGES, path) {
+                    self.messages = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("Shares.csv") => {
+                if options.should_load(Sections::POSTS, path) {
+                    self.shares = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("Comments.csv") => {
+                if options.should_load(Sections::COMMENTS, path) {
+                    self.comments = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("Reactions.csv") => {
+                if options.should_load(Sections::LIKES, path) {
+                    self.reactions = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            _ => {
+                options.skip(path, "Skipped unsupported file");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Full name of the account, from `Profile.csv`
+    pub fn full_name(&self) -> Option<String> {
+        self.profile.iter().flatten().find_map(Profile::full_name)
+    }
+
+    /// Posts made by the account, normalized
+    pub fn normalized_posts(&self) -> Vec<Post> {
+        let author = self.full_name();
+        self.shares
+            .iter()
+            .flatten()
+            .map(|share| share.to_post(author.as_deref()))
+            .collect()
+    }
+
+    /// Comments made by the account, normalized
+    pub fn normalized_comments(&self) -> Vec<Comment> {
+        let author = self.full_name();
+        self.comments
+            .iter()
+            .flatten()
+            .map(|comment| comment.to_comment(author.as_deref()))
+            .collect()
+    }
+
+    /// Messages grouped into normalized conversations
+    pub fn conversations(&self) -> Vec<Conversation> {
+        self.messages
+            .as_deref()
+            .map(messages::conversations)
+            .unwrap_or_default()
+    }
+}
+
+impl TryFrom<&Path> for LinkedInArchive {
+    type Error = ParseError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        LinkedInArchive::load(path, &LoadOptions::default())
+    }
+}
+
+/// Parse every row of a CSV file, skipping a `Notes:` preamble, and reporting progress
+/// through `options`
+fn load_rows<T: DeserializeOwned, R: Read>(
+    path: &Path,
+    mut reader: R,
+    bytes: u64,
+    options: &LoadOptions,
+) -> Result<Vec<T>, ParseError> {
+    options.load_with(path, bytes, || {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        read_csv_from(skip_preamble(&text).as_bytes(), path)
+    })
+}
+
+/// Text after the `Notes:` preamble LinkedIn puts before the header of some files, which
+/// ends at the first blank line
+fn skip_preamble(text: &str) -> &str {
+    let text = text.trim_start_matches('\u{feff}');
+    if !text.starts_with("Notes:") {
+        return text;
+    }
+
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        offset += line.len();
+        if line.trim().is_empty() {
+            return &text[offset..];
+        }
+    }
+    ""
+}
+
+/// Parse the dates LinkedIn writes, e.g. `2023-01-31 10:00:00 UTC`, `31 Jan 2023` or
+/// `1/31/23, 10:00 AM`. Dates without an offset are in UTC.
+pub fn parse_date(s: &str) -> Option<ArchiveTimestamp<Milliseconds>> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let s = s.trim();
+    if let Some(timestamp) = ArchiveTimestamp::parse_iso8601(s) {
+        return Some(timestamp);
+    }
+
+    // `31 Jan 2023`
+    let parts: Vec<&str> = s.split_whitespace().collect();
+    if let [day, month, year] = parts[..] {
+        if let Some(index) = MONTHS.iter().position(|m| month.starts_with(m)) {
+            let day: u32 = day.parse().ok()?;
+            return ArchiveTimestamp::parse_iso8601(&format!(
+                "{}-{:02}-{:02}",
+                year,
+                index + 1,
+                day
+            ));
+        }
+    }
+
+    // `1/31/23, 10:00 AM`
+    let (date, time) = s.split_once(", ").unwrap_or((s, "12:00 AM"));
+    let mut date = date.split('/');
+    let month: u32 = date.next()?.parse().ok()?;
+    let day: u32 = date.next()?.parse().ok()?;
+    let year: u32 = date.next()?.parse().ok()?;
+    let year = if year < 100 { year + 2000 } else { year };
+
+    let (clock, suffix) = time.split_once(' ').unwrap_or((time, ""));
+    let (hours, minutes) = clock.split_once(':')?;
+    let mut hours: u32 = hours.parse().ok()?;
+    match suffix {
+        "PM" if hours < 12 => hours += 12,
+        "AM" if hours == 12 => hours = 0,
+        _ => {}
+    }
+
+    ArchiveTimestamp::parse_iso8601(&format!(
+        "{}-{:02}-{:02}T{:02}:{}",
+        year, month, day, hours, minutes
+    ))
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use super::parse_date;
+use crate::common::{ArchiveTimestamp, Milliseconds};
+
+/// Row of `Connections.csv`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Connection {
+    #[serde(rename = "First Name")]
+    pub first_name: Option<String>,
+    #[serde(rename = "Last Name")]
+    pub last_name: Option<String>,
+    /// Profile URL
+    #[serde(rename = "URL")]
+    pub url: Option<String>,
+    /// Only shared by connections that allow it
+    #[serde(rename = "Email Address", default)]
+    pub email_address: Option<String>,
+    #[serde(rename = "Company", default)]
+    pub company: Option<String>,
+    #[serde(rename = "Position", default)]
+    pub position: Option<String>,
+    /// e.g. `01 Jan 2023`
+    #[serde(rename = "Connected On")]
+    pub connected_on: String,
+}
+
+impl Connection {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_date(&self.connected_on)
+    }
+}
+
+/// Row of `Invitations.csv`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Invitation {
+    #[serde(rename = "From")]
+    pub from: String,
+    #[serde(rename = "To")]
+    pub to: String,
+    /// e.g. `1/31/23, 10:00 AM`
+    #[serde(rename = "Sent At")]
+    pub sent_at: String,
+    #[serde(rename = "Message", default)]
+    pub message: Option<String>,
+    /// `INCOMING` or `OUTGOING`
+    #[serde(rename = "Direction")]
+    pub direction: String,
+    #[serde(rename = "inviterProfileUrl", default)]
+    pub inviter_profile_url: Option<String>,
+    #[serde(rename = "inviteeProfileUrl", default)]
+    pub invitee_profile_url: Option<String>,
+}
+
+impl Invitation {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_date(&self.sent_at)
+    }
+}
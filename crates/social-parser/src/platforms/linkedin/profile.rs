@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+/// Row of `Profile.csv`, which has a single row
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Profile {
+    #[serde(rename = "First Name")]
+    pub first_name: Option<String>,
+    #[serde(rename = "Last Name")]
+    pub last_name: Option<String>,
+    #[serde(rename = "Maiden Name", default)]
+    pub maiden_name: Option<String>,
+    #[serde(rename = "Address", default)]
+    pub address: Option<String>,
+    /// e.g. `Jan 1, 1990`
+    #[serde(rename = "Birth Date", default)]
+    pub birth_date: Option<String>,
+    #[serde(rename = "Headline", default)]
+    pub headline: Option<String>,
+    #[serde(rename = "Summary", default)]
+    pub summary: Option<String>,
+    #[serde(rename = "Industry", default)]
+    pub industry: Option<String>,
+    #[serde(rename = "Zip Code", default)]
+    pub zip_code: Option<String>,
+    #[serde(rename = "Geo Location", default)]
+    pub geo_location: Option<String>,
+    #[serde(rename = "Twitter Handles", default)]
+    pub twitter_handles: Option<String>,
+    /// e.g. `[PERSONAL:https://example.com]`
+    #[serde(rename = "Websites", default)]
+    pub websites: Option<String>,
+    #[serde(rename = "Instant Messengers", default)]
+    pub instant_messengers: Option<String>,
+}
+
+impl Profile {
+    /// First and last name joined, as shown on messages
+    pub fn full_name(&self) -> Option<String> {
+        let name = [&self.first_name, &self.last_name]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+        Some(name).filter(|name| !name.is_empty())
+    }
+}
+
+/// Row of `Positions.csv`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Position {
+    #[serde(rename = "Company Name")]
+    pub company_name: String,
+    #[serde(rename = "Title")]
+    pub title: Option<String>,
+    #[serde(rename = "Description", default)]
+    pub description: Option<String>,
+    #[serde(rename = "Location", default)]
+    pub location: Option<String>,
+    /// Month only, e.g. `Jan 2020`
+    #[serde(rename = "Started On", default)]
+    pub started_on: Option<String>,
+    /// Missing for current positions
+    #[serde(rename = "Finished On", default)]
+    pub finished_on: Option<String>,
+}
+
+/// Row of `Education.csv`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Education {
+    #[serde(rename = "School Name")]
+    pub school_name: String,
+    /// Year or month, e.g. `2015` or `Sep 2015`
+    #[serde(rename = "Start Date", default)]
+    pub start_date: Option<String>,
+    #[serde(rename = "End Date", default)]
+    pub end_date: Option<String>,
+    #[serde(rename = "Notes", default)]
+    pub notes: Option<String>,
+    #[serde(rename = "Degree Name", default)]
+    pub degree_name: Option<String>,
+    #[serde(rename = "Activities", default)]
+    pub activities: Option<String>,
+}
+
+/// Row of `Skills.csv`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Skill {
+    #[serde(rename = "Name")]
+    pub name: String,
+}
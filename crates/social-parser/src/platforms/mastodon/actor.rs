@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::ItemCount;
+
+/// `actor.json`, the account's profile
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct Actor {
+    /// e.g. `https://example.social/users/jane`
+    pub id: String,
+    pub preferred_username: Option<String>,
+    /// Display name
+    pub name: Option<String>,
+    /// Bio, as HTML
+    pub summary: Option<String>,
+    pub url: Option<String>,
+    pub published: Option<String>,
+    #[serde(default)]
+    pub manually_approves_followers: bool,
+    /// Avatar
+    pub icon: Option<Image>,
+    /// Header image
+    pub image: Option<Image>,
+    /// Profile metadata fields
+    #[serde(default)]
+    pub attachment: Vec<ProfileField>,
+}
+
+impl ItemCount for Actor {
+    fn item_count(&self) -> usize {
+        1
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct Image {
+    pub media_type: Option<String>,
+    /// Path inside the archive, e.g. `avatar.png`
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ProfileField {
+    pub name: Option<String>,
+    /// HTML
+    pub value: Option<String>,
+}
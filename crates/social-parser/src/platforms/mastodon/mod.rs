@@ -0,0 +1,277 @@
+//! Mastodon account archive, requested from Preferences > Import and export > Request your
+//! archive.
+//!
+//! The archive is a `.tar.gz` of ActivityStreams JSON: `actor.json` for the profile,
+//! `outbox.json` for statuses and boosts, `likes.json` and `bookmarks.json`, next to the
+//! uploaded files under `media_attachments`. Both the tarball and its extracted folder can
+//! be loaded. Other Fediverse servers export the same layout. Like the other newer
+//! platforms, these models accept unknown fields.
+
+pub mod actor;
+pub mod outbox;
+
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::{absolute, Path},
+};
+
+use actor::Actor;
+use outbox::{Activity, Boost, Note, Outbox, StatusCollection};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::common::{
+    bundle::{for_each_tar_gz_file, is_tar_gz},
+    AccountInfo, Archive, Comment, Dataset, ItemCount, Like, LoadOptions, ParseError, Post,
+    SavedItem, Sections,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct MastodonArchive {
+    pub actor: Option<Actor>,
+    pub outbox: Option<Outbox>,
+    /// URLs of the statuses the account favourited
+    pub likes: Option<StatusCollection>,
+    /// URLs of the statuses the account bookmarked
+    pub bookmarks: Option<StatusCollection>,
+    /// Paths of the files included in the archive, relative to its root, e.g.
+    /// `media_attachments/files/000/000/001/original/a.png`
+    pub media: Option<Vec<String>>,
+}
+
+impl Archive for MastodonArchive {
+    /// Load from the `.tar.gz` archive or its extracted directory, only parsing the
+    /// sections selected in `options`
+    fn load<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        let mut archive = MastodonArchive::default();
+
+        if is_tar_gz(path) {
+            options.enter_section("MastodonArchive", path)?;
+            let root = path;
+            for_each_tar_gz_file(path, |path, reader, bytes| {
+                archive.load_file(root, path, reader, bytes, options)
+            })?;
+            return Ok(archive);
+        }
+
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Expected a directory or .tar.gz file, found: {:?}",
+                absolute(path)
+            )));
+        }
+
+        options.enter_section("MastodonArchive", path)?;
+        archive.load_dir(path, path, options)?;
+
+        Ok(archive)
+    }
+
+    fn to_dataset(&self) -> Dataset {
+        let statuses = |collection: &Option<StatusCollection>| {
+            collection
+                .iter()
+                .flat_map(|collection| collection.ordered_items.clone())
+                .collect::<Vec<_>>()
+        };
+
+        Dataset {
+            account: AccountInfo {
+                username: self.username().map(str::to_owned),
+                display_name: self.actor.as_ref().and_then(|actor| actor.name.clone()),
+                email: None,
+            },
+            posts: self.normalized_posts(),
+            comments: self.normalized_comments(),
+            likes: statuses(&self.likes)
+                .into_iter()
+                .map(|status| Like {
+                    target: Some(status),
+                    title: None,
+                    reaction: None,
+                    timestamp: None,
+                })
+                .collect(),
+            saved_items: statuses(&self.bookmarks)
+                .into_iter()
+                .map(|status| SavedItem {
+                    target: Some(status),
+                    title: None,
+                    collection: None,
+                    timestamp: None,
+                })
+                .collect(),
+            ..Dataset::new("mastodon")
+        }
+    }
+}
+
+impl MastodonArchive {
+    /// Load every file under `dir`, including subfolders
+    fn load_dir(
+        &mut self,
+        root: &Path,
+        dir: &Path,
+        options: &LoadOptions,
+    ) -> Result<(), ParseError> {
+        for entry in dir.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if path.is_dir() {
+                self.load_dir(root, &path, options)?;
+            } else if path.is_file() {
+                let bytes = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                self.load_file(root, &path, File::open(&path)?, bytes, options)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parse one file into the matching section, or record it as media
+    fn load_file<R: Read>(
+        &mut self,
+        root: &Path,
+        path: &Path,
+        reader: R,
+        bytes: u64,
+        options: &LoadOptions,
+    ) -> Result<(), ParseError> {
+        let Ok(relative) = path.strip_prefix(root) else {
+            return Ok(());
+        };
+
+        match relative.to_str() {
+            Some("actor.json") => {
+                if options.should_load(Sections::PROFILE, path) {
+                    self.actor = Some(load_json(path, reader, bytes, options)?);
+                }
+            }
+            Some("outbox.json") => {
+                if options.should_load(Sections::POSTS, path) {
+                    self.outbox = Some(load_json(path, reader, bytes, options)?);
+                }
+            }
+            Some("likes.json") => {
+                if options.should_load(Sections::LIKES, path) {
+                    self.likes = Some(load_json(path, reader, bytes, options)?);
+                }
+            }
+            Some("bookmarks.json") => {
+                if options.should_load(Sections::SAVED, path) {
+                    self.bookmarks = Some(load_json(path, reader, bytes, options)?);
+                }
+            }
+            Some(name) if name.ends_with(".json") => {
+                options.warn(path, "Skipped unsupported file");
+            }
+            _ => {
+                if options.should_load(Sections::MEDIA, path) {
+                    let name = relative
+                        .components()
+                        .map(|c| c.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/");
+                    self.media.get_or_insert_with(Vec::new).push(name);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Username of the account, e.g. `jane`
+    pub fn username(&self) -> Option<&str> {
+        self.actor.as_ref()?.preferred_username.as_deref()
+    }
+
+    /// Path of a file in the archive if it was included, for resolving attachments
+    fn resolve(&self, path: &str) -> Option<String> {
+        self.media
+            .iter()
+            .flatten()
+            .find(|name| *name == path)
+            .cloned()
+    }
+
+    /// Statuses posted by the account, normalized. Replies are left out, see
+    /// [`MastodonArchive::normalized_comments`]. The content warning becomes the title,
+    /// and attachments point into the archive when their files were included.
+    pub fn normalized_posts(&self) -> Vec<Post> {
+        self.notes()
+            .filter(|(_, note)| note.in_reply_to.is_none())
+            .map(|(activity, note)| Post {
+                id: Some(note.id.clone()),
+                author: self.username().map(str::to_owned),
+                timestamp: note.timestamp().or_else(|| activity.timestamp()),
+                title: note.summary.clone().filter(|s| !s.is_empty()),
+                text: note.text(),
+                url: note.url.clone().or_else(|| Some(note.id.clone())),
+                link: None,
+                community: None,
+                attachments: note.attachments(|path| self.resolve(path)),
+            })
+            .collect()
+    }
+
+    /// Replies posted by the account, normalized, including replies in the account's own
+    /// threads
+    pub fn normalized_comments(&self) -> Vec<Comment> {
+        self.notes()
+            .filter_map(|(activity, note)| {
+                let parent = note.in_reply_to.clone()?;
+                Some(Comment {
+                    id: Some(note.id.clone()),
+                    author: self.username().map(str::to_owned),
+                    timestamp: note.timestamp().or_else(|| activity.timestamp()),
+                    text: note.text().unwrap_or_default(),
+                    url: note.url.clone().or_else(|| Some(note.id.clone())),
+                    post: None,
+                    parent: Some(parent),
+                    community: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Statuses boosted by the account
+    pub fn boosts(&self) -> Vec<Boost> {
+        self.outbox
+            .iter()
+            .flat_map(|outbox| &outbox.ordered_items)
+            .filter_map(|activity| activity.boost())
+            .collect()
+    }
+
+    /// Statuses created by the account, with the activity that created them
+    fn notes(&self) -> impl Iterator<Item = (&Activity, &Note)> {
+        self.outbox
+            .iter()
+            .flat_map(|outbox| &outbox.ordered_items)
+            .filter_map(|activity| Some((activity, activity.note()?)))
+    }
+}
+
+impl TryFrom<&Path> for MastodonArchive {
+    type Error = ParseError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        MastodonArchive::load(path, &LoadOptions::default())
+    }
+}
+
+/// Parse a JSON file from `reader`, reporting progress through `options`
+fn load_json<T: DeserializeOwned + ItemCount, R: Read>(
+    path: &Path,
+    reader: R,
+    bytes: u64,
+    options: &LoadOptions,
+) -> Result<T, ParseError> {
+    options.load_with(path, bytes, || {
+        serde_json::from_reader(BufReader::new(reader))
+            .map_err(|e| ParseError::Serde(path.to_owned(), e))
+    })
+}
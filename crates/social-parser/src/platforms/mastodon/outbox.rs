@@ -0,0 +1,207 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::{html, ArchiveTimestamp, Attachment, AttachmentKind, ItemCount, Milliseconds};
+
+/// `outbox.json`, every activity the account published, oldest first
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct Outbox {
+    pub total_items: Option<u64>,
+    #[serde(default)]
+    pub ordered_items: Vec<Activity>,
+}
+
+impl ItemCount for Outbox {
+    fn item_count(&self) -> usize {
+        self.ordered_items.len()
+    }
+}
+
+/// `likes.json` or `bookmarks.json`, a list of status URLs
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct StatusCollection {
+    #[serde(default)]
+    pub ordered_items: Vec<String>,
+}
+
+impl ItemCount for StatusCollection {
+    fn item_count(&self) -> usize {
+        self.ordered_items.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Activity {
+    pub id: String,
+    /// `Create` for statuses, `Announce` for boosts
+    #[serde(rename = "type")]
+    pub activity_type: String,
+    pub actor: Option<String>,
+    /// e.g. `2023-01-01T10:00:00Z`
+    pub published: Option<String>,
+    /// Audience, e.g. the public collection or the account's followers
+    #[serde(default)]
+    pub to: Vec<String>,
+    #[serde(default)]
+    pub cc: Vec<String>,
+    pub object: ActivityObject,
+}
+
+impl Activity {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        self.published
+            .as_deref()
+            .and_then(ArchiveTimestamp::parse_iso8601)
+    }
+
+    /// Status created by a `Create` activity
+    pub fn note(&self) -> Option<&Note> {
+        match (&self.object, self.activity_type.as_str()) {
+            (ActivityObject::Note(note), "Create") => Some(note),
+            _ => None,
+        }
+    }
+
+    /// Status boosted by an `Announce` activity
+    pub fn boost(&self) -> Option<Boost> {
+        match (&self.object, self.activity_type.as_str()) {
+            (ActivityObject::Link(url), "Announce") => Some(Boost {
+                id: self.id.clone(),
+                timestamp: self.timestamp(),
+                status: url.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Object of an activity, embedded for statuses and referenced by URL for boosts
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(untagged)]
+pub enum ActivityObject {
+    Link(String),
+    Note(Box<Note>),
+}
+
+/// Status, or a poll when `note_type` is `Question`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct Note {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub note_type: String,
+    /// Content warning
+    pub summary: Option<String>,
+    /// Status being replied to
+    pub in_reply_to: Option<String>,
+    pub published: Option<String>,
+    /// Public page of the status
+    pub url: Option<String>,
+    pub attributed_to: Option<String>,
+    #[serde(default)]
+    pub sensitive: bool,
+    /// HTML
+    pub content: Option<String>,
+    #[serde(default)]
+    pub attachment: Vec<Document>,
+    #[serde(default)]
+    pub tag: Vec<Tag>,
+    /// Options of a single choice poll
+    #[serde(default)]
+    pub one_of: Vec<PollOption>,
+    /// Options of a multiple choice poll
+    #[serde(default)]
+    pub any_of: Vec<PollOption>,
+}
+
+impl Note {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        self.published
+            .as_deref()
+            .and_then(ArchiveTimestamp::parse_iso8601)
+    }
+
+    /// Content as plain text, keeping paragraphs and line breaks
+    pub fn text(&self) -> Option<String> {
+        let content = self.content.as_deref()?;
+        Some(html::text_with_breaks(content)).filter(|text| !text.is_empty())
+    }
+
+    /// Attached media. `resolve` maps the path of a file in the archive to where it can be
+    /// found, or `None` if it isn't there and the original URL should be kept.
+    pub fn attachments(&self, resolve: impl Fn(&str) -> Option<String>) -> Vec<Attachment> {
+        self.attachment
+            .iter()
+            .map(|document| {
+                let uri = resolve(document.url.trim_start_matches('/'))
+                    .unwrap_or_else(|| document.url.clone());
+                let media_type = document.media_type.as_deref().unwrap_or_default();
+                let kind = if media_type.starts_with("image/") {
+                    AttachmentKind::Photo
+                } else if media_type.starts_with("video/") {
+                    AttachmentKind::Video
+                } else if media_type.starts_with("audio/") {
+                    AttachmentKind::Audio
+                } else {
+                    AttachmentKind::from_uri(&uri)
+                };
+
+                Attachment {
+                    kind,
+                    uri,
+                    name: document.name.clone(),
+                    created_at: None,
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct Document {
+    pub media_type: Option<String>,
+    /// Path inside the archive, e.g. `/media_attachments/files/000/000/001/original/a.png`
+    pub url: String,
+    /// Alt text
+    pub name: Option<String>,
+    pub blurhash: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+/// Hashtag, mention or custom emoji used in a status
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Tag {
+    /// `Hashtag`, `Mention` or `Emoji`
+    #[serde(rename = "type")]
+    pub tag_type: String,
+    pub href: Option<String>,
+    /// e.g. `#rust` or `@jane@example.social`
+    pub name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct PollOption {
+    pub name: String,
+}
+
+/// Status boosted by the account
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Boost {
+    /// Id of the `Announce` activity
+    pub id: String,
+    pub timestamp: Option<ArchiveTimestamp<Milliseconds>>,
+    /// URL of the boosted status
+    pub status: String,
+}
@@ -0,0 +1,144 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::ActivityEntry;
+use crate::{
+    common::{ItemCount, LoadOptions, ParseError},
+    platforms::meta::read_json,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct CommentsAndReactions {
+    pub comments: Option<Comments>,
+    pub reactions: Option<Reactions>,
+}
+
+impl CommentsAndReactions {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<CommentsAndReactions, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in CommentsAndReactions: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("CommentsAndReactions", path)?;
+
+        let mut comments = None;
+        let mut reactions: Option<Reactions> = None;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_file() {
+                options.skip(&path, "Skipped unsupported section");
+                continue;
+            }
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("comments.json") => {
+                    comments = Some(options.load_file::<Comments>(&path)?);
+                }
+                // Split into `likes_and_reactions_1.json`, ... in newer exports
+                Some(name)
+                    if name == "posts_and_comments.json"
+                        || (name.starts_with("likes_and_reactions") && name.ends_with(".json")) =>
+                {
+                    let part = options.load_file::<Reactions>(&path)?;
+                    reactions
+                        .get_or_insert_with(Default::default)
+                        .0
+                        .extend(part.0);
+                }
+                _ => {
+                    options.skip(&path, "Skipped unsupported file");
+                }
+            }
+        }
+
+        Ok(CommentsAndReactions {
+            comments,
+            reactions,
+        })
+    }
+}
+
+impl TryFrom<&Path> for CommentsAndReactions {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<CommentsAndReactions, Self::Error> {
+        CommentsAndReactions::load(path, &LoadOptions::default())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Comments {
+    #[serde(rename = "comments_v2")]
+    pub comments: Vec<ActivityEntry>,
+}
+
+impl TryFrom<&Path> for Comments {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<Comments, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in Comments: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for Comments {
+    fn item_count(&self) -> usize {
+        self.comments.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Reactions(pub Vec<ActivityEntry>);
+
+/// Older exports wrap the list in a `reactions_v2` object
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ReactionsFile {
+    List(Vec<ActivityEntry>),
+    Wrapped { reactions_v2: Vec<ActivityEntry> },
+}
+
+impl TryFrom<&Path> for Reactions {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<Reactions, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in Reactions: {:?}",
+                path
+            )));
+        }
+
+        match read_json(path)? {
+            ReactionsFile::List(reactions) => Ok(Reactions(reactions)),
+            ReactionsFile::Wrapped { reactions_v2 } => Ok(Reactions(reactions_v2)),
+        }
+    }
+}
+
+impl ItemCount for Reactions {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
@@ -0,0 +1,187 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::Place;
+use crate::{
+    common::{ArchiveTimestamp, ItemCount, LoadOptions, ParseError},
+    platforms::meta::read_json,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Events {
+    pub responses: Option<EventResponses>,
+    pub invitations: Option<EventInvitations>,
+    pub your_events: Option<YourEvents>,
+}
+
+impl Events {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Events, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in Events: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("Events", path)?;
+
+        let mut responses = None;
+        let mut invitations = None;
+        let mut your_events = None;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_file() {
+                options.skip(&path, "Skipped unsupported section");
+                continue;
+            }
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("your_event_responses.json") => {
+                    responses = Some(options.load_file::<EventResponses>(&path)?);
+                }
+                Some("event_invitations.json") => {
+                    invitations = Some(options.load_file::<EventInvitations>(&path)?);
+                }
+                Some("your_events.json") => {
+                    your_events = Some(options.load_file::<YourEvents>(&path)?);
+                }
+                _ => {
+                    options.skip(&path, "Skipped unsupported file");
+                }
+            }
+        }
+
+        Ok(Events {
+            responses,
+            invitations,
+            your_events,
+        })
+    }
+}
+
+impl TryFrom<&Path> for Events {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Events, Self::Error> {
+        Events::load(path, &LoadOptions::default())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Event {
+    pub name: String,
+    pub start_timestamp: Option<ArchiveTimestamp>,
+    pub end_timestamp: Option<ArchiveTimestamp>,
+    pub create_timestamp: Option<ArchiveTimestamp>,
+    pub description: Option<String>,
+    pub place: Option<Place>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct EventResponses {
+    #[serde(rename = "event_responses_v2")]
+    pub responses: EventResponseLists,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct EventResponseLists {
+    #[serde(default)]
+    pub events_joined: Vec<Event>,
+    #[serde(default)]
+    pub events_declined: Vec<Event>,
+    #[serde(default)]
+    pub events_interested: Vec<Event>,
+}
+
+impl TryFrom<&Path> for EventResponses {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<EventResponses, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in EventResponses: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for EventResponses {
+    fn item_count(&self) -> usize {
+        self.responses.events_joined.len()
+            + self.responses.events_declined.len()
+            + self.responses.events_interested.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct EventInvitations {
+    #[serde(rename = "events_invited_v2")]
+    pub invitations: Vec<Event>,
+}
+
+impl TryFrom<&Path> for EventInvitations {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<EventInvitations, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in EventInvitations: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for EventInvitations {
+    fn item_count(&self) -> usize {
+        self.invitations.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct YourEvents {
+    #[serde(rename = "your_events_v2")]
+    pub events: Vec<Event>,
+}
+
+impl TryFrom<&Path> for YourEvents {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<YourEvents, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in YourEvents: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for YourEvents {
+    fn item_count(&self) -> usize {
+        self.events.len()
+    }
+}
@@ -0,0 +1,170 @@
+use std::{collections::BTreeMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::{ArchiveTimestamp, ItemCount, LoadOptions, Milliseconds, ParseError},
+    platforms::meta::{read_json, LinkTimeValueData},
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct FriendsAndFollowers {
+    pub friends: Option<ConnectionList>,
+    pub received_friend_requests: Option<ConnectionList>,
+    pub sent_friend_requests: Option<ConnectionList>,
+    pub rejected_friend_requests: Option<ConnectionList>,
+    pub removed_friends: Option<ConnectionList>,
+    pub following: Option<ConnectionList>,
+    pub followers: Option<ConnectionList>,
+}
+
+impl FriendsAndFollowers {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<FriendsAndFollowers, ParseError> {
+        let mut friends_and_followers = FriendsAndFollowers::default();
+        friends_and_followers.load_into(path, options)?;
+        Ok(friends_and_followers)
+    }
+
+    /// Add the lists found in a directory. Newer exports split friends and followers
+    /// into separate `connections/friends` and `connections/followers` folders.
+    pub(super) fn load_into(
+        &mut self,
+        path: &Path,
+        options: &LoadOptions,
+    ) -> Result<(), ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in FriendsAndFollowers: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("FriendsAndFollowers", path)?;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_file() {
+                options.skip(&path, "Skipped unsupported section");
+                continue;
+            }
+
+            let list = match path.file_name().and_then(|s| s.to_str()) {
+                Some("your_friends.json") | Some("friends.json") => &mut self.friends,
+                Some("received_friend_requests.json") => &mut self.received_friend_requests,
+                Some("sent_friend_requests.json") => &mut self.sent_friend_requests,
+                Some("rejected_friend_requests.json") => &mut self.rejected_friend_requests,
+                Some("removed_friends.json") => &mut self.removed_friends,
+                Some("who_you_follow.json")
+                | Some("who_you've_followed.json")
+                | Some("following.json") => &mut self.following,
+                Some("people_who_follow_you.json")
+                | Some("people_who_followed_you.json")
+                | Some("followers.json") => &mut self.followers,
+                _ => {
+                    options.skip(&path, "Skipped unsupported file");
+                    continue;
+                }
+            };
+            *list = Some(options.load_file::<ConnectionList>(&path)?);
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<&Path> for FriendsAndFollowers {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<FriendsAndFollowers, Self::Error> {
+        FriendsAndFollowers::load(path, &LoadOptions::default())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ConnectionList(pub Vec<Connection>);
+
+/// Each file wraps its list in a versioned key (`friends_v2`, `following_v3`, ...),
+/// except for newer follower lists which are top-level arrays
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ConnectionListFile {
+    List(Vec<Connection>),
+    Keyed(BTreeMap<String, Vec<Connection>>),
+}
+
+impl TryFrom<&Path> for ConnectionList {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<ConnectionList, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in ConnectionList: {:?}",
+                path
+            )));
+        }
+
+        match read_json(path)? {
+            ConnectionListFile::List(connections) => Ok(ConnectionList(connections)),
+            ConnectionListFile::Keyed(lists) => {
+                Ok(ConnectionList(lists.into_values().flatten().collect()))
+            }
+        }
+    }
+}
+
+impl ItemCount for ConnectionList {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Person in a friend or follower list
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(untagged)]
+pub enum Connection {
+    Named {
+        name: String,
+        timestamp: Option<ArchiveTimestamp>,
+    },
+    /// Same layout as Instagram's follower lists
+    Linked {
+        title: String,
+        string_list_data: Vec<LinkTimeValueData>,
+    },
+}
+
+impl Connection {
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Connection::Named { name, .. } => Some(name),
+            Connection::Linked {
+                title,
+                string_list_data,
+            } => string_list_data
+                .first()
+                .and_then(|data| data.value.as_deref())
+                .or((!title.is_empty()).then_some(title.as_str())),
+        }
+    }
+
+    /// When the connection was made, if exported
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        match self {
+            Connection::Named { timestamp, .. } => timestamp.map(|t| t.convert()),
+            Connection::Linked {
+                string_list_data, ..
+            } => string_list_data
+                .first()
+                .map(|data| data.timestamp.convert()),
+        }
+    }
+}
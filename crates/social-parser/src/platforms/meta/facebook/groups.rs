@@ -0,0 +1,199 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{ActivityEntry, NameTimestamp};
+use crate::{
+    common::{ItemCount, LoadOptions, ParseError},
+    platforms::meta::read_json,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Groups {
+    pub memberships: Option<GroupMemberships>,
+    pub posts: Option<GroupPosts>,
+    pub comments: Option<GroupComments>,
+    pub admined: Option<AdminedGroups>,
+}
+
+impl Groups {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Groups, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in Groups: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("Groups", path)?;
+
+        let mut memberships = None;
+        let mut posts = None;
+        let mut comments = None;
+        let mut admined = None;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_file() {
+                options.skip(&path, "Skipped unsupported section");
+                continue;
+            }
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("your_group_membership_activity.json") => {
+                    memberships = Some(options.load_file::<GroupMemberships>(&path)?);
+                }
+                Some("group_posts_and_comments.json") | Some("your_posts_in_groups.json") => {
+                    posts = Some(options.load_file::<GroupPosts>(&path)?);
+                }
+                Some("your_comments_in_groups.json") => {
+                    comments = Some(options.load_file::<GroupComments>(&path)?);
+                }
+                Some("your_groups.json") => {
+                    admined = Some(options.load_file::<AdminedGroups>(&path)?);
+                }
+                _ => {
+                    options.skip(&path, "Skipped unsupported file");
+                }
+            }
+        }
+
+        Ok(Groups {
+            memberships,
+            posts,
+            comments,
+            admined,
+        })
+    }
+}
+
+impl TryFrom<&Path> for Groups {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Groups, Self::Error> {
+        Groups::load(path, &LoadOptions::default())
+    }
+}
+
+/// Groups joined and left, with the group name in each entry's `data`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct GroupMemberships {
+    #[serde(rename = "groups_joined_v2")]
+    pub memberships: Vec<ActivityEntry>,
+}
+
+impl TryFrom<&Path> for GroupMemberships {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<GroupMemberships, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in GroupMemberships: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for GroupMemberships {
+    fn item_count(&self) -> usize {
+        self.memberships.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct GroupPosts {
+    #[serde(rename = "group_posts_v2")]
+    pub posts: Vec<ActivityEntry>,
+}
+
+impl TryFrom<&Path> for GroupPosts {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<GroupPosts, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in GroupPosts: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for GroupPosts {
+    fn item_count(&self) -> usize {
+        self.posts.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct GroupComments {
+    #[serde(rename = "group_comments_v2")]
+    pub comments: Vec<ActivityEntry>,
+}
+
+impl TryFrom<&Path> for GroupComments {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<GroupComments, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in GroupComments: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for GroupComments {
+    fn item_count(&self) -> usize {
+        self.comments.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct AdminedGroups {
+    #[serde(rename = "groups_admined_v2")]
+    pub groups: Vec<NameTimestamp>,
+}
+
+impl TryFrom<&Path> for AdminedGroups {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<AdminedGroups, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in AdminedGroups: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for AdminedGroups {
+    fn item_count(&self) -> usize {
+        self.groups.len()
+    }
+}
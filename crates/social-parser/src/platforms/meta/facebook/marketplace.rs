@@ -0,0 +1,105 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::Place;
+use crate::{
+    common::{ArchiveTimestamp, ItemCount, LoadOptions, ParseError},
+    platforms::meta::read_json,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Marketplace {
+    pub items_sold: Option<ListedItems>,
+}
+
+impl Marketplace {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Marketplace, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in Marketplace: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("Marketplace", path)?;
+
+        let mut items_sold = None;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_file() {
+                options.skip(&path, "Skipped unsupported section");
+                continue;
+            }
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("items_sold.json") => {
+                    items_sold = Some(options.load_file::<ListedItems>(&path)?);
+                }
+                _ => {
+                    options.skip(&path, "Skipped unsupported file");
+                }
+            }
+        }
+
+        Ok(Marketplace { items_sold })
+    }
+}
+
+impl TryFrom<&Path> for Marketplace {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Marketplace, Self::Error> {
+        Marketplace::load(path, &LoadOptions::default())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ListedItems {
+    #[serde(rename = "items_selling_v2")]
+    pub items: Vec<ListedItem>,
+}
+
+impl TryFrom<&Path> for ListedItems {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<ListedItems, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in ListedItems: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for ListedItems {
+    fn item_count(&self) -> usize {
+        self.items.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ListedItem {
+    pub title: Option<String>,
+    /// Formatted with its currency, e.g. "$25"
+    pub price: Option<String>,
+    pub seller: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub location: Option<Place>,
+    pub created_timestamp: Option<ArchiveTimestamp>,
+    pub updated_timestamp: Option<ArchiveTimestamp>,
+}
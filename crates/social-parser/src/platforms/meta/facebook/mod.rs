@@ -0,0 +1,384 @@
+//! Facebook "Download Your Information" export in JSON format.
+//!
+//! Facebook renames files and adds fields between export versions, so unlike the Instagram
+//! models these accept unknown fields, and unknown files are reported as warnings instead of
+//! failing the whole load.
+
+pub mod comments_and_reactions;
+pub mod events;
+pub mod friends_and_followers;
+pub mod groups;
+pub mod marketplace;
+pub mod pages;
+pub mod posts;
+pub mod profile_information;
+pub mod search;
+
+use std::path::{absolute, Path};
+
+use comments_and_reactions::CommentsAndReactions;
+use events::Events;
+use friends_and_followers::FriendsAndFollowers;
+use groups::Groups;
+use marketplace::Marketplace;
+use pages::Pages;
+use posts::Posts;
+use profile_information::ProfileInformation;
+use search::Search;
+use serde::{Deserialize, Serialize};
+
+use super::MediaUri;
+use crate::common::{
+    self, AccountInfo, Archive, ArchiveTimestamp, AttachmentKind, Dataset, Follow, FollowDirection,
+    Like, LoadOptions, ParseError, Post, Sections,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct FacebookArchive {
+    pub posts: Option<Posts>,
+    pub comments_and_reactions: Option<CommentsAndReactions>,
+    pub friends_and_followers: Option<FriendsAndFollowers>,
+    pub groups: Option<Groups>,
+    pub events: Option<Events>,
+    pub pages: Option<Pages>,
+    pub marketplace: Option<Marketplace>,
+    pub profile_information: Option<ProfileInformation>,
+    pub search: Option<Search>,
+}
+
+impl Archive for FacebookArchive {
+    /// Load from a directory, only parsing the sections selected in `options`
+    fn load<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Expected a directory, found: {:?}",
+                absolute(path)
+            )));
+        }
+
+        options.enter_section("FacebookArchive", path)?;
+
+        let mut archive = FacebookArchive::default();
+        archive.load_folder(path, options)?;
+        Ok(archive)
+    }
+
+    fn to_dataset(&self) -> Dataset {
+        let mut dataset = Dataset::new("facebook");
+
+        if let Some(profile) = self
+            .profile_information
+            .as_ref()
+            .and_then(|information| information.profile.as_ref())
+        {
+            let profile = &profile.profile;
+            dataset.account = AccountInfo {
+                username: profile.username.clone(),
+                display_name: profile.name.as_ref().map(|name| name.full_name.clone()),
+                email: profile
+                    .emails
+                    .as_ref()
+                    .and_then(|emails| emails.emails.first().cloned()),
+            };
+        }
+
+        dataset.posts = self
+            .posts
+            .iter()
+            .flat_map(|posts| &posts.your_posts)
+            .flat_map(|posts| &posts.0)
+            .map(ActivityEntry::to_post)
+            .collect();
+
+        if let Some(activity) = &self.comments_and_reactions {
+            dataset.comments = activity
+                .comments
+                .iter()
+                .flat_map(|comments| &comments.comments)
+                .flat_map(|entry| {
+                    entry.data.iter().filter_map(|data| {
+                        let comment = data.comment.as_ref()?;
+                        Some(common::Comment {
+                            id: None,
+                            author: comment.author.clone(),
+                            timestamp: Some(comment.timestamp.unwrap_or(entry.timestamp).convert()),
+                            text: comment.comment.clone()?,
+                            url: None,
+                            post: None,
+                            parent: None,
+                            community: comment.group.clone(),
+                        })
+                    })
+                })
+                .collect();
+            dataset.likes = activity
+                .reactions
+                .iter()
+                .flat_map(|reactions| &reactions.0)
+                .flat_map(|entry| {
+                    entry.data.iter().filter_map(|data| {
+                        let reaction = data.reaction.as_ref()?;
+                        Some(Like {
+                            target: None,
+                            title: entry.title.clone(),
+                            reaction: Some(reaction.reaction.clone()),
+                            timestamp: Some(entry.timestamp.convert()),
+                        })
+                    })
+                })
+                .collect();
+        }
+
+        if let Some(connections) = &self.friends_and_followers {
+            let lists = [
+                (FollowDirection::Mutual, &connections.friends),
+                (FollowDirection::Following, &connections.following),
+                (FollowDirection::Follower, &connections.followers),
+            ];
+            dataset.follows = lists
+                .into_iter()
+                .flat_map(|(direction, list)| {
+                    list.iter()
+                        .flat_map(|list| &list.0)
+                        .map(move |connection| (direction, connection))
+                })
+                .filter_map(|(direction, connection)| {
+                    Some(Follow {
+                        direction,
+                        name: connection.name()?.to_owned(),
+                        url: None,
+                        timestamp: connection.timestamp(),
+                    })
+                })
+                .collect();
+        }
+
+        dataset
+    }
+}
+
+impl FacebookArchive {
+    /// Load every section found in a folder. Newer exports group sections under
+    /// folders like `your_facebook_activity`, which are searched recursively.
+    fn load_folder(&mut self, path: &Path, options: &LoadOptions) -> Result<(), ParseError> {
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_dir() {
+                options.warn(&path, "Skipped unexpected file");
+                continue;
+            }
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("your_facebook_activity")
+                | Some("connections")
+                | Some("personal_information")
+                | Some("logged_information") => {
+                    self.load_folder(&path, options)?;
+                }
+                Some("posts") => {
+                    if options.should_load(Sections::POSTS, &path) {
+                        self.posts = Some(Posts::load(&path, options)?);
+                    }
+                }
+                Some("comments_and_reactions") => {
+                    if options.should_load(Sections::COMMENTS | Sections::LIKES, &path) {
+                        self.comments_and_reactions =
+                            Some(CommentsAndReactions::load(&path, options)?);
+                    }
+                }
+                Some("friends_and_followers") | Some("friends") | Some("followers") => {
+                    if options.should_load(Sections::FOLLOWERS_AND_FOLLOWING, &path) {
+                        self.friends_and_followers
+                            .get_or_insert_with(Default::default)
+                            .load_into(&path, options)?;
+                    }
+                }
+                Some("groups") => {
+                    if options.should_load(Sections::GROUPS, &path) {
+                        self.groups = Some(Groups::load(&path, options)?);
+                    }
+                }
+                Some("events") => {
+                    if options.should_load(Sections::EVENTS, &path) {
+                        self.events = Some(Events::load(&path, options)?);
+                    }
+                }
+                Some("pages") => {
+                    if options.should_load(Sections::PAGES, &path) {
+                        self.pages = Some(Pages::load(&path, options)?);
+                    }
+                }
+                Some("facebook_marketplace") | Some("marketplace") => {
+                    if options.should_load(Sections::MARKETPLACE, &path) {
+                        self.marketplace = Some(Marketplace::load(&path, options)?);
+                    }
+                }
+                Some("profile_information") => {
+                    if options.should_load(Sections::PROFILE, &path) {
+                        self.profile_information = Some(ProfileInformation::load(&path, options)?);
+                    }
+                }
+                Some("search") | Some("search_history") => {
+                    if options.should_load(Sections::SEARCH_HISTORY, &path) {
+                        self.search = Some(Search::load(&path, options)?);
+                    }
+                }
+                Some("messages") => {
+                    options.skip(&path, "Skipped messages, load them with MessengerArchive");
+                }
+                _ => {
+                    options.skip(&path, "Skipped unsupported section");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<&Path> for FacebookArchive {
+    type Error = ParseError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        FacebookArchive::load(path, &LoadOptions::default())
+    }
+}
+
+/// Most Facebook records share this shape: a timestamp, a title describing the action
+/// (e.g. "Jane commented on John's post.") and lists of data and attachments.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ActivityEntry {
+    pub timestamp: ArchiveTimestamp,
+    pub title: Option<String>,
+    #[serde(default)]
+    pub data: Vec<EntryData>,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+}
+
+impl ActivityEntry {
+    /// Normalize a post of the account. Photos and videos become attachments, and the
+    /// first shared link becomes the link.
+    pub fn to_post(&self) -> Post {
+        let data = self.attachments.iter().flat_map(|a| &a.data);
+        Post {
+            id: None,
+            author: None,
+            timestamp: Some(self.timestamp.convert()),
+            title: self.title.clone(),
+            text: self.data.iter().find_map(|data| data.post.clone()),
+            url: None,
+            link: data
+                .clone()
+                .find_map(|data| data.external_context.as_ref()?.url.clone()),
+            community: None,
+            attachments: data
+                .filter_map(|data| data.media.as_ref())
+                .map(|media| {
+                    media
+                        .file
+                        .to_attachment(AttachmentKind::from_uri(&media.file.uri))
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct EntryData {
+    pub post: Option<String>,
+    pub update_timestamp: Option<ArchiveTimestamp>,
+    pub comment: Option<CommentData>,
+    pub reaction: Option<ReactionData>,
+    pub name: Option<String>,
+    pub text: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct CommentData {
+    pub timestamp: Option<ArchiveTimestamp>,
+    pub comment: Option<String>,
+    pub author: Option<String>,
+    pub group: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ReactionData {
+    pub reaction: String,
+    pub actor: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Tag {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Attachment {
+    #[serde(default)]
+    pub data: Vec<AttachmentData>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct AttachmentData {
+    pub media: Option<Media>,
+    pub external_context: Option<ExternalContext>,
+    pub place: Option<Place>,
+    pub text: Option<String>,
+    pub name: Option<String>,
+}
+
+/// Photo or video, with the same `uri` and `creation_timestamp` as Instagram media
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Media {
+    #[serde(flatten)]
+    pub file: MediaUri,
+    pub title: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ExternalContext {
+    pub url: Option<String>,
+    pub name: Option<String>,
+    pub source: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Place {
+    pub name: Option<String>,
+    pub address: Option<String>,
+    pub url: Option<String>,
+    pub coordinate: Option<Coordinate>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Coordinate {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// Name with an optional timestamp, used by most lists of people, pages and groups
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct NameTimestamp {
+    pub name: String,
+    pub timestamp: Option<ArchiveTimestamp>,
+}
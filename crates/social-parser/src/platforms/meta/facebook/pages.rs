@@ -0,0 +1,172 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::ActivityEntry;
+use crate::{
+    common::{ArchiveTimestamp, ItemCount, LoadOptions, ParseError},
+    platforms::meta::read_json,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Pages {
+    pub liked: Option<LikedPages>,
+    pub followed: Option<FollowedPages>,
+    pub unfollowed: Option<UnfollowedPages>,
+}
+
+impl Pages {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Pages, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in Pages: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("Pages", path)?;
+
+        let mut liked = None;
+        let mut followed = None;
+        let mut unfollowed = None;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_file() {
+                options.skip(&path, "Skipped unsupported section");
+                continue;
+            }
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("pages_you've_liked.json") | Some("pages.json") => {
+                    liked = Some(options.load_file::<LikedPages>(&path)?);
+                }
+                Some("pages_you_follow.json") => {
+                    followed = Some(options.load_file::<FollowedPages>(&path)?);
+                }
+                Some("pages_you've_unfollowed.json") => {
+                    unfollowed = Some(options.load_file::<UnfollowedPages>(&path)?);
+                }
+                _ => {
+                    options.skip(&path, "Skipped unsupported file");
+                }
+            }
+        }
+
+        Ok(Pages {
+            liked,
+            followed,
+            unfollowed,
+        })
+    }
+}
+
+impl TryFrom<&Path> for Pages {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Pages, Self::Error> {
+        Pages::load(path, &LoadOptions::default())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct PageLike {
+    pub name: String,
+    pub timestamp: ArchiveTimestamp,
+    pub url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct LikedPages {
+    #[serde(rename = "page_likes_v2")]
+    pub pages: Vec<PageLike>,
+}
+
+impl TryFrom<&Path> for LikedPages {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<LikedPages, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in LikedPages: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for LikedPages {
+    fn item_count(&self) -> usize {
+        self.pages.len()
+    }
+}
+
+/// Pages followed, with the page name in each entry's `data`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct FollowedPages {
+    #[serde(rename = "pages_followed_v2")]
+    pub pages: Vec<ActivityEntry>,
+}
+
+impl TryFrom<&Path> for FollowedPages {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<FollowedPages, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in FollowedPages: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for FollowedPages {
+    fn item_count(&self) -> usize {
+        self.pages.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct UnfollowedPages {
+    #[serde(rename = "pages_unfollowed_v2")]
+    pub pages: Vec<ActivityEntry>,
+}
+
+impl TryFrom<&Path> for UnfollowedPages {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<UnfollowedPages, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in UnfollowedPages: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for UnfollowedPages {
+    fn item_count(&self) -> usize {
+        self.pages.len()
+    }
+}
@@ -0,0 +1,94 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::ActivityEntry;
+use crate::{
+    common::{ItemCount, LoadOptions, ParseError},
+    platforms::meta::read_json,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Posts {
+    pub your_posts: Option<YourPosts>,
+}
+
+impl Posts {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Posts, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in Posts: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("Posts", path)?;
+
+        let mut your_posts: Option<YourPosts> = None;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_file() {
+                options.skip(&path, "Skipped unsupported section");
+                continue;
+            }
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                // Split into `your_posts_1.json`, `your_posts_2.json`, ... for large accounts,
+                // named `your_posts__check_ins__photos_and_videos_1.json` in newer exports
+                Some(name) if name.starts_with("your_posts") && name.ends_with(".json") => {
+                    let part = options.load_file::<YourPosts>(&path)?;
+                    your_posts
+                        .get_or_insert_with(Default::default)
+                        .0
+                        .extend(part.0);
+                }
+                _ => {
+                    options.skip(&path, "Skipped unsupported file");
+                }
+            }
+        }
+
+        Ok(Posts { your_posts })
+    }
+}
+
+impl TryFrom<&Path> for Posts {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Posts, Self::Error> {
+        Posts::load(path, &LoadOptions::default())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct YourPosts(pub Vec<ActivityEntry>);
+
+impl TryFrom<&Path> for YourPosts {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<YourPosts, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in YourPosts: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for YourPosts {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
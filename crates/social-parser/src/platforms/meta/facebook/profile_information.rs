@@ -0,0 +1,200 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::NameTimestamp;
+use crate::{
+    common::{ArchiveTimestamp, ItemCount, LoadOptions, ParseError},
+    platforms::meta::read_json,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ProfileInformation {
+    pub profile: Option<ProfileFile>,
+}
+
+impl ProfileInformation {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<ProfileInformation, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in ProfileInformation: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("ProfileInformation", path)?;
+
+        let mut profile = None;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_file() {
+                options.skip(&path, "Skipped unsupported section");
+                continue;
+            }
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("profile_information.json") => {
+                    profile = Some(options.load_file::<ProfileFile>(&path)?);
+                }
+                _ => {
+                    options.skip(&path, "Skipped unsupported file");
+                }
+            }
+        }
+
+        Ok(ProfileInformation { profile })
+    }
+}
+
+impl TryFrom<&Path> for ProfileInformation {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<ProfileInformation, Self::Error> {
+        ProfileInformation::load(path, &LoadOptions::default())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ProfileFile {
+    #[serde(rename = "profile_v2")]
+    pub profile: Profile,
+}
+
+impl TryFrom<&Path> for ProfileFile {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<ProfileFile, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in ProfileFile: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for ProfileFile {
+    fn item_count(&self) -> usize {
+        1
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Profile {
+    pub name: Option<FullName>,
+    pub emails: Option<Emails>,
+    pub birthday: Option<Birthday>,
+    pub gender: Option<Gender>,
+    pub current_city: Option<NameTimestamp>,
+    pub hometown: Option<NameTimestamp>,
+    pub relationship: Option<Relationship>,
+    #[serde(default)]
+    pub family_members: Vec<FamilyMember>,
+    #[serde(default)]
+    pub education_experiences: Vec<EducationExperience>,
+    #[serde(default)]
+    pub work_experiences: Vec<WorkExperience>,
+    #[serde(default)]
+    pub languages: Vec<NameTimestamp>,
+    #[serde(default)]
+    pub phone_numbers: Vec<PhoneNumber>,
+    pub username: Option<String>,
+    pub about_me: Option<String>,
+    pub registration_timestamp: Option<ArchiveTimestamp>,
+    pub profile_uri: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct FullName {
+    pub full_name: String,
+    pub first_name: Option<String>,
+    pub middle_name: Option<String>,
+    pub last_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Emails {
+    #[serde(default)]
+    pub emails: Vec<String>,
+    #[serde(default)]
+    pub previous_emails: Vec<String>,
+    #[serde(default)]
+    pub pending_emails: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Birthday {
+    pub year: Option<i32>,
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Gender {
+    pub gender_option: Option<String>,
+    pub pronoun: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Relationship {
+    pub status: Option<String>,
+    pub partner: Option<String>,
+    pub timestamp: Option<ArchiveTimestamp>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct FamilyMember {
+    pub name: String,
+    pub relation: Option<String>,
+    pub timestamp: Option<ArchiveTimestamp>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct EducationExperience {
+    pub name: String,
+    pub start_timestamp: Option<ArchiveTimestamp>,
+    pub end_timestamp: Option<ArchiveTimestamp>,
+    pub graduated: Option<bool>,
+    pub degree: Option<String>,
+    #[serde(default)]
+    pub concentrations: Vec<String>,
+    pub school_type: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct WorkExperience {
+    pub employer: String,
+    pub title: Option<String>,
+    pub location: Option<String>,
+    pub description: Option<String>,
+    pub start_timestamp: Option<ArchiveTimestamp>,
+    pub end_timestamp: Option<ArchiveTimestamp>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct PhoneNumber {
+    pub phone_type: Option<String>,
+    pub phone_number: String,
+    pub verified: Option<bool>,
+}
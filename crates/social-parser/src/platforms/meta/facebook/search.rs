@@ -0,0 +1,92 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::ActivityEntry;
+use crate::{
+    common::{ItemCount, LoadOptions, ParseError},
+    platforms::meta::read_json,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Search {
+    pub search_history: Option<SearchHistory>,
+}
+
+impl Search {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Search, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in Search: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("Search", path)?;
+
+        let mut search_history = None;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_file() {
+                options.skip(&path, "Skipped unsupported section");
+                continue;
+            }
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("your_search_history.json") => {
+                    search_history = Some(options.load_file::<SearchHistory>(&path)?);
+                }
+                _ => {
+                    options.skip(&path, "Skipped unsupported file");
+                }
+            }
+        }
+
+        Ok(Search { search_history })
+    }
+}
+
+impl TryFrom<&Path> for Search {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Search, Self::Error> {
+        Search::load(path, &LoadOptions::default())
+    }
+}
+
+/// Searches, with the query as `text` in each entry's `data`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct SearchHistory {
+    #[serde(rename = "searches_v2")]
+    pub searches: Vec<ActivityEntry>,
+}
+
+impl TryFrom<&Path> for SearchHistory {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<SearchHistory, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in SearchHistory: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for SearchHistory {
+    fn item_count(&self) -> usize {
+        self.searches.len()
+    }
+}
@@ -1,27 +1,23 @@
-use std::{
-    fs::File,
-    io::BufReader,
-    path::{absolute, Path},
-};
+use std::path::{absolute, Path};
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    common::ParseError,
-    platforms::meta::instagram::{MediaUri, Timestamp, Value},
+    common::{ItemCount, LoadOptions, ParseError},
+    platforms::meta::{read_json, MediaUri, Timestamp, Value},
 };
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 pub struct Comments {
     pub post_comments: Option<PostComments>,
     pub reel_comments: Option<ReelComments>,
 }
 
-impl TryFrom<&Path> for Comments {
-    type Error = ParseError;
-
-    /// Load from a directory. Assumes path is a directory.
-    fn try_from(path: &Path) -> Result<Comments, Self::Error> {
+impl Comments {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Comments, ParseError> {
         if !path.is_dir() {
             return Err(ParseError::UnexpectedFormat(format!(
                 "Found unexpected non-directory in Comments: {:?}",
@@ -29,6 +25,8 @@ impl TryFrom<&Path> for Comments {
             )));
         }
 
+        options.enter_section("Comments", path)?;
+
         let mut post_comments = None;
         let mut reel_comments = None;
 
@@ -46,10 +44,10 @@ impl TryFrom<&Path> for Comments {
 
             match path.file_name().and_then(|s| s.to_str()) {
                 Some("post_comments_1.json") => {
-                    post_comments = Some(PostComments::try_from(path.as_path())?);
+                    post_comments = Some(options.load_file::<PostComments>(&path)?);
                 }
                 Some("reels_comments.json") => {
-                    reel_comments = Some(ReelComments::try_from(path.as_path())?);
+                    reel_comments = Some(options.load_file::<ReelComments>(&path)?);
                 }
                 _ => {
                     return Err(ParseError::UnexpectedFormat(format!(
@@ -67,7 +65,17 @@ impl TryFrom<&Path> for Comments {
     }
 }
 
+impl TryFrom<&Path> for Comments {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Comments, Self::Error> {
+        Comments::load(path, &LoadOptions::default())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct PostComments(pub Vec<PostComment>);
 
@@ -83,14 +91,18 @@ impl TryFrom<&Path> for PostComments {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
+        read_json(path)
+    }
+}
 
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+impl ItemCount for PostComments {
+    fn item_count(&self) -> usize {
+        self.0.len()
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct PostComment {
     pub media_list_data: Vec<MediaUri>,
@@ -98,6 +110,7 @@ pub struct PostComment {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct CommentData {
     #[serde(rename = "Comment")]
@@ -109,12 +122,14 @@ pub struct CommentData {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct ReelComments {
     pub comments_reels_comments: Vec<ReelComment>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct ReelComment {
     pub string_map_data: CommentData,
@@ -132,9 +147,12 @@ impl TryFrom<&Path> for ReelComments {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
+        read_json(path)
+    }
+}
 
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+impl ItemCount for ReelComments {
+    fn item_count(&self) -> usize {
+        self.comments_reels_comments.len()
     }
 }
@@ -1,24 +1,23 @@
-use std::{
-    fs::File,
-    io::BufReader,
-    path::{absolute, Path},
-};
+use std::path::{absolute, Path};
 
 use serde::{Deserialize, Serialize};
 
-use crate::{common::ParseError, platforms::meta::instagram::LinkTimeValueData};
+use crate::{
+    common::{ItemCount, LoadOptions, ParseError},
+    platforms::meta::{read_json, LinkTimeValueData},
+};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 pub struct Likes {
     pub liked_comments: Option<LikedComments>,
     pub liked_posts: Option<LikedPosts>,
 }
 
-impl TryFrom<&Path> for Likes {
-    type Error = ParseError;
-
-    /// Load from a directory. Assumes path is a directory.
-    fn try_from(path: &Path) -> Result<Likes, Self::Error> {
+impl Likes {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Likes, ParseError> {
         if !path.is_dir() {
             return Err(ParseError::UnexpectedFormat(format!(
                 "Found unexpected non-directory in Likes: {:?}",
@@ -26,6 +25,8 @@ impl TryFrom<&Path> for Likes {
             )));
         }
 
+        options.enter_section("Likes", path)?;
+
         let mut liked_comments = None;
         let mut liked_posts = None;
 
@@ -43,10 +44,10 @@ impl TryFrom<&Path> for Likes {
 
             match path.file_name().and_then(|s| s.to_str()) {
                 Some("liked_comments.json") => {
-                    liked_comments = Some(LikedComments::try_from(path.as_path())?);
+                    liked_comments = Some(options.load_file::<LikedComments>(&path)?);
                 }
                 Some("liked_posts.json") => {
-                    liked_posts = Some(LikedPosts::try_from(path.as_path())?);
+                    liked_posts = Some(options.load_file::<LikedPosts>(&path)?);
                 }
                 _ => {
                     return Err(ParseError::UnexpectedFormat(format!(
@@ -64,7 +65,17 @@ impl TryFrom<&Path> for Likes {
     }
 }
 
+impl TryFrom<&Path> for Likes {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Likes, Self::Error> {
+        Likes::load(path, &LoadOptions::default())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct LikedComments {
     pub likes_comment_likes: Vec<LikedContent>,
@@ -82,14 +93,18 @@ impl TryFrom<&Path> for LikedComments {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
+        read_json(path)
+    }
+}
 
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+impl ItemCount for LikedComments {
+    fn item_count(&self) -> usize {
+        self.likes_comment_likes.len()
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct LikedPosts {
     pub likes_media_likes: Vec<LikedContent>,
@@ -107,16 +122,20 @@ impl TryFrom<&Path> for LikedPosts {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+        read_json(path)
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct LikedContent {
     pub title: Option<String>,
     pub string_list_data: Vec<LinkTimeValueData>,
 }
+
+impl ItemCount for LikedPosts {
+    fn item_count(&self) -> usize {
+        self.likes_media_likes.len()
+    }
+}
@@ -1,16 +1,21 @@
 use std::{
     collections::HashMap,
-    fs::File,
-    io::BufReader,
-    path::{absolute, Path},
+    path::{absolute, Path, PathBuf},
 };
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-use crate::{common::ParseError, platforms::meta::instagram::MediaUri};
+use crate::{
+    common::{
+        ArchiveTimestamp, AttachmentKind, Conversation, ConversationMessage, ItemCount,
+        LoadOptions, MessageKind, MessageReaction, Milliseconds, ParseError,
+    },
+    platforms::meta::{read_json, MediaUri},
+};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 pub struct Messages {
     pub inbox: Option<Inbox>,
     // photos: Photos, // TODO: Implement
@@ -20,11 +25,10 @@ pub struct Messages {
     // pub ai_conversations: Option<Vec<InboxData>>,
 } // TODO
 
-impl TryFrom<&Path> for Messages {
-    type Error = ParseError;
-
-    /// Load from a directory. Assumes path is a directory.
-    fn try_from(path: &Path) -> Result<Messages, Self::Error> {
+impl Messages {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Messages, ParseError> {
         if !path.is_dir() {
             return Err(ParseError::UnexpectedFormat(format!(
                 "Found unexpected non-directory in Messages: {:?}",
@@ -32,6 +36,8 @@ impl TryFrom<&Path> for Messages {
             )));
         }
 
+        options.enter_section("Messages", path)?;
+
         let mut inbox = None;
 
         for entry in path.read_dir()? {
@@ -47,7 +53,7 @@ impl TryFrom<&Path> for Messages {
 
             match path.file_name().and_then(|s| s.to_str()) {
                 Some("inbox") => {
-                    inbox = Some(Inbox::try_from(path.as_path())?);
+                    inbox = Some(Inbox::load(path.as_path(), options)?);
                 }
                 Some("reported_conversations.json")
                 | Some("secret_conversations.json")
@@ -59,6 +65,7 @@ impl TryFrom<&Path> for Messages {
                             absolute(path)
                         )));
                     }
+                    options.skip(&path, "Skipped unsupported conversations file");
                 }
                 Some("cross-app-inbox") | Some("message_requests") => {
                     // TODO: Implement
@@ -68,8 +75,12 @@ impl TryFrom<&Path> for Messages {
                             absolute(path)
                         )));
                     }
+                    options.skip(&path, "Skipped unsupported inbox");
+                }
+                Some("photos") => {
+                    // TODO: Implement
+                    options.skip(&path, "Skipped unsupported photos");
                 }
-                Some("photos") => {} // TODO: Implement
                 _ => {
                     return Err(ParseError::UnexpectedFormat(format!(
                         "Found unexpected file in Messages: {:?}",
@@ -83,14 +94,61 @@ impl TryFrom<&Path> for Messages {
     }
 }
 
+impl TryFrom<&Path> for Messages {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Messages, Self::Error> {
+        Messages::load(path, &LoadOptions::default())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 pub struct Inbox(pub HashMap<String, Vec<InboxData>>);
 
-impl TryFrom<&Path> for Inbox {
-    type Error = ParseError;
+impl Inbox {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Inbox, ParseError> {
+        let mut inner = HashMap::new();
+        Inbox::read(path, options, |name, paths| {
+            let parts = paths
+                .iter()
+                .map(|path| options.load_file::<InboxData>(path))
+                .collect::<Result<Vec<_>, _>>()?;
+            inner.insert(name.to_owned(), parts);
+            Ok(())
+        })?;
 
-    /// Load from a directory. Assumes path is a directory.
-    fn try_from(path: &Path) -> Result<Inbox, Self::Error> {
+        Ok(Inbox(inner))
+    }
+
+    /// Load from a directory, passing each `message_N.json` file to `on_conversation` as
+    /// it is parsed instead of keeping it. Assumes path is a directory.
+    pub fn stream<F>(
+        path: &Path,
+        options: &LoadOptions,
+        mut on_conversation: F,
+    ) -> Result<(), ParseError>
+    where
+        F: FnMut(&Path, Conversation) -> Result<(), ParseError>,
+    {
+        Inbox::read(path, options, |name, paths| {
+            for path in paths {
+                let part = options.load_file::<InboxData>(path)?;
+                on_conversation(path, part.to_conversation(name))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Pass the name of each thread folder and the paths of its `message_N.json` files to
+    /// `on_thread`
+    fn read<F>(path: &Path, options: &LoadOptions, mut on_thread: F) -> Result<(), ParseError>
+    where
+        F: FnMut(&str, &[PathBuf]) -> Result<(), ParseError>,
+    {
         if !path.is_dir() {
             return Err(ParseError::UnexpectedFormat(format!(
                 "Found unexpected non-directory in Inbox: {:?}",
@@ -98,96 +156,104 @@ impl TryFrom<&Path> for Inbox {
             )));
         }
 
+        options.enter_section("Inbox", path)?;
+
         // Define the regex pattern
         let pattern = r"^message_.*\.json$";
         let re = Regex::new(pattern).expect("Invalid regex pattern");
 
-        let inner = path
-            .read_dir()?
-            .map(|entry| {
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_dir() {
+                return Err(ParseError::UnexpectedFormat(format!(
+                    "Found unexpected non-directory in inbox: {:?}",
+                    absolute(path)
+                )));
+            }
+
+            let inbox_file_name = path.file_name().and_then(|s| s.to_str()).ok_or_else(|| {
+                ParseError::UnexpectedFormat(format!("Invalid inbox name: {:?}", absolute(&path)))
+            })?;
+
+            // Search for files inside
+            // There should only exist message_*.json
+            let mut message_json_paths = vec![];
+            for entry in path.read_dir()? {
                 let entry = entry?;
                 let path = entry.path();
 
-                if !path.is_dir() {
-                    return Err(ParseError::UnexpectedFormat(format!(
-                        "Found unexpected non-directory in inbox: {:?}",
-                        absolute(path)
-                    )));
-                }
-
-                let binding = path.clone();
-                let inbox_file_name =
-                    binding
-                        .file_name()
-                        .and_then(|s| s.to_str())
-                        .ok_or_else(|| {
-                            ParseError::UnexpectedFormat(format!(
-                                "Invalid inbox name: {:?}",
-                                absolute(path.clone())
-                            ))
-                        })?;
-
-                let parse_result = {
-                    // Search for files inside
-                    // There should only exist message_*.json
-                    let mut message_json_paths = vec![];
-                    for entry in path.read_dir()? {
-                        let entry = entry?;
-                        let path = entry.path();
-
-                        match path.file_name().and_then(|s| s.to_str()) {
-                            Some(filename) if re.is_match(filename) => {
-                                if !path.is_file() {
-                                    return Err(ParseError::UnexpectedFormat(format!(
-                                        "Found unexpected non-file in inbox: {:?}",
-                                        absolute(path)
-                                    )));
-                                }
-                                message_json_paths.push(path);
-                            }
-                            Some("files") => {} // TODO: See how to store the files inside
-                            Some("photos") => {} // TODO: See how to store the photos inside
-                            Some("videos") => {} // TODO: See how to store the videos inside
-                            Some("audio") => {} // TODO: See how to store the audio inside
-                            Some("gifs") => {}  // TODO: See how to store the gif inside
-                            _ => {
-                                return Err(ParseError::UnexpectedFormat(format!(
-                                    "Found unexpected file in inbox: {:?}",
-                                    absolute(path)
-                                )));
-                            }
+                match path.file_name().and_then(|s| s.to_str()) {
+                    Some(filename) if re.is_match(filename) => {
+                        if !path.is_file() {
+                            return Err(ParseError::UnexpectedFormat(format!(
+                                "Found unexpected non-file in inbox: {:?}",
+                                absolute(path)
+                            )));
                         }
+                        message_json_paths.push(path);
                     }
-
-                    if !message_json_paths.is_empty() {
-                        message_json_paths
-                            .into_iter()
-                            .map(|p| InboxData::try_from(p.as_ref()))
-                            .collect::<Result<Vec<_>, _>>()
-                    } else {
-                        Err(ParseError::UnexpectedFormat(format!(
-                            "Expected message_1.json in inbox '{:?}': {:?}",
-                            path.clone()
-                                .file_name()
-                                .and_then(|s| s.to_str())
-                                .unwrap_or("N/A"),
+                    Some("files") => {} // TODO: See how to store the files inside
+                    Some("photos") => {} // TODO: See how to store the photos inside
+                    Some("videos") => {} // TODO: See how to store the videos inside
+                    Some("audio") => {} // TODO: See how to store the audio inside
+                    Some("gifs") => {}  // TODO: See how to store the gif inside
+                    _ => {
+                        return Err(ParseError::UnexpectedFormat(format!(
+                            "Found unexpected file in inbox: {:?}",
                             absolute(path)
-                        )))
+                        )));
                     }
-                };
+                }
+            }
+
+            if message_json_paths.is_empty() {
+                return Err(ParseError::UnexpectedFormat(format!(
+                    "Expected message_1.json in inbox '{:?}': {:?}",
+                    inbox_file_name,
+                    absolute(&path)
+                )));
+            }
+            // In order of the parts, `message_1.json` first
+            message_json_paths.sort();
+
+            on_thread(inbox_file_name, &message_json_paths)?;
+        }
+
+        Ok(())
+    }
 
-                match parse_result {
-                    Ok(data) => Ok((inbox_file_name.to_owned(), data)),
-                    Err(e) => Err(e),
+    /// Normalize every thread, merging threads split over several `message_N.json` files
+    pub fn conversations(&self) -> Vec<Conversation> {
+        self.0
+            .iter()
+            .filter_map(|(id, parts)| {
+                let mut parts = parts.iter().map(|part| part.to_conversation(id));
+                let mut conversation = parts.next()?;
+                for part in parts {
+                    conversation.messages.extend(part.messages);
                 }
+                conversation
+                    .messages
+                    .sort_by_key(|message| message.timestamp);
+                Some(conversation)
             })
-            .collect::<Result<HashMap<_, _>, ParseError>>()?;
+            .collect()
+    }
+}
 
-        Ok(Inbox(inner))
+impl TryFrom<&Path> for Inbox {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Inbox, Self::Error> {
+        Inbox::load(path, &LoadOptions::default())
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct InboxData {
     pub participants: Vec<Participant>,
@@ -212,24 +278,48 @@ impl TryFrom<&Path> for InboxData {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
+        read_json(path)
+    }
+}
+
+impl InboxData {
+    /// Normalize the part of a thread held in one `message_N.json` file
+    pub fn to_conversation(&self, id: &str) -> Conversation {
+        let mut messages: Vec<ConversationMessage> = self
+            .messages
+            .iter()
+            .map(ConversationMessage::from)
+            .collect();
+        messages.sort_by_key(|message| message.timestamp);
+
+        Conversation {
+            id: id.to_owned(),
+            title: Some(self.title.clone()),
+            participants: self.participants.iter().map(|p| p.name.clone()).collect(),
+            messages,
+        }
+    }
+}
 
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+impl ItemCount for InboxData {
+    fn item_count(&self) -> usize {
+        self.messages.len()
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct Participant {
     pub name: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct Message {
     pub sender_name: String,
-    pub timestamp_ms: u64,
+    pub timestamp_ms: ArchiveTimestamp<Milliseconds>,
     pub content: Option<String>,
     pub photos: Option<Vec<MediaUri>>,
     pub videos: Option<Vec<MediaUri>>,
@@ -244,7 +334,58 @@ pub struct Message {
     pub reactions: Option<Vec<Reaction>>,
 }
 
+impl From<&Message> for ConversationMessage {
+    fn from(message: &Message) -> Self {
+        let mut normalized =
+            ConversationMessage::new(message.sender_name.clone(), Some(message.timestamp_ms));
+        normalized.content = message.content.clone();
+        normalized.is_unsent = message.is_unsent.unwrap_or(false);
+
+        if let Some(share) = &message.share {
+            normalized.kind = MessageKind::Share {
+                link: share.link.clone(),
+                text: share.share_text.clone(),
+            };
+        } else if message.call_duration.is_some() {
+            normalized.kind = MessageKind::Call {
+                duration_secs: message.call_duration.map(u64::from),
+                missed: message.call_duration == Some(0),
+            };
+        }
+
+        let media = [
+            (AttachmentKind::Photo, &message.photos),
+            (AttachmentKind::Video, &message.videos),
+            (AttachmentKind::Gif, &message.gifs),
+            (AttachmentKind::File, &message.files),
+            (AttachmentKind::Audio, &message.audio_files),
+        ];
+        normalized.attachments = media
+            .into_iter()
+            .flat_map(|(kind, uris)| {
+                uris.iter()
+                    .flatten()
+                    .map(move |uri| uri.to_attachment(kind))
+            })
+            .collect();
+
+        normalized.reactions = message
+            .reactions
+            .iter()
+            .flatten()
+            .map(|reaction| MessageReaction {
+                reaction: reaction.reaction.clone(),
+                actor: reaction.actor.clone(),
+                timestamp: reaction.timestamp.map(|t| t.convert()),
+            })
+            .collect();
+
+        normalized
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct Share {
     pub link: Option<String>,
@@ -255,6 +396,7 @@ pub struct Share {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct Reaction {
     /// Emoji reaction in unicode
@@ -262,10 +404,11 @@ pub struct Reaction {
     /// User who reacted
     pub actor: String,
     /// Timestamp of the reaction in seconds
-    pub timestamp: Option<u64>,
+    pub timestamp: Option<ArchiveTimestamp>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct JoinableMode {
     pub mode: u32,
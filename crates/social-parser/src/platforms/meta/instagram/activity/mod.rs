@@ -13,9 +13,10 @@ use saved::Saved;
 use serde::{Deserialize, Serialize};
 use threads::Threads;
 
-use crate::common::ParseError;
+use crate::common::{LoadOptions, ParseError, Sections};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 pub struct Activity {
     pub comments: Option<Comments>,
     pub likes: Option<Likes>,
@@ -24,11 +25,10 @@ pub struct Activity {
     pub threads: Option<Threads>,
 }
 
-impl TryFrom<&Path> for Activity {
-    type Error = ParseError;
-
-    /// Load from a directory. Assumes path is a directory.
-    fn try_from(path: &Path) -> Result<Activity, Self::Error> {
+impl Activity {
+    /// Load from a directory, only parsing the sections selected in `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Activity, ParseError> {
         if !path.is_dir() {
             return Err(ParseError::UnexpectedFormat(format!(
                 "Found unexpected non-directory in Activity: {:?}",
@@ -36,11 +36,13 @@ impl TryFrom<&Path> for Activity {
             )));
         }
 
+        options.enter_section("Activity", path)?;
+
         let mut comments = None;
         let mut likes = None;
         let mut messages = None;
         let mut saved = None;
-        let mut threads: Option<Threads> = None;
+        let mut threads = None;
 
         for entry in path.read_dir()? {
             let entry = entry?;
@@ -56,40 +58,77 @@ impl TryFrom<&Path> for Activity {
 
             match path.file_name().and_then(|s| s.to_str()) {
                 Some("comments") => {
-                    comments = Some(Comments::try_from(path.as_ref())?);
+                    if options.should_load(Sections::COMMENTS, &path) {
+                        comments = Some(Comments::load(path.as_ref(), options)?);
+                    }
+                }
+                Some("content") => {
+                    options.skip(&path, "Skipped unsupported section");
+                }
+                Some("events") => {
+                    options.skip(&path, "Skipped unsupported section");
+                }
+                Some("fundraisers") => {
+                    options.skip(&path, "Skipped unsupported section");
+                }
+                Some("gifts") => {
+                    options.skip(&path, "Skipped unsupported section");
+                }
+                Some("instagram_live") => {
+                    options.skip(&path, "Skipped unsupported section");
                 }
-                Some("content") => {}
-                Some("events") => {}
-                Some("fundraisers") => {}
-                Some("gifts") => {}
-                Some("instagram_live") => {}
                 Some("likes") => {
-                    likes = Some(Likes::try_from(path.as_ref())?);
+                    if options.should_load(Sections::LIKES, &path) {
+                        likes = Some(Likes::load(path.as_ref(), options)?);
+                    }
                 }
                 Some("media") => {
                     // todo!("Parsing for media not implemented yet");
+                    options.skip(&path, "Skipped unsupported section");
                 }
                 Some("messages") => {
-                    messages = Some(Messages::try_from(path.as_ref())?);
+                    if options.should_load(Sections::MESSAGES, &path) {
+                        messages = Some(Messages::load(path.as_ref(), options)?);
+                    }
+                }
+                Some("meta_spark") => {
+                    options.skip(&path, "Skipped unsupported section");
+                }
+                Some("monetization") => {
+                    options.skip(&path, "Skipped unsupported section");
+                }
+                Some("other_activity") => {
+                    options.skip(&path, "Skipped unsupported section");
+                }
+                Some("reports") => {
+                    options.skip(&path, "Skipped unsupported section");
                 }
-                Some("meta_spark") => {}
-                Some("monetization") => {}
-                Some("other_activity") => {}
-                Some("reports") => {}
                 Some("saved") => {
-                    saved = Some(Saved::try_from(path.as_ref())?);
+                    if options.should_load(Sections::SAVED, &path) {
+                        saved = Some(Saved::load(path.as_ref(), options)?);
+                    }
+                }
+                Some("shopping") => {
+                    options.skip(&path, "Skipped unsupported section");
+                }
+                Some("story_sticker_interactions") => {
+                    options.skip(&path, "Skipped unsupported section");
+                }
+                Some("subscriptions") => {
+                    options.skip(&path, "Skipped unsupported section");
                 }
-                Some("shopping") => {}
-                Some("story_sticker_interactions") => {}
-                Some("subscriptions") => {}
                 Some("threads") => {
-                    // threads = Some(Threads::try_from(path.as_ref())?); // TODO: Implement
+                    if options.should_load(Sections::THREADS, &path) {
+                        threads = Some(Threads::load(path.as_ref(), options)?);
+                    }
                 }
                 Some("avatars_store") => {
                     // todo!("Parsing for avatars store not implemented yet");
+                    options.skip(&path, "Skipped unsupported section");
                 }
                 Some("story_interactions") => {
                     // todo!("Parsing for story interactions not implemented yet");
+                    options.skip(&path, "Skipped unsupported section");
                 }
                 _ => {
                     return Err(ParseError::UnexpectedFormat(format!(
@@ -109,3 +148,12 @@ impl TryFrom<&Path> for Activity {
         })
     }
 }
+
+impl TryFrom<&Path> for Activity {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Activity, Self::Error> {
+        Activity::load(path, &LoadOptions::default())
+    }
+}
@@ -1,27 +1,23 @@
-use std::{
-    fs::File,
-    io::BufReader,
-    path::{absolute, Path},
-};
+use std::path::{absolute, Path};
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    common::ParseError,
-    platforms::meta::instagram::{LinkData, LinkTimeData, LinkTimeValueData, Timestamp, Value},
+    common::{ItemCount, LoadOptions, ParseError},
+    platforms::meta::{read_json, LinkData, LinkTimeData, Timestamp, Value},
 };
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 pub struct Saved {
     pub saved_collections: Option<SavedCollections>,
     pub saved_posts: Option<SavedPosts>,
 }
 
-impl TryFrom<&Path> for Saved {
-    type Error = ParseError;
-
-    /// Load from a directory. Assumes path is a directory.
-    fn try_from(path: &Path) -> Result<Saved, Self::Error> {
+impl Saved {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Saved, ParseError> {
         if !path.is_dir() {
             return Err(ParseError::UnexpectedFormat(format!(
                 "Found unexpected non-directory in Saved: {:?}",
@@ -29,6 +25,8 @@ impl TryFrom<&Path> for Saved {
             )));
         }
 
+        options.enter_section("Saved", path)?;
+
         let mut saved_collections = None;
         let mut saved_posts = None;
 
@@ -46,10 +44,10 @@ impl TryFrom<&Path> for Saved {
 
             match path.file_name().and_then(|s| s.to_str()) {
                 Some("saved_collections.json") => {
-                    saved_collections = Some(SavedCollections::try_from(path.as_path())?);
+                    saved_collections = Some(options.load_file::<SavedCollections>(&path)?);
                 }
                 Some("saved_posts.json") => {
-                    saved_posts = Some(SavedPosts::try_from(path.as_path())?);
+                    saved_posts = Some(options.load_file::<SavedPosts>(&path)?);
                 }
                 _ => {
                     return Err(ParseError::UnexpectedFormat(format!(
@@ -67,7 +65,17 @@ impl TryFrom<&Path> for Saved {
     }
 }
 
+impl TryFrom<&Path> for Saved {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Saved, Self::Error> {
+        Saved::load(path, &LoadOptions::default())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct SavedCollections {
     pub saved_saved_collections: Vec<SavedCollectionData>,
@@ -85,14 +93,18 @@ impl TryFrom<&Path> for SavedCollections {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
+        read_json(path)
+    }
+}
 
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+impl ItemCount for SavedCollections {
+    fn item_count(&self) -> usize {
+        self.saved_saved_collections.len()
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(untagged)]
 pub enum SavedCollectionData {
     Album(SavedCollectionAlbum),
@@ -100,6 +112,7 @@ pub enum SavedCollectionData {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct SavedCollectionAlbum {
     pub title: String,
@@ -107,6 +120,7 @@ pub struct SavedCollectionAlbum {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct SavedCollectionAlbumData {
     #[serde(rename = "Name")]
@@ -118,12 +132,14 @@ pub struct SavedCollectionAlbumData {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct SavedCollectionEntry {
     pub string_map_data: SavedCollectionEntryData,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct SavedCollectionEntryData {
     #[serde(rename = "Name")]
@@ -133,6 +149,7 @@ pub struct SavedCollectionEntryData {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct SavedPosts {
     pub saved_saved_media: Vec<SavedContent>,
@@ -150,14 +167,12 @@ impl TryFrom<&Path> for SavedPosts {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+        read_json(path)
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct SavedContent {
     pub title: String,
@@ -165,8 +180,15 @@ pub struct SavedContent {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct SavedContentData {
     #[serde(rename = "Saved on")]
-    saved_on: LinkTimeData,
+    pub saved_on: LinkTimeData,
+}
+
+impl ItemCount for SavedPosts {
+    fn item_count(&self) -> usize {
+        self.saved_saved_media.len()
+    }
 }
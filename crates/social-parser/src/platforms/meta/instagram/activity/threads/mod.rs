@@ -1,4 +1,4 @@
-use serde::{Deserialize, Serialize};
+//! Threads data included in the Instagram export, shared with the standalone Threads
+//! archive so both give the same view.
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
-pub struct Threads {} // TODO
+pub use crate::platforms::meta::threads::{interactions, posts, Threads};
@@ -1,22 +1,21 @@
 use serde::{Deserialize, Serialize};
-use std::{
-    fs::File,
-    io::BufReader,
-    path::{absolute, Path},
-};
+use std::path::{absolute, Path};
 
-use crate::{common::ParseError, platforms::meta::instagram::LinkTimeValueData};
+use crate::{
+    common::{ItemCount, LoadOptions, ParseError},
+    platforms::meta::{read_json, LinkTimeValueData},
+};
 
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 pub struct Contacts {
     pub synced_contacts: Option<SyncedContacts>,
 }
 
-impl TryFrom<&Path> for Contacts {
-    type Error = ParseError;
-
-    /// Load from a directory. Assumes path is a directory.
-    fn try_from(path: &Path) -> Result<Contacts, Self::Error> {
+impl Contacts {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Contacts, ParseError> {
         if !path.is_dir() {
             return Err(ParseError::UnexpectedFormat(format!(
                 "Found unexpected non-directory in Contacts: {:?}",
@@ -24,6 +23,8 @@ impl TryFrom<&Path> for Contacts {
             )));
         }
 
+        options.enter_section("Contacts", path)?;
+
         let mut synced_contacts = None;
 
         for entry in path.read_dir()? {
@@ -40,7 +41,7 @@ impl TryFrom<&Path> for Contacts {
 
             match path.file_name().and_then(|s| s.to_str()) {
                 Some("synced_contacts.json") => {
-                    synced_contacts = Some(SyncedContacts::try_from(path.as_path())?);
+                    synced_contacts = Some(options.load_file::<SyncedContacts>(&path)?);
                 }
                 _ => {
                     return Err(ParseError::UnexpectedFormat(format!(
@@ -55,7 +56,17 @@ impl TryFrom<&Path> for Contacts {
     }
 }
 
+impl TryFrom<&Path> for Contacts {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Contacts, Self::Error> {
+        Contacts::load(path, &LoadOptions::default())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct SyncedContacts {
     pub contacts_contact_info: Vec<ContactInfo>,
@@ -73,14 +84,18 @@ impl TryFrom<&Path> for SyncedContacts {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
+        read_json(path)
+    }
+}
 
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+impl ItemCount for SyncedContacts {
+    fn item_count(&self) -> usize {
+        self.contacts_contact_info.len()
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct ContactInfo {
     pub title: String,
@@ -89,10 +104,12 @@ pub struct ContactInfo {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct MediaMapData {}
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct ContactStringMapData {
     #[serde(rename = "First Name")]
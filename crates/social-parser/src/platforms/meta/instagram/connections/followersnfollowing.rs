@@ -1,13 +1,13 @@
 use serde::{Deserialize, Serialize};
-use std::{
-    fs::File,
-    io::BufReader,
-    path::{absolute, Path},
-};
+use std::path::{absolute, Path};
 
-use crate::{common::ParseError, platforms::meta::instagram::LinkTimeValueData};
+use crate::{
+    common::{ItemCount, LoadOptions, ParseError},
+    platforms::meta::{read_json, LinkTimeValueData},
+};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 pub struct FollowersNFollowing {
     pub favourite_accounts: Option<FavouriteAccounts>,
     pub blocked_accounts: Option<BlockedAccounts>,
@@ -22,11 +22,10 @@ pub struct FollowersNFollowing {
     pub restricted_accounts: Option<RestrictedAccounts>,
 }
 
-impl TryFrom<&Path> for FollowersNFollowing {
-    type Error = ParseError;
-
-    /// Load from a file. Assumes path is a file.
-    fn try_from(path: &Path) -> Result<FollowersNFollowing, Self::Error> {
+impl FollowersNFollowing {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<FollowersNFollowing, ParseError> {
         if !path.is_dir() {
             return Err(ParseError::UnexpectedFormat(format!(
                 "Found unexpected non-directory in FollowersNFollowing: {:?}",
@@ -34,6 +33,8 @@ impl TryFrom<&Path> for FollowersNFollowing {
             )));
         }
 
+        options.enter_section("FollowersNFollowing", path)?;
+
         let mut favourite_accounts = None;
         let mut blocked_accounts = None;
         let mut close_friends = None;
@@ -60,38 +61,39 @@ impl TryFrom<&Path> for FollowersNFollowing {
 
             match path.file_name().and_then(|s| s.to_str()) {
                 Some("accounts_you've_favorited.json") => {
-                    favourite_accounts = Some(FavouriteAccounts::try_from(path.as_path())?);
+                    favourite_accounts = Some(options.load_file::<FavouriteAccounts>(&path)?);
                 }
                 Some("blocked_accounts.json") => {
-                    blocked_accounts = Some(BlockedAccounts::try_from(path.as_path())?);
+                    blocked_accounts = Some(options.load_file::<BlockedAccounts>(&path)?);
                 }
                 Some("close_friends.json") => {
-                    close_friends = Some(CloseFriends::try_from(path.as_path())?);
+                    close_friends = Some(options.load_file::<CloseFriends>(&path)?);
                 }
                 Some("followers_1.json") => {
-                    followers = Some(Followers::try_from(path.as_path())?);
+                    followers = Some(options.load_file::<Followers>(&path)?);
                 }
                 Some("following.json") => {
-                    following = Some(Following::try_from(path.as_path())?);
+                    following = Some(options.load_file::<Following>(&path)?);
                 }
                 Some("hide_story_from.json") => {
-                    hide_story_from = Some(HideStoryFrom::try_from(path.as_path())?);
+                    hide_story_from = Some(options.load_file::<HideStoryFrom>(&path)?);
                 }
                 Some("pending_follow_requests.json") => {
                     pending_follow_requests =
-                        Some(PendingFollowRequests::try_from(path.as_path())?);
+                        Some(options.load_file::<PendingFollowRequests>(&path)?);
                 }
                 Some("recent_follow_requests.json") => {
-                    recent_follow_requests = Some(RecentFollowRequests::try_from(path.as_path())?);
+                    recent_follow_requests =
+                        Some(options.load_file::<RecentFollowRequests>(&path)?);
                 }
                 Some("recently_unfollowed_accounts.json") => {
-                    recently_unfollowed = Some(RecentlyUnfollowed::try_from(path.as_path())?);
+                    recently_unfollowed = Some(options.load_file::<RecentlyUnfollowed>(&path)?);
                 }
                 Some("removed_suggestions.json") => {
-                    removed_suggestions = Some(RemovedSuggestions::try_from(path.as_path())?);
+                    removed_suggestions = Some(options.load_file::<RemovedSuggestions>(&path)?);
                 }
                 Some("restricted_accounts.json") => {
-                    restricted_accounts = Some(RestrictedAccounts::try_from(path.as_path())?);
+                    restricted_accounts = Some(options.load_file::<RestrictedAccounts>(&path)?);
                 }
                 _ => {
                     return Err(ParseError::UnexpectedFormat(format!(
@@ -118,7 +120,17 @@ impl TryFrom<&Path> for FollowersNFollowing {
     }
 }
 
+impl TryFrom<&Path> for FollowersNFollowing {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<FollowersNFollowing, Self::Error> {
+        FollowersNFollowing::load(path, &LoadOptions::default())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct FavouriteAccounts {
     pub relationships_feed_favorites: Vec<FavouriteAccount>,
@@ -136,14 +148,18 @@ impl TryFrom<&Path> for FavouriteAccounts {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
+        read_json(path)
+    }
+}
 
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+impl ItemCount for FavouriteAccounts {
+    fn item_count(&self) -> usize {
+        self.relationships_feed_favorites.len()
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct FavouriteAccount {
     pub title: String,
@@ -152,6 +168,7 @@ pub struct FavouriteAccount {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct BlockedAccounts {
     pub relationships_blocked_users: Vec<BlockedAccount>,
@@ -169,14 +186,12 @@ impl TryFrom<&Path> for BlockedAccounts {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+        read_json(path)
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct BlockedAccount {
     pub title: String,
@@ -184,6 +199,7 @@ pub struct BlockedAccount {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct CloseFriends {
     pub relationships_close_friends: Vec<Relationship>,
@@ -201,14 +217,18 @@ impl TryFrom<&Path> for CloseFriends {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
+        read_json(path)
+    }
+}
 
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+impl ItemCount for CloseFriends {
+    fn item_count(&self) -> usize {
+        self.relationships_close_friends.len()
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct Followers(pub Vec<Relationship>);
 
@@ -224,14 +244,12 @@ impl TryFrom<&Path> for Followers {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+        read_json(path)
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct Following {
     pub relationships_following: Vec<Relationship>,
@@ -249,14 +267,18 @@ impl TryFrom<&Path> for Following {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
+        read_json(path)
+    }
+}
 
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+impl ItemCount for Following {
+    fn item_count(&self) -> usize {
+        self.relationships_following.len()
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct HideStoryFrom {
     pub relationships_hide_stories_from: Vec<Relationship>,
@@ -274,14 +296,12 @@ impl TryFrom<&Path> for HideStoryFrom {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+        read_json(path)
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct PendingFollowRequests {
     pub relationships_follow_requests_sent: Vec<Relationship>,
@@ -299,14 +319,18 @@ impl TryFrom<&Path> for PendingFollowRequests {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
+        read_json(path)
+    }
+}
 
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+impl ItemCount for PendingFollowRequests {
+    fn item_count(&self) -> usize {
+        self.relationships_follow_requests_sent.len()
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct RecentFollowRequests {
     pub relationships_permanent_follow_requests: Vec<Relationship>,
@@ -324,14 +348,12 @@ impl TryFrom<&Path> for RecentFollowRequests {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+        read_json(path)
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct RecentlyUnfollowed {
     pub relationships_unfollowed_users: Vec<Relationship>,
@@ -349,14 +371,18 @@ impl TryFrom<&Path> for RecentlyUnfollowed {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
+        read_json(path)
+    }
+}
 
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+impl ItemCount for RecentlyUnfollowed {
+    fn item_count(&self) -> usize {
+        self.relationships_unfollowed_users.len()
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct RemovedSuggestions {
     pub relationships_dismissed_suggested_users: Vec<Relationship>,
@@ -374,14 +400,12 @@ impl TryFrom<&Path> for RemovedSuggestions {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+        read_json(path)
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct RestrictedAccounts {
     pub relationships_restricted_users: Vec<Relationship>,
@@ -399,17 +423,51 @@ impl TryFrom<&Path> for RestrictedAccounts {
             )));
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
+        read_json(path)
+    }
+}
 
-        serde_json::from_reader(reader).map_err(|e| ParseError::Serde(path.to_owned(), e))
+impl ItemCount for RestrictedAccounts {
+    fn item_count(&self) -> usize {
+        self.relationships_restricted_users.len()
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(deny_unknown_fields)]
 pub struct Relationship {
     pub title: String,
     pub media_list_data: Vec<()>,
     pub string_list_data: Vec<LinkTimeValueData>,
 }
+
+impl ItemCount for BlockedAccounts {
+    fn item_count(&self) -> usize {
+        self.relationships_blocked_users.len()
+    }
+}
+
+impl ItemCount for Followers {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl ItemCount for HideStoryFrom {
+    fn item_count(&self) -> usize {
+        self.relationships_hide_stories_from.len()
+    }
+}
+
+impl ItemCount for RecentFollowRequests {
+    fn item_count(&self) -> usize {
+        self.relationships_permanent_follow_requests.len()
+    }
+}
+
+impl ItemCount for RemovedSuggestions {
+    fn item_count(&self) -> usize {
+        self.relationships_dismissed_suggested_users.len()
+    }
+}
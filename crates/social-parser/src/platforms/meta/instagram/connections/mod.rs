@@ -6,9 +6,10 @@ use followersnfollowing::FollowersNFollowing;
 use serde::{Deserialize, Serialize};
 use std::path::{absolute, Path};
 
-use crate::common::ParseError;
+use crate::common::{LoadOptions, ParseError, Sections};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 pub struct Connections {
     pub contacts: Option<Contacts>,
     pub followers_n_following: Option<FollowersNFollowing>,
@@ -17,9 +18,14 @@ pub struct Connections {
 impl Connections {
     /// Load from a folder
     /// Assumes path is a directory.
-    pub(super) fn from_folder<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+    pub(super) fn from_folder<P: AsRef<Path>>(
+        path: P,
+        options: &LoadOptions,
+    ) -> Result<Self, ParseError> {
         assert!(path.as_ref().is_dir());
 
+        options.enter_section("Connections", path.as_ref())?;
+
         // Load all directories in the directory
         let mut contacts = None;
         let mut followers_n_following = None;
@@ -38,10 +44,15 @@ impl Connections {
 
             match path.file_stem().and_then(|s| s.to_str()) {
                 Some("contacts") => {
-                    contacts = Some(Contacts::try_from(path.as_path())?);
+                    if options.should_load(Sections::CONTACTS, &path) {
+                        contacts = Some(Contacts::load(path.as_path(), options)?);
+                    }
                 }
                 Some("followers_and_following") => {
-                    followers_n_following = Some(FollowersNFollowing::try_from(path.as_path())?);
+                    if options.should_load(Sections::FOLLOWERS_AND_FOLLOWING, &path) {
+                        followers_n_following =
+                            Some(FollowersNFollowing::load(path.as_path(), options)?);
+                    }
                 }
                 _ => {
                     return Err(ParseError::UnexpectedFormat(format!(
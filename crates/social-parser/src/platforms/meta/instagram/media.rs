@@ -2,18 +2,25 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
-use crate::common::ParseError;
+use crate::common::{LoadOptions, ParseError};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 pub struct Media; // TODO: Complete
 
 impl Media {
     /// Load from a folder
     /// Assumes path is a directory.
-    pub(super) fn from_folder<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+    pub(super) fn from_folder<P: AsRef<Path>>(
+        path: P,
+        options: &LoadOptions,
+    ) -> Result<Self, ParseError> {
         assert!(path.as_ref().is_dir());
 
+        options.enter_section("Media", path.as_ref())?;
+
         // TODO: Implement
+        options.skip(path.as_ref(), "Skipped unsupported section");
         Ok(Media)
     }
 }
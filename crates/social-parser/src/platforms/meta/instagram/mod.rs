@@ -4,26 +4,235 @@ pub mod media;
 
 use std::path::{absolute, Path};
 
-use activity::Activity;
+use activity::{messages::Inbox, saved::SavedCollectionData, Activity};
 use connections::Connections;
 use media::Media;
 use serde::{Deserialize, Serialize};
 
-use crate::common::{ParseError, WriteError};
+pub use super::{LinkData, LinkTimeData, LinkTimeValueData, MediaUri, Timestamp, Value};
+use crate::common::{
+    Archive, Comment, Conversation, Dataset, Follow, FollowDirection, Like, LoadOptions,
+    ParseError, SavedItem, Sections, StreamingArchive,
+};
+
+#[cfg(feature = "typescript")]
+pub(crate) use typescript::*;
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS), ts(export))]
 pub struct InstagramArchive {
     pub connections: Option<Connections>,
     pub media: Option<Media>,
     pub activity: Option<Activity>,
 }
 
+impl Archive for InstagramArchive {
+    /// Load from the extracted export directory, only parsing the sections selected in
+    /// `options`. Zip files aren't supported yet and fail with
+    /// [`ParseError::UnexpectedFormat`].
+    fn load<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        if path.is_dir() {
+            // Parse as root directory
+            InstagramArchive::from_folder(path, options)
+        } else if path
+            .extension()
+            .map(|ext| ext.to_str() == Some("zip"))
+            .unwrap_or(false)
+        {
+            Err(ParseError::UnexpectedFormat(format!(
+                "Zip files aren't supported for Instagram yet, extract the archive first: {:?}",
+                absolute(path)
+            )))
+        } else {
+            Err(ParseError::UnexpectedFormat(format!(
+                "Expected a directory, found: {:?}",
+                absolute(path)
+            )))
+        }
+    }
+
+    fn to_dataset(&self) -> Dataset {
+        let mut dataset = Dataset::new("instagram");
+
+        if let Some(relationships) = self
+            .connections
+            .as_ref()
+            .and_then(|connections| connections.followers_n_following.as_ref())
+        {
+            let followers = relationships
+                .followers
+                .iter()
+                .flat_map(|followers| &followers.0)
+                .map(|relationship| (FollowDirection::Follower, relationship));
+            let following = relationships
+                .following
+                .iter()
+                .flat_map(|following| &following.relationships_following)
+                .map(|relationship| (FollowDirection::Following, relationship));
+            dataset.follows = followers
+                .chain(following)
+                .filter_map(|(direction, relationship)| {
+                    let data = relationship.string_list_data.first();
+                    // Recent exports leave the title empty and put the username in the value
+                    let name = Some(relationship.title.clone())
+                        .filter(|title| !title.is_empty())
+                        .or_else(|| data.and_then(|data| data.value.clone()))?;
+                    Some(Follow {
+                        direction,
+                        name,
+                        url: data.map(|data| data.href.clone()),
+                        timestamp: data.map(|data| data.timestamp.convert()),
+                    })
+                })
+                .collect();
+        }
+
+        let Some(activity) = &self.activity else {
+            return dataset;
+        };
+
+        if let Some(inbox) = activity
+            .messages
+            .as_ref()
+            .and_then(|messages| messages.inbox.as_ref())
+        {
+            dataset.conversations = inbox.conversations();
+        }
+
+        if let Some(comments) = &activity.comments {
+            let post_comments = comments
+                .post_comments
+                .iter()
+                .flat_map(|comments| &comments.0)
+                .map(|comment| &comment.string_map_data);
+            let reel_comments = comments
+                .reel_comments
+                .iter()
+                .flat_map(|comments| &comments.comments_reels_comments)
+                .map(|comment| &comment.string_map_data);
+            dataset.comments = post_comments
+                .chain(reel_comments)
+                .map(|comment| Comment {
+                    id: None,
+                    author: None,
+                    timestamp: Some(comment.time.timestamp.convert()),
+                    text: comment.comment.value.clone(),
+                    url: None,
+                    post: None,
+                    parent: None,
+                    community: None,
+                })
+                .collect();
+        }
+
+        if let Some(likes) = &activity.likes {
+            let liked_posts = likes
+                .liked_posts
+                .iter()
+                .flat_map(|likes| &likes.likes_media_likes);
+            let liked_comments = likes
+                .liked_comments
+                .iter()
+                .flat_map(|likes| &likes.likes_comment_likes);
+            dataset.likes = liked_posts
+                .chain(liked_comments)
+                .map(|like| {
+                    let data = like.string_list_data.first();
+                    Like {
+                        target: data.map(|data| data.href.clone()),
+                        title: like.title.clone(),
+                        reaction: data.and_then(|data| data.value.clone()),
+                        timestamp: data.map(|data| data.timestamp.convert()),
+                    }
+                })
+                .collect();
+        }
+
+        if let Some(saved) = &activity.saved {
+            let saved_posts = saved
+                .saved_posts
+                .iter()
+                .flat_map(|saved| &saved.saved_saved_media)
+                .map(|post| SavedItem {
+                    target: Some(post.string_map_data.saved_on.href.clone()),
+                    title: Some(post.title.clone()),
+                    collection: None,
+                    timestamp: Some(post.string_map_data.saved_on.timestamp.convert()),
+                });
+            dataset.saved_items.extend(saved_posts);
+
+            // Each collection is listed as an album followed by its entries
+            let mut collection = None;
+            for data in saved
+                .saved_collections
+                .iter()
+                .flat_map(|collections| &collections.saved_saved_collections)
+            {
+                match data {
+                    SavedCollectionData::Album(album) => {
+                        collection = Some(album.string_map_data.name.value.clone());
+                    }
+                    SavedCollectionData::Entry(entry) => {
+                        let data = &entry.string_map_data;
+                        dataset.saved_items.push(SavedItem {
+                            target: Some(data.name.href.clone()),
+                            title: data.name.value.clone(),
+                            collection: collection.clone(),
+                            timestamp: Some(data.added_time.timestamp.convert()),
+                        });
+                    }
+                }
+            }
+        }
+
+        if let Some(threads) = &activity.threads {
+            threads.add_to_dataset(&mut dataset);
+        }
+
+        dataset
+    }
+}
+
+impl StreamingArchive for InstagramArchive {
+    const PLATFORM: &'static str = "instagram";
+
+    /// Load from the extracted export directory, passing the messages of each
+    /// `message_N.json` file in the inbox to `on_conversation` instead of keeping them
+    fn stream_conversations<P, F>(
+        path: P,
+        options: &LoadOptions,
+        on_conversation: F,
+    ) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&Path, Conversation) -> Result<(), ParseError>,
+    {
+        let path = path.as_ref();
+        let archive = InstagramArchive::load(
+            path,
+            &options
+                .clone()
+                .with_sections(options.sections - Sections::MESSAGES),
+        )?;
+
+        let inbox = path.join("your_instagram_activity/messages/inbox");
+        if inbox.is_dir() && options.should_load(Sections::MESSAGES, &inbox) {
+            Inbox::stream(&inbox, options, on_conversation)?;
+        }
+
+        Ok(archive)
+    }
+}
+
 impl InstagramArchive {
     /// Load from a folder
     /// Assumes path is a directory.
-    fn from_folder<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+    fn from_folder<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError> {
         assert!(path.as_ref().is_dir());
 
+        options.enter_section("InstagramArchive", path.as_ref())?;
+
         // Load all directories in the directory
         let mut connections = None;
         let mut media = None;
@@ -42,20 +251,38 @@ impl InstagramArchive {
             }
 
             match path.file_stem().and_then(|s| s.to_str()) {
-                Some("ads_information") => {}
-                Some("apps_and_websites_off_of_instagram") => {}
+                Some("ads_information") => {
+                    options.skip(&path, "Skipped unsupported section");
+                }
+                Some("apps_and_websites_off_of_instagram") => {
+                    options.skip(&path, "Skipped unsupported section");
+                }
                 Some("connections") => {
-                    connections = Some(Connections::from_folder(&path)?);
+                    if options.should_load(Sections::CONNECTIONS, &path) {
+                        connections = Some(Connections::from_folder(&path, options)?);
+                    }
+                }
+                Some("logged_information") => {
+                    options.skip(&path, "Skipped unsupported section");
                 }
-                Some("logged_information") => {}
                 Some("media") => {
-                    media = Some(Media::from_folder(&path)?);
+                    if options.should_load(Sections::MEDIA, &path) {
+                        media = Some(Media::from_folder(&path, options)?);
+                    }
+                }
+                Some("personal_information") => {
+                    options.skip(&path, "Skipped unsupported section");
+                }
+                Some("preferences") => {
+                    options.skip(&path, "Skipped unsupported section");
+                }
+                Some("security_and_login_information") => {
+                    options.skip(&path, "Skipped unsupported section");
                 }
-                Some("personal_information") => {}
-                Some("preferences") => {}
-                Some("security_and_login_information") => {}
                 Some("your_instagram_activity") => {
-                    activity = Some(Activity::try_from(path.as_ref())?);
+                    if options.should_load(Sections::ACTIVITY, &path) {
+                        activity = Some(Activity::load(path.as_ref(), options)?);
+                    }
                 }
                 _ => {
                     return Err(ParseError::UnexpectedFormat(format!(
@@ -72,77 +299,55 @@ impl InstagramArchive {
             activity,
         })
     }
-
-    // TODO: Move this method to a common Archive trait
-    /// Save to file
-    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), WriteError> {
-        let path = path.as_ref();
-        let file = std::fs::File::create(path)?;
-        serde_json::to_writer_pretty(file, self)?;
-        Ok(())
-    }
 }
 
 impl TryFrom<&Path> for InstagramArchive {
     type Error = ParseError;
 
     fn try_from(path: &Path) -> Result<Self, Self::Error> {
-        if path.is_dir() {
-            // Parse as root directory
-            InstagramArchive::from_folder(path)
-        } else if path
-            .extension()
-            .map(|ext| ext.to_str() == Some("zip"))
-            .unwrap_or(false)
-        {
-            // Parse as zip file
-            todo!()
-        } else {
-            Err(ParseError::UnexpectedFormat(
-                format!("Expected a directory or zip file, found: {:?}", absolute(path)),
-            ))
-        }
+        InstagramArchive::load(path, &LoadOptions::default())
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
-#[serde(deny_unknown_fields)]
-pub struct LinkTimeValueData {
-    pub href: String,
-    pub value: Option<String>,
-    pub timestamp: i32,
-}
+#[cfg(feature = "typescript")]
+mod typescript {
+    use neon::prelude::*;
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
-#[serde(deny_unknown_fields)]
-pub struct LinkTimeData {
-    pub href: String,
-    pub timestamp: i32,
-}
+    use super::InstagramArchive;
+    use crate::bindings::typescript::{load_options, throw_parse_error, to_js};
+    use crate::common::Archive;
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
-#[serde(deny_unknown_fields)]
-pub struct LinkData {
-    pub href: String,
-    pub value: Option<String>,
-}
+    /// Initialize context for instagram module
+    pub(crate) fn init_context(cx: &mut ModuleContext) -> NeonResult<()> {
+        cx.export_function("loadInstagramArchive", load_instagram_archive)?;
+        cx.export_function("loadInstagramArchiveAsync", load_instagram_archive_async)?;
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
-#[serde(deny_unknown_fields)]
-pub struct Value {
-    pub value: String,
-}
+        Ok(())
+    }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
-#[serde(deny_unknown_fields)]
-pub struct Timestamp {
-    pub timestamp: u64,
-}
+    /// `loadInstagramArchive(path, options?)`: load an archive, blocking the main thread
+    fn load_instagram_archive(mut cx: FunctionContext) -> JsResult<JsValue> {
+        let path = cx.argument::<JsString>(0)?.value(&mut cx);
+        let options = load_options(&mut cx, 1)?;
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
-#[serde(deny_unknown_fields)]
-pub struct MediaUri {
-    pub uri: String,
-    pub creation_timestamp: Option<u64>,
-    pub backup_uri: Option<String>,
+        match InstagramArchive::load(&path, &options) {
+            Ok(archive) => to_js(&mut cx, &archive),
+            Err(err) => throw_parse_error(&mut cx, err),
+        }
+    }
+
+    /// `loadInstagramArchiveAsync(path, options?)`: load an archive on the libuv thread pool
+    fn load_instagram_archive_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let path = cx.argument::<JsString>(0)?.value(&mut cx);
+        let options = load_options(&mut cx, 1)?;
+
+        let promise = cx
+            .task(move || InstagramArchive::load(&path, &options))
+            .promise(|mut cx, result| match result {
+                Ok(archive) => to_js(&mut cx, &archive),
+                Err(err) => throw_parse_error(&mut cx, err),
+            });
+
+        Ok(promise)
+    }
 }
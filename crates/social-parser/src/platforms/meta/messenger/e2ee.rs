@@ -0,0 +1,199 @@
+//! Threads from end-to-end encrypted chats, downloaded from Messenger's secure storage.
+//! Each thread is a single camelCase JSON file next to a `media` folder.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::{
+        ArchiveTimestamp, Attachment, AttachmentKind, Conversation, ConversationMessage, ItemCount,
+        MessageKind, MessageReaction, Milliseconds, ParseError, Poll, PollOption, ReplyTo,
+    },
+    platforms::meta::read_json,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct E2eeThread {
+    #[serde(default)]
+    pub participants: Vec<String>,
+    pub thread_name: Option<String>,
+    #[serde(default)]
+    pub messages: Vec<E2eeMessage>,
+}
+
+impl E2eeThread {
+    /// Normalize into a conversation identified by `id`, the thread's file name
+    pub fn to_conversation(&self, id: &str) -> Conversation {
+        let mut messages: Vec<ConversationMessage> = self
+            .messages
+            .iter()
+            .map(ConversationMessage::from)
+            .collect();
+        messages.sort_by_key(|message| message.timestamp);
+
+        Conversation {
+            id: id.to_owned(),
+            title: self.thread_name.clone(),
+            participants: self.participants.clone(),
+            messages,
+        }
+    }
+}
+
+impl TryFrom<&Path> for E2eeThread {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<E2eeThread, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in E2eeThread: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for E2eeThread {
+    fn item_count(&self) -> usize {
+        self.messages.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct E2eeMessage {
+    pub sender_name: String,
+    pub timestamp: ArchiveTimestamp<Milliseconds>,
+    pub text: Option<String>,
+    /// `text`, `media`, `link`, `call`, `poll` or `placeholder` for content that wasn't exported
+    #[serde(rename = "type")]
+    pub message_type: Option<String>,
+    #[serde(default)]
+    pub is_unsent: bool,
+    #[serde(default)]
+    pub media: Vec<E2eeMedia>,
+    #[serde(default)]
+    pub reactions: Vec<E2eeReaction>,
+    pub reply_to: Option<E2eeReply>,
+    pub poll: Option<E2eePoll>,
+    pub call: Option<E2eeCall>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct E2eeMedia {
+    /// Relative to the folder containing the thread file
+    pub uri: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct E2eeReaction {
+    pub actor: String,
+    pub reaction: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct E2eeReply {
+    pub message_id: Option<String>,
+    pub sender_name: Option<String>,
+    pub text: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct E2eePoll {
+    pub question: String,
+    #[serde(default)]
+    pub options: Vec<E2eePollOption>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct E2eePollOption {
+    pub text: String,
+    #[serde(default)]
+    pub voters: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct E2eeCall {
+    /// Duration in seconds
+    pub duration: Option<u64>,
+    #[serde(default)]
+    pub missed: bool,
+}
+
+impl From<&E2eeMessage> for ConversationMessage {
+    fn from(message: &E2eeMessage) -> Self {
+        let mut normalized =
+            ConversationMessage::new(message.sender_name.clone(), Some(message.timestamp));
+        normalized.content = message.text.clone();
+        normalized.is_unsent = message.is_unsent;
+
+        normalized.kind = if let Some(poll) = &message.poll {
+            MessageKind::Poll(Poll {
+                question: poll.question.clone(),
+                options: poll
+                    .options
+                    .iter()
+                    .map(|option| PollOption {
+                        text: option.text.clone(),
+                        voters: option.voters.clone(),
+                    })
+                    .collect(),
+            })
+        } else if let Some(call) = &message.call {
+            MessageKind::Call {
+                duration_secs: call.duration,
+                missed: call.missed,
+            }
+        } else if message.message_type.as_deref() == Some("link") {
+            MessageKind::Share {
+                link: message.text.clone(),
+                text: None,
+            }
+        } else {
+            MessageKind::Text
+        };
+
+        normalized.attachments = message
+            .media
+            .iter()
+            .map(|media| Attachment {
+                kind: AttachmentKind::from_uri(&media.uri),
+                uri: media.uri.clone(),
+                name: None,
+                created_at: None,
+            })
+            .collect();
+
+        normalized.reactions = message
+            .reactions
+            .iter()
+            .map(|reaction| MessageReaction {
+                reaction: reaction.reaction.clone(),
+                actor: reaction.actor.clone(),
+                timestamp: None,
+            })
+            .collect();
+
+        normalized.reply_to = message.reply_to.as_ref().map(|reply| ReplyTo {
+            message_id: reply.message_id.clone(),
+            sender: reply.sender_name.clone(),
+            content: reply.text.clone(),
+        });
+
+        normalized
+    }
+}
@@ -0,0 +1,321 @@
+//! Threads from the classic `messages/inbox/<thread>/message_N.json` layout,
+//! which uses the same schema as Instagram's inbox with a few Messenger-only fields.
+
+use std::{
+    collections::HashMap,
+    path::{absolute, Path},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::{
+        ArchiveTimestamp, AttachmentKind, Conversation, ConversationMessage, ItemCount,
+        LoadOptions, MessageKind, MessageReaction, Milliseconds, ParseError,
+    },
+    platforms::meta::{read_json, MediaUri},
+};
+
+/// Threads keyed by folder name, each split over one or more `message_N.json` files
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct LegacyInbox(pub HashMap<String, Vec<LegacyThread>>);
+
+impl LegacyInbox {
+    /// Load from a directory, reporting progress through `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<LegacyInbox, ParseError> {
+        let mut threads: HashMap<String, Vec<LegacyThread>> = HashMap::new();
+        LegacyInbox::read(path, options, |name, _, part| {
+            threads.entry(name.to_owned()).or_default().push(part);
+            Ok(())
+        })?;
+
+        Ok(LegacyInbox(threads))
+    }
+
+    /// Load from a directory, passing each `message_N.json` file to `on_conversation` as
+    /// it is parsed instead of keeping it. Assumes path is a directory.
+    pub fn stream<F>(
+        path: &Path,
+        options: &LoadOptions,
+        mut on_conversation: F,
+    ) -> Result<(), ParseError>
+    where
+        F: FnMut(&Path, Conversation) -> Result<(), ParseError>,
+    {
+        LegacyInbox::read(path, options, |name, path, part| {
+            on_conversation(path, part.to_conversation(name))
+        })
+    }
+
+    /// Parse every `message_N.json` file, passing it to `on_part` with the name of its
+    /// thread folder and its path
+    fn read<F>(path: &Path, options: &LoadOptions, mut on_part: F) -> Result<(), ParseError>
+    where
+        F: FnMut(&str, &Path, LegacyThread) -> Result<(), ParseError>,
+    {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in LegacyInbox: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("LegacyInbox", path)?;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_dir() {
+                options.warn(&path, "Skipped unexpected file");
+                continue;
+            }
+
+            let name = path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .ok_or_else(|| {
+                    ParseError::UnexpectedFormat(format!(
+                        "Invalid thread name: {:?}",
+                        absolute(&path)
+                    ))
+                })?
+                .to_owned();
+
+            let mut parts = 0;
+            for entry in path.read_dir()? {
+                let path = entry?.path();
+                match path.file_name().and_then(|s| s.to_str()) {
+                    Some(file_name)
+                        if file_name.starts_with("message_") && file_name.ends_with(".json") =>
+                    {
+                        let part = options.load_file::<LegacyThread>(&path)?;
+                        on_part(&name, &path, part)?;
+                        parts += 1;
+                    }
+                    // Media folders referenced by the messages' `uri`s
+                    Some("photos") | Some("videos") | Some("audio") | Some("gifs")
+                    | Some("files") => {}
+                    _ => {
+                        options.warn(&path, "Skipped unsupported file");
+                    }
+                }
+            }
+
+            if parts == 0 {
+                options.warn(&path, "Skipped thread without message_1.json");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Normalize every thread, merging threads split over several files
+    pub fn conversations(&self) -> Vec<Conversation> {
+        self.0
+            .iter()
+            .filter_map(|(id, parts)| {
+                let mut parts = parts.iter().map(|part| part.to_conversation(id));
+                let mut conversation = parts.next()?;
+                for part in parts {
+                    conversation.messages.extend(part.messages);
+                }
+                conversation
+                    .messages
+                    .sort_by_key(|message| message.timestamp);
+                Some(conversation)
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<&Path> for LegacyInbox {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<LegacyInbox, Self::Error> {
+        LegacyInbox::load(path, &LoadOptions::default())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct LegacyThread {
+    #[serde(default)]
+    pub participants: Vec<LegacyParticipant>,
+    #[serde(default)]
+    pub messages: Vec<LegacyMessage>,
+    pub title: Option<String>,
+    pub is_still_participant: Option<bool>,
+    /// e.g. `inbox/janedoe_1234567890`
+    pub thread_path: Option<String>,
+    pub image: Option<MediaUri>,
+}
+
+impl TryFrom<&Path> for LegacyThread {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<LegacyThread, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in LegacyThread: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl LegacyThread {
+    /// Normalize the part of a thread held in one `message_N.json` file
+    pub fn to_conversation(&self, id: &str) -> Conversation {
+        let mut messages: Vec<ConversationMessage> = self
+            .messages
+            .iter()
+            .map(ConversationMessage::from)
+            .collect();
+        messages.sort_by_key(|message| message.timestamp);
+
+        Conversation {
+            id: id.to_owned(),
+            title: self.title.clone(),
+            participants: self.participants.iter().map(|p| p.name.clone()).collect(),
+            messages,
+        }
+    }
+}
+
+impl ItemCount for LegacyThread {
+    fn item_count(&self) -> usize {
+        self.messages.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct LegacyParticipant {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct LegacyMessage {
+    pub sender_name: String,
+    pub timestamp_ms: ArchiveTimestamp<Milliseconds>,
+    pub content: Option<String>,
+    /// `Generic`, `Share`, `Call`, `Subscribe` or `Unsubscribe`
+    #[serde(rename = "type")]
+    pub message_type: Option<String>,
+    #[serde(default)]
+    pub photos: Vec<LegacyMedia>,
+    #[serde(default)]
+    pub videos: Vec<LegacyMedia>,
+    #[serde(default)]
+    pub gifs: Vec<LegacyMedia>,
+    #[serde(default)]
+    pub files: Vec<LegacyMedia>,
+    #[serde(default)]
+    pub audio_files: Vec<LegacyMedia>,
+    pub sticker: Option<LegacyMedia>,
+    pub share: Option<LegacyShare>,
+    #[serde(default)]
+    pub reactions: Vec<LegacyReaction>,
+    pub call_duration: Option<u64>,
+    pub missed: Option<bool>,
+    #[serde(default)]
+    pub is_unsent: bool,
+}
+
+/// Media file, with a thumbnail for videos
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct LegacyMedia {
+    #[serde(flatten)]
+    pub file: MediaUri,
+    pub thumbnail: Option<LegacyThumbnail>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct LegacyThumbnail {
+    pub uri: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct LegacyShare {
+    pub link: Option<String>,
+    pub share_text: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct LegacyReaction {
+    pub reaction: String,
+    pub actor: String,
+    /// Timestamp of the reaction in seconds
+    pub timestamp: Option<ArchiveTimestamp>,
+}
+
+impl From<&LegacyMessage> for ConversationMessage {
+    fn from(message: &LegacyMessage) -> Self {
+        let mut normalized =
+            ConversationMessage::new(message.sender_name.clone(), Some(message.timestamp_ms));
+        normalized.content = message.content.clone();
+        normalized.is_unsent = message.is_unsent;
+
+        normalized.kind = match message.message_type.as_deref() {
+            Some("Call") => MessageKind::Call {
+                duration_secs: message.call_duration,
+                missed: message.missed.unwrap_or(false),
+            },
+            Some("Share") => MessageKind::Share {
+                link: message.share.as_ref().and_then(|share| share.link.clone()),
+                text: message
+                    .share
+                    .as_ref()
+                    .and_then(|share| share.share_text.clone()),
+            },
+            Some("Subscribe") | Some("Unsubscribe") => MessageKind::System,
+            _ => MessageKind::Text,
+        };
+
+        let media = [
+            (AttachmentKind::Photo, &message.photos),
+            (AttachmentKind::Video, &message.videos),
+            (AttachmentKind::Gif, &message.gifs),
+            (AttachmentKind::File, &message.files),
+            (AttachmentKind::Audio, &message.audio_files),
+        ];
+        normalized.attachments = media
+            .into_iter()
+            .flat_map(|(kind, files)| {
+                files
+                    .iter()
+                    .map(move |media| media.file.to_attachment(kind))
+            })
+            .chain(
+                message
+                    .sticker
+                    .iter()
+                    .map(|sticker| sticker.file.to_attachment(AttachmentKind::Sticker)),
+            )
+            .collect();
+
+        normalized.reactions = message
+            .reactions
+            .iter()
+            .map(|reaction| MessageReaction {
+                reaction: reaction.reaction.clone(),
+                actor: reaction.actor.clone(),
+                timestamp: reaction.timestamp.map(|t| t.convert()),
+            })
+            .collect();
+
+        normalized
+    }
+}
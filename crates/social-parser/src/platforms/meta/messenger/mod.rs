@@ -0,0 +1,187 @@
+//! Messenger export, either the "Download Your Information" messages folder with the
+//! classic inbox layout, or a secure storage download of end-to-end encrypted chats.
+//!
+//! Like the Facebook models, these accept unknown fields and warn about unknown files.
+
+pub mod e2ee;
+pub mod legacy;
+
+use std::{
+    collections::HashMap,
+    path::{absolute, Path},
+};
+
+use e2ee::E2eeThread;
+use legacy::LegacyInbox;
+use serde::{Deserialize, Serialize};
+
+use crate::common::{
+    Archive, Conversation, Dataset, LoadOptions, OnConversation, ParseError, Sections,
+    StreamingArchive,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct MessengerArchive {
+    pub inbox: Option<LegacyInbox>,
+    pub archived_threads: Option<LegacyInbox>,
+    pub filtered_threads: Option<LegacyInbox>,
+    pub message_requests: Option<LegacyInbox>,
+    /// End-to-end encrypted threads keyed by file name
+    pub e2ee_threads: Option<HashMap<String, E2eeThread>>,
+}
+
+impl Archive for MessengerArchive {
+    /// Load from a directory, only parsing the sections selected in `options`
+    fn load<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError> {
+        MessengerArchive::read(path.as_ref(), options, None)
+    }
+
+    fn to_dataset(&self) -> Dataset {
+        Dataset {
+            conversations: self.conversations(),
+            ..Dataset::new("messenger")
+        }
+    }
+}
+
+impl StreamingArchive for MessengerArchive {
+    const PLATFORM: &'static str = "messenger";
+
+    /// Load from a directory, passing the messages of each `message_N.json` file and
+    /// end-to-end encrypted thread to `on_conversation` instead of keeping them
+    fn stream_conversations<P, F>(
+        path: P,
+        options: &LoadOptions,
+        mut on_conversation: F,
+    ) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&Path, Conversation) -> Result<(), ParseError>,
+    {
+        MessengerArchive::read(path.as_ref(), options, Some(&mut on_conversation))
+    }
+}
+
+impl MessengerArchive {
+    /// Load from a directory, passing threads to `on_conversation` instead of keeping them
+    /// when it is set
+    fn read(
+        path: &Path,
+        options: &LoadOptions,
+        on_conversation: Option<&mut OnConversation>,
+    ) -> Result<Self, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Expected a directory, found: {:?}",
+                absolute(path)
+            )));
+        }
+
+        options.enter_section("MessengerArchive", path)?;
+
+        let mut archive = MessengerArchive::default();
+        if options.should_load(Sections::MESSAGES, path) {
+            archive.load_folder(path, true, options, on_conversation)?;
+        }
+        Ok(archive)
+    }
+
+    /// Load every inbox found in a folder, searching the folders that wrap them in full
+    /// Facebook exports. JSON files at the root are end-to-end encrypted threads.
+    fn load_folder(
+        &mut self,
+        path: &Path,
+        root: bool,
+        options: &LoadOptions,
+        mut on_conversation: Option<&mut OnConversation>,
+    ) -> Result<(), ParseError> {
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+            let name = path.file_name().and_then(|s| s.to_str());
+
+            if path.is_file() {
+                match (root, path.file_stem().and_then(|s| s.to_str())) {
+                    (true, Some(stem)) if name.is_some_and(|name| name.ends_with(".json")) => {
+                        let thread = options.load_file::<E2eeThread>(&path)?;
+                        if let Some(on_conversation) = on_conversation.as_deref_mut() {
+                            on_conversation(&path, thread.to_conversation(stem))?;
+                        } else {
+                            self.e2ee_threads
+                                .get_or_insert_with(Default::default)
+                                .insert(stem.to_owned(), thread);
+                        }
+                    }
+                    _ => {
+                        options.skip(&path, "Skipped unsupported file");
+                    }
+                }
+                continue;
+            }
+
+            let inbox = match name {
+                Some("your_facebook_activity")
+                | Some("your_activity_across_facebook")
+                | Some("messages") => {
+                    self.load_folder(&path, false, options, on_conversation.as_deref_mut())?;
+                    continue;
+                }
+                // Threads moved to end-to-end encryption keep their history in the classic layout
+                Some("inbox") | Some("e2ee_cutover") => &mut self.inbox,
+                Some("archived_threads") => &mut self.archived_threads,
+                Some("filtered_threads") => &mut self.filtered_threads,
+                Some("message_requests") => &mut self.message_requests,
+                Some("media") if root => {
+                    // Referenced by the end-to-end encrypted threads
+                    continue;
+                }
+                _ => {
+                    options.skip(&path, "Skipped unsupported section");
+                    continue;
+                }
+            };
+
+            if let Some(on_conversation) = on_conversation.as_deref_mut() {
+                LegacyInbox::stream(&path, options, on_conversation)?;
+            } else {
+                let threads = LegacyInbox::load(&path, options)?;
+                inbox
+                    .get_or_insert_with(Default::default)
+                    .0
+                    .extend(threads.0);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Every thread in the archive as a normalized conversation
+    pub fn conversations(&self) -> Vec<Conversation> {
+        let legacy = [
+            &self.inbox,
+            &self.archived_threads,
+            &self.filtered_threads,
+            &self.message_requests,
+        ]
+        .into_iter()
+        .flatten()
+        .flat_map(LegacyInbox::conversations);
+
+        let e2ee = self
+            .e2ee_threads
+            .iter()
+            .flatten()
+            .map(|(id, thread)| thread.to_conversation(id));
+
+        legacy.chain(e2ee).collect()
+    }
+}
+
+impl TryFrom<&Path> for MessengerArchive {
+    type Error = ParseError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        MessengerArchive::load(path, &LoadOptions::default())
+    }
+}
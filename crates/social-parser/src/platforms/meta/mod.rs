@@ -1,15 +1,114 @@
+pub mod facebook;
 pub mod instagram;
+pub mod messenger;
+pub mod threads;
+
+mod repair;
+
+use std::{borrow::Cow, fs::File, io::BufReader, path::Path};
+
+use repair::Repair;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::common::{ArchiveTimestamp, Attachment, AttachmentKind, ParseError};
 
 #[cfg(feature = "typescript")]
-pub use typescript::*;
+pub(crate) use typescript::*;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(deny_unknown_fields)]
+pub struct LinkTimeValueData {
+    pub href: String,
+    pub value: Option<String>,
+    pub timestamp: ArchiveTimestamp,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(deny_unknown_fields)]
+pub struct LinkTimeData {
+    pub href: String,
+    pub timestamp: ArchiveTimestamp,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(deny_unknown_fields)]
+pub struct LinkData {
+    pub href: String,
+    pub value: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(deny_unknown_fields)]
+pub struct Value {
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(deny_unknown_fields)]
+pub struct Timestamp {
+    pub timestamp: ArchiveTimestamp,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(deny_unknown_fields)]
+pub struct MediaUri {
+    pub uri: String,
+    pub creation_timestamp: Option<ArchiveTimestamp>,
+    pub backup_uri: Option<String>,
+}
+
+impl MediaUri {
+    /// Normalize into a conversation attachment
+    pub fn to_attachment(&self, kind: AttachmentKind) -> Attachment {
+        Attachment {
+            kind,
+            uri: self.uri.clone(),
+            name: None,
+            created_at: self.creation_timestamp.map(|t| t.convert()),
+        }
+    }
+}
+
+/// Meta exports write every UTF-8 byte as its own `\u00XX` escape, so "é" comes out as "Ã©".
+/// Reinterpret such strings as UTF-8, leaving anything that isn't valid UTF-8 untouched.
+pub fn repair_mojibake(s: &str) -> Cow<'_, str> {
+    if s.is_ascii() || s.chars().any(|c| c as u32 > 0xFF) {
+        return Cow::Borrowed(s);
+    }
+
+    let bytes: Vec<u8> = s.chars().map(|c| c as u8).collect();
+    match String::from_utf8(bytes) {
+        Ok(repaired) => Cow::Owned(repaired),
+        Err(_) => Cow::Borrowed(s),
+    }
+}
+
+/// Read a JSON file from a Meta export, repairing mojibake while deserializing
+pub(crate) fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, ParseError> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    T::deserialize(Repair(&mut deserializer))
+        .and_then(|value| deserializer.end().map(|()| value))
+        .map_err(|e| ParseError::Serde(path.to_owned(), e))
+}
 
 #[cfg(feature = "typescript")]
 mod typescript {
     use neon::prelude::*;
 
+    use super::instagram;
+
     /// Initialize context for meta module
-    pub(super) fn init_context(_cx: &mut ModuleContext) -> NeonResult<()> {
-        // TODO: Implement
+    pub(crate) fn init_context(cx: &mut ModuleContext) -> NeonResult<()> {
+        instagram::init_context(cx)?;
 
         Ok(())
     }
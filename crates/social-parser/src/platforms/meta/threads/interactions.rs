@@ -0,0 +1,59 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::read_list;
+use crate::{
+    common::{ItemCount, ParseError},
+    platforms::meta::LinkTimeValueData,
+};
+
+/// Reposts, likes, followers or following. Each entry links to a post or profile.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ThreadLinks(pub Vec<ThreadLink>);
+
+impl TryFrom<&Path> for ThreadLinks {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<ThreadLinks, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in ThreadLinks: {:?}",
+                path
+            )));
+        }
+
+        read_list(path).map(ThreadLinks)
+    }
+}
+
+impl ItemCount for ThreadLinks {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ThreadLink {
+    /// Author of the post, empty for followers and following
+    pub title: Option<String>,
+    #[serde(default)]
+    pub string_list_data: Vec<LinkTimeValueData>,
+}
+
+impl ThreadLink {
+    /// Username of the account, or of the post's author
+    pub fn username(&self) -> Option<&str> {
+        self.title
+            .as_deref()
+            .filter(|title| !title.is_empty())
+            .or_else(|| {
+                self.string_list_data
+                    .iter()
+                    .find_map(|d| d.value.as_deref())
+            })
+    }
+}
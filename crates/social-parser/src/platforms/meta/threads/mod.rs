@@ -0,0 +1,267 @@
+//! Threads data, either from the standalone Threads "Download your information" bundle or
+//! the `threads` folder of an Instagram export. Both are loaded into the same [`Threads`]
+//! structure.
+//!
+//! Each file holds a single list under a key that has changed between export versions,
+//! so the key is ignored. Like the Facebook models, these accept unknown fields and warn
+//! about unknown files.
+
+pub mod interactions;
+pub mod posts;
+
+use std::{
+    collections::BTreeMap,
+    path::{absolute, Path},
+};
+
+use interactions::ThreadLinks;
+use posts::ThreadPosts;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::read_json;
+use crate::common::{
+    Archive, Comment, Dataset, Follow, FollowDirection, Like, LoadOptions, ParseError, Post,
+    Sections,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ThreadsArchive {
+    pub threads: Option<Threads>,
+}
+
+impl Archive for ThreadsArchive {
+    /// Load from a directory, only parsing the sections selected in `options`
+    fn load<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Expected a directory, found: {:?}",
+                absolute(path)
+            )));
+        }
+
+        options.enter_section("ThreadsArchive", path)?;
+
+        let mut threads = None;
+        if options.should_load(Sections::THREADS, path) {
+            threads = Some(Threads::load(path, options)?);
+        }
+
+        Ok(Self { threads })
+    }
+
+    fn to_dataset(&self) -> Dataset {
+        let mut dataset = Dataset::new("threads");
+        if let Some(threads) = &self.threads {
+            threads.add_to_dataset(&mut dataset);
+        }
+        dataset
+    }
+}
+
+impl TryFrom<&Path> for ThreadsArchive {
+    type Error = ParseError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        ThreadsArchive::load(path, &LoadOptions::default())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Threads {
+    pub posts: Option<ThreadPosts>,
+    pub replies: Option<ThreadPosts>,
+    pub quotes: Option<ThreadPosts>,
+    pub reposts: Option<ThreadLinks>,
+    pub likes: Option<ThreadLinks>,
+    pub followers: Option<ThreadLinks>,
+    pub following: Option<ThreadLinks>,
+}
+
+impl Threads {
+    /// Load from a directory, only parsing the sections selected in `options`.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<Threads, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in Threads: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("Threads", path)?;
+
+        let mut threads = Threads::default();
+        threads.load_folder(path, options)?;
+        Ok(threads)
+    }
+
+    /// Load every file found in a folder, searching the folders that group them in the
+    /// standalone export
+    fn load_folder(&mut self, path: &Path, options: &LoadOptions) -> Result<(), ParseError> {
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+            let name = path.file_name().and_then(|s| s.to_str());
+
+            if path.is_dir() {
+                match name {
+                    Some("your_threads_activity")
+                    | Some("threads")
+                    | Some("likes")
+                    | Some("reposts")
+                    | Some("connections")
+                    | Some("followers_and_following") => {
+                        self.load_folder(&path, options)?;
+                    }
+                    Some("media") => {
+                        // Referenced by the posts
+                    }
+                    _ => {
+                        options.skip(&path, "Skipped unsupported section");
+                    }
+                }
+                continue;
+            }
+
+            match name {
+                Some("threads_and_replies.json") | Some("threads.json") => {
+                    if options.should_load(Sections::POSTS, &path) {
+                        self.posts = Some(options.load_file(&path)?);
+                    }
+                }
+                Some("replies.json") => {
+                    if options.should_load(Sections::POSTS, &path) {
+                        self.replies = Some(options.load_file(&path)?);
+                    }
+                }
+                Some("quotes.json") | Some("quoted_threads.json") => {
+                    if options.should_load(Sections::POSTS, &path) {
+                        self.quotes = Some(options.load_file(&path)?);
+                    }
+                }
+                Some("reposts.json") | Some("reposted_threads.json") => {
+                    if options.should_load(Sections::POSTS, &path) {
+                        self.reposts = Some(options.load_file(&path)?);
+                    }
+                }
+                Some("liked_threads.json") => {
+                    if options.should_load(Sections::LIKES, &path) {
+                        self.likes = Some(options.load_file(&path)?);
+                    }
+                }
+                Some("followers.json") => {
+                    if options.should_load(Sections::FOLLOWERS_AND_FOLLOWING, &path) {
+                        self.followers = Some(options.load_file(&path)?);
+                    }
+                }
+                Some("following.json") => {
+                    if options.should_load(Sections::FOLLOWERS_AND_FOLLOWING, &path) {
+                        self.following = Some(options.load_file(&path)?);
+                    }
+                }
+                _ => {
+                    options.skip(&path, "Skipped unsupported file");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Posts and quotes of the account, normalized. Replies are left out, see
+    /// [`Threads::normalized_comments`].
+    pub fn normalized_posts(&self) -> Vec<Post> {
+        self.posts
+            .iter()
+            .chain(&self.quotes)
+            .flat_map(|posts| &posts.0)
+            .map(|post| Post {
+                id: None,
+                author: None,
+                timestamp: post.timestamp(),
+                title: None,
+                text: post.text().map(str::to_owned),
+                url: None,
+                link: None,
+                community: None,
+                attachments: post.attachments(),
+            })
+            .collect()
+    }
+
+    /// Replies of the account, normalized. The export doesn't say what they reply to.
+    pub fn normalized_comments(&self) -> Vec<Comment> {
+        self.replies
+            .iter()
+            .flat_map(|posts| &posts.0)
+            .map(|post| Comment {
+                id: None,
+                author: None,
+                timestamp: post.timestamp(),
+                text: post.text().unwrap_or_default().to_owned(),
+                url: None,
+                post: None,
+                parent: None,
+                community: None,
+            })
+            .collect()
+    }
+
+    /// Add the posts, replies, likes, followers and following to `dataset`
+    pub(crate) fn add_to_dataset(&self, dataset: &mut Dataset) {
+        dataset.posts.extend(self.normalized_posts());
+        dataset.comments.extend(self.normalized_comments());
+
+        let followers = self
+            .followers
+            .iter()
+            .flat_map(|links| &links.0)
+            .map(|link| (FollowDirection::Follower, link));
+        let following = self
+            .following
+            .iter()
+            .flat_map(|links| &links.0)
+            .map(|link| (FollowDirection::Following, link));
+        dataset
+            .follows
+            .extend(followers.chain(following).filter_map(|(direction, link)| {
+                let data = link.string_list_data.first();
+                Some(Follow {
+                    direction,
+                    name: link.username()?.to_owned(),
+                    url: data.map(|data| data.href.clone()),
+                    timestamp: data.map(|data| data.timestamp.convert()),
+                })
+            }));
+
+        dataset
+            .likes
+            .extend(self.likes.iter().flat_map(|links| &links.0).map(|link| {
+                let data = link.string_list_data.first();
+                Like {
+                    target: data.map(|data| data.href.clone()),
+                    title: link.username().map(str::to_owned),
+                    reaction: None,
+                    timestamp: data.map(|data| data.timestamp.convert()),
+                }
+            }));
+    }
+}
+
+impl TryFrom<&Path> for Threads {
+    type Error = ParseError;
+
+    /// Load from a directory. Assumes path is a directory.
+    fn try_from(path: &Path) -> Result<Threads, Self::Error> {
+        Threads::load(path, &LoadOptions::default())
+    }
+}
+
+/// Read a file holding a single list, e.g. `{ "text_post_app_text_posts": [...] }`
+fn read_list<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, ParseError> {
+    let lists: BTreeMap<String, Vec<T>> = read_json(path)?;
+    Ok(lists.into_values().flatten().collect())
+}
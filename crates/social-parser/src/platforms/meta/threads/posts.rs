@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::read_list;
+use crate::common::{
+    ArchiveTimestamp, Attachment, AttachmentKind, ItemCount, Milliseconds, ParseError,
+};
+
+/// Threads posted by the account, from `threads_and_replies.json`, or the replies and
+/// quotes files in newer exports
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ThreadPosts(pub Vec<ThreadPost>);
+
+impl TryFrom<&Path> for ThreadPosts {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<ThreadPosts, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in ThreadPosts: {:?}",
+                path
+            )));
+        }
+
+        read_list(path).map(ThreadPosts)
+    }
+}
+
+impl ItemCount for ThreadPosts {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Text-only posts are exported as a single media entry with an empty `uri`, carrying
+/// the text in its `title`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ThreadPost {
+    #[serde(default)]
+    pub media: Vec<ThreadMedia>,
+    pub title: Option<String>,
+    pub creation_timestamp: Option<ArchiveTimestamp>,
+}
+
+impl ThreadPost {
+    /// Text of the post, from the post itself or its first media entry
+    pub fn text(&self) -> Option<&str> {
+        self.title
+            .iter()
+            .chain(self.media.iter().filter_map(|media| media.title.as_ref()))
+            .map(String::as_str)
+            .find(|text| !text.is_empty())
+    }
+
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        self.creation_timestamp
+            .or_else(|| self.media.iter().find_map(|media| media.creation_timestamp))
+            .map(|timestamp| timestamp.convert())
+    }
+
+    /// Photos and videos attached to the post, skipping the placeholder entry of
+    /// text-only posts
+    pub fn attachments(&self) -> Vec<Attachment> {
+        self.media
+            .iter()
+            .filter(|media| !media.uri.is_empty())
+            .map(|media| Attachment {
+                kind: AttachmentKind::from_uri(&media.uri),
+                uri: media.uri.clone(),
+                name: None,
+                created_at: media.creation_timestamp.map(|t| t.convert()),
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ThreadMedia {
+    /// Empty for text-only posts
+    #[serde(default)]
+    pub uri: String,
+    pub creation_timestamp: Option<ArchiveTimestamp>,
+    pub title: Option<String>,
+}
@@ -1,14 +1,28 @@
 #[cfg(feature = "typescript")]
-pub use typescript::*;
+pub(crate) use typescript::*;
 
+pub mod bluesky;
+pub mod discord;
+pub mod google;
+pub mod linkedin;
+pub mod mastodon;
 pub mod meta;
+pub mod reddit;
+pub mod snapchat;
+pub mod telegram;
+pub mod tiktok;
+pub mod tumblr;
+pub mod twitter;
+pub mod whatsapp;
 
 #[cfg(feature = "typescript")]
 mod typescript {
     use neon::prelude::*;
 
+    use super::meta;
+
     /// Initialize context for different platforms
-    pub(super) fn init_context(cx: &mut ModuleContext) -> NeonResult<()> {
+    pub(crate) fn init_context(cx: &mut ModuleContext) -> NeonResult<()> {
         meta::init_context(cx)?;
 
         Ok(())
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::{ArchiveTimestamp, Milliseconds};
+
+/// Row of `subscribed_subreddits.csv`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct SubscribedSubreddit {
+    pub subreddit: String,
+}
+
+/// Row of `friends.csv`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Friend {
+    pub username: String,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// Row of `statistics.csv`, e.g. `account name` or `registration date`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Statistic {
+    pub statistic: String,
+    pub value: String,
+}
+
+/// Row of `ip_logs.csv`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct IpLog {
+    /// e.g. `2023-01-01 10:00:00 UTC`, or `registration ip` for the sign up address
+    pub date: String,
+    pub ip: String,
+}
+
+impl IpLog {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        ArchiveTimestamp::parse_iso8601(&self.date)
+    }
+}
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::{ArchiveTimestamp, Comment, Milliseconds, Post};
+
+/// Row of `posts.csv`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct RedditPost {
+    pub id: String,
+    pub permalink: String,
+    /// e.g. `2023-01-01 10:00:00 UTC`
+    pub date: String,
+    #[serde(default)]
+    pub ip: Option<String>,
+    pub subreddit: String,
+    #[serde(default)]
+    pub gildings: Option<String>,
+    pub title: String,
+    /// Link shared by link posts, or the permalink for text posts
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+impl RedditPost {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        ArchiveTimestamp::parse_iso8601(&self.date)
+    }
+
+    /// Normalize into a post written by `author`
+    pub fn to_post(&self, author: Option<&str>) -> Post {
+        Post {
+            id: Some(self.id.clone()),
+            author: author.map(str::to_owned),
+            timestamp: self.timestamp(),
+            title: Some(self.title.clone()),
+            text: self.body.clone().filter(|body| !body.is_empty()),
+            url: Some(self.permalink.clone()),
+            link: self
+                .url
+                .clone()
+                .filter(|url| !url.is_empty() && *url != self.permalink),
+            community: Some(self.subreddit.clone()),
+            attachments: vec![],
+        }
+    }
+}
+
+/// Row of `comments.csv`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct RedditComment {
+    pub id: String,
+    pub permalink: String,
+    /// e.g. `2023-01-01 10:00:00 UTC`
+    pub date: String,
+    #[serde(default)]
+    pub ip: Option<String>,
+    pub subreddit: String,
+    #[serde(default)]
+    pub gildings: Option<String>,
+    /// URL of the post the comment was made on
+    pub link: String,
+    /// Id of the comment being replied to, empty for top-level comments
+    #[serde(default)]
+    pub parent: Option<String>,
+    pub body: String,
+    #[serde(default)]
+    pub media: Option<String>,
+}
+
+impl RedditComment {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        ArchiveTimestamp::parse_iso8601(&self.date)
+    }
+
+    /// Normalize into a comment written by `author`
+    pub fn to_comment(&self, author: Option<&str>) -> Comment {
+        Comment {
+            id: Some(self.id.clone()),
+            author: author.map(str::to_owned),
+            timestamp: self.timestamp(),
+            text: self.body.clone(),
+            url: Some(self.permalink.clone()),
+            post: Some(self.link.clone()),
+            parent: self.parent.clone().filter(|parent| !parent.is_empty()),
+            community: Some(self.subreddit.clone()),
+        }
+    }
+}
+
+/// Row of `post_votes.csv` or `comment_votes.csv`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Vote {
+    pub id: String,
+    pub permalink: String,
+    /// `up`, `down` or `none` for removed votes
+    pub direction: String,
+}
+
+/// Row of `saved_posts.csv`, `saved_comments.csv` or `hidden_posts.csv`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct SavedItem {
+    pub id: String,
+    pub permalink: String,
+}
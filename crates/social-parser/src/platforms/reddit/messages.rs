@@ -0,0 +1,162 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{ArchiveTimestamp, Conversation, ConversationMessage, Milliseconds, ReplyTo};
+
+/// Row of `messages.csv` or `messages_archive.csv`, private messages sent and received
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct RedditMessage {
+    pub id: String,
+    #[serde(default)]
+    pub permalink: Option<String>,
+    /// Shared by a message and its replies, empty for messages without replies
+    #[serde(default)]
+    pub thread_id: Option<String>,
+    /// e.g. `2023-01-01 10:00:00 UTC`
+    pub date: String,
+    #[serde(default)]
+    pub ip: Option<String>,
+    pub from: String,
+    pub to: String,
+    #[serde(default)]
+    pub subject: Option<String>,
+    pub body: String,
+}
+
+impl RedditMessage {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        ArchiveTimestamp::parse_iso8601(&self.date)
+    }
+
+    fn thread(&self) -> &str {
+        self.thread_id
+            .as_deref()
+            .filter(|thread| !thread.is_empty())
+            .unwrap_or(&self.id)
+    }
+}
+
+/// Group private messages into conversations by thread
+pub fn message_conversations(messages: &[RedditMessage]) -> Vec<Conversation> {
+    let mut threads: BTreeMap<&str, Vec<&RedditMessage>> = BTreeMap::new();
+    for message in messages {
+        threads.entry(message.thread()).or_default().push(message);
+    }
+
+    threads
+        .into_iter()
+        .map(|(id, messages)| {
+            let participants: BTreeSet<&str> = messages
+                .iter()
+                .flat_map(|message| [message.from.as_str(), message.to.as_str()])
+                .collect();
+
+            let mut converted: Vec<ConversationMessage> = messages
+                .iter()
+                .map(|message| {
+                    let mut converted =
+                        ConversationMessage::new(message.from.clone(), message.timestamp());
+                    converted.id = Some(message.id.clone());
+                    converted.content = Some(message.body.clone());
+                    converted
+                })
+                .collect();
+            converted.sort_by_key(|message| message.timestamp);
+
+            Conversation {
+                id: id.to_owned(),
+                title: messages
+                    .iter()
+                    .min_by_key(|message| message.timestamp())
+                    .and_then(|message| message.subject.clone())
+                    .filter(|subject| !subject.is_empty()),
+                participants: participants.into_iter().map(str::to_owned).collect(),
+                messages: converted,
+            }
+        })
+        .collect()
+}
+
+/// Row of `chat_history.csv`, messages from Reddit Chat
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChatMessage {
+    pub message_id: String,
+    /// e.g. `2023-01-01 10:00:00 UTC`
+    pub created_at: String,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    pub username: String,
+    pub message: String,
+    #[serde(default)]
+    pub thread_parent_message_id: Option<String>,
+    /// Identifies the channel the message was sent in
+    pub channel_url: String,
+    #[serde(default)]
+    pub subreddit: Option<String>,
+    #[serde(default)]
+    pub channel_name: Option<String>,
+    /// e.g. `direct` or `group`
+    #[serde(default)]
+    pub conversation_type: Option<String>,
+}
+
+impl ChatMessage {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        ArchiveTimestamp::parse_iso8601(&self.created_at)
+    }
+}
+
+/// Group chat messages into conversations by channel
+pub fn chat_conversations(messages: &[ChatMessage]) -> Vec<Conversation> {
+    let mut channels: BTreeMap<&str, Vec<&ChatMessage>> = BTreeMap::new();
+    for message in messages {
+        channels
+            .entry(message.channel_url.as_str())
+            .or_default()
+            .push(message);
+    }
+
+    channels
+        .into_iter()
+        .map(|(id, messages)| {
+            let participants: BTreeSet<&str> = messages
+                .iter()
+                .map(|message| message.username.as_str())
+                .collect();
+
+            let mut converted: Vec<ConversationMessage> = messages
+                .iter()
+                .map(|message| {
+                    let mut converted =
+                        ConversationMessage::new(message.username.clone(), message.timestamp());
+                    converted.id = Some(message.message_id.clone());
+                    converted.content = Some(message.message.clone());
+                    converted.reply_to = message
+                        .thread_parent_message_id
+                        .clone()
+                        .filter(|parent| !parent.is_empty())
+                        .map(|parent| ReplyTo {
+                            message_id: Some(parent),
+                            sender: None,
+                            content: None,
+                        });
+                    converted
+                })
+                .collect();
+            converted.sort_by_key(|message| message.timestamp);
+
+            Conversation {
+                id: id.to_owned(),
+                title: messages
+                    .iter()
+                    .find_map(|message| message.channel_name.clone())
+                    .filter(|name| !name.is_empty()),
+                participants: participants.into_iter().map(str::to_owned).collect(),
+                messages: converted,
+            }
+        })
+        .collect()
+}
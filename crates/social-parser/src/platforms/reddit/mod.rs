@@ -0,0 +1,341 @@
+//! Reddit data export, requested from the data request page under privacy settings.
+//!
+//! The export is a flat set of CSV files, delivered as a zip. Both the zip and the
+//! extracted folder can be loaded. Unknown files are reported as warnings, and columns
+//! that aren't in every export version are optional.
+
+pub mod account;
+pub mod content;
+pub mod messages;
+
+use std::{
+    fs::File,
+    io::Read,
+    path::{absolute, Path},
+};
+
+use account::{Friend, IpLog, Statistic, SubscribedSubreddit};
+use content::{RedditComment, RedditPost, SavedItem, Vote};
+use messages::{ChatMessage, RedditMessage};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::common::{
+    self,
+    bundle::{for_each_zip_file, is_zip},
+    read_csv_from, AccountInfo, Archive, Comment, Conversation, Dataset, Follow, FollowDirection,
+    Like, LoadOptions, Login, OnConversation, ParseError, Post, Sections, StreamingArchive,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct RedditArchive {
+    pub posts: Option<Vec<RedditPost>>,
+    pub comments: Option<Vec<RedditComment>>,
+    /// Private messages, including those from `messages_archive.csv`
+    pub messages: Option<Vec<RedditMessage>>,
+    pub chat_history: Option<Vec<ChatMessage>>,
+    pub post_votes: Option<Vec<Vote>>,
+    pub comment_votes: Option<Vec<Vote>>,
+    pub saved_posts: Option<Vec<SavedItem>>,
+    pub saved_comments: Option<Vec<SavedItem>>,
+    pub hidden_posts: Option<Vec<SavedItem>>,
+    pub subscribed_subreddits: Option<Vec<SubscribedSubreddit>>,
+    pub friends: Option<Vec<Friend>>,
+    pub statistics: Option<Vec<Statistic>>,
+    pub ip_logs: Option<Vec<IpLog>>,
+}
+
+impl Archive for RedditArchive {
+    /// Load from a zip file or an extracted directory, only parsing the sections selected
+    /// in `options`
+    fn load<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError> {
+        RedditArchive::read(path.as_ref(), options, None)
+    }
+
+    fn to_dataset(&self) -> Dataset {
+        let votes = self.post_votes.iter().chain(&self.comment_votes).flatten();
+        let saved = self
+            .saved_posts
+            .iter()
+            .chain(&self.saved_comments)
+            .flatten();
+
+        Dataset {
+            account: AccountInfo {
+                username: self.username().map(str::to_owned),
+                display_name: None,
+                email: None,
+            },
+            conversations: self.conversations(),
+            posts: self.normalized_posts(),
+            comments: self.normalized_comments(),
+            follows: self
+                .friends
+                .iter()
+                .flatten()
+                .map(|friend| Follow {
+                    direction: FollowDirection::Mutual,
+                    name: friend.username.clone(),
+                    url: None,
+                    timestamp: None,
+                })
+                .collect(),
+            // Removed votes are left out, downvotes are kept as a `down` reaction
+            likes: votes
+                .filter(|vote| vote.direction != "none")
+                .map(|vote| Like {
+                    target: Some(vote.permalink.clone()),
+                    title: None,
+                    reaction: Some(vote.direction.clone()),
+                    timestamp: None,
+                })
+                .collect(),
+            saved_items: saved
+                .map(|item| common::SavedItem {
+                    target: Some(item.permalink.clone()),
+                    title: None,
+                    collection: None,
+                    timestamp: None,
+                })
+                .collect(),
+            logins: self
+                .ip_logs
+                .iter()
+                .flatten()
+                .map(|log| Login {
+                    timestamp: log.timestamp(),
+                    ip_address: Some(log.ip.clone()),
+                    device: None,
+                    location: None,
+                })
+                .collect(),
+            ..Dataset::new("reddit")
+        }
+    }
+}
+
+impl StreamingArchive for RedditArchive {
+    const PLATFORM: &'static str = "reddit";
+
+    /// Load from a zip file or an extracted directory, passing the conversations in each
+    /// message file to `on_conversation` instead of keeping them
+    fn stream_conversations<P, F>(
+        path: P,
+        options: &LoadOptions,
+        mut on_conversation: F,
+    ) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&Path, Conversation) -> Result<(), ParseError>,
+    {
+        RedditArchive::read(path.as_ref(), options, Some(&mut on_conversation))
+    }
+}
+
+impl RedditArchive {
+    /// Load from a zip file or directory, passing conversations to `on_conversation`
+    /// instead of keeping them when it is set
+    fn read(
+        path: &Path,
+        options: &LoadOptions,
+        mut on_conversation: Option<&mut OnConversation>,
+    ) -> Result<Self, ParseError> {
+        let mut archive = RedditArchive::default();
+
+        if is_zip(path) {
+            options.enter_section("RedditArchive", path)?;
+            for_each_zip_file(path, |path, reader, bytes| {
+                archive.load_csv(path, reader, bytes, options, on_conversation.as_deref_mut())
+            })?;
+            return Ok(archive);
+        }
+
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Expected a directory or zip file, found: {:?}",
+                absolute(path)
+            )));
+        }
+
+        options.enter_section("RedditArchive", path)?;
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_file() {
+                options.warn(&path, "Skipped unsupported section");
+                continue;
+            }
+
+            let bytes = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            archive.load_csv(
+                &path,
+                File::open(&path)?,
+                bytes,
+                options,
+                on_conversation.as_deref_mut(),
+            )?;
+        }
+
+        Ok(archive)
+    }
+
+    /// Parse one CSV file into the matching section, if that section is selected
+    fn load_csv<R: Read>(
+        &mut self,
+        path: &Path,
+        reader: R,
+        bytes: u64,
+        options: &LoadOptions,
+        on_conversation: Option<&mut OnConversation>,
+    ) -> Result<(), ParseError> {
+        match path.file_name().and_then(|s| s.to_str()) {
+            Some("posts.csv") => {
+                if options.should_load(Sections::POSTS, path) {
+                    self.posts = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("comments.csv") => {
+                if options.should_load(Sections::COMMENTS, path) {
+                    self.comments = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("messages.csv") | Some("messages_archive.csv") => {
+                if options.should_load(Sections::MESSAGES, path) {
+                    let rows: Vec<RedditMessage> = load_rows(path, reader, bytes, options)?;
+                    if let Some(on_conversation) = on_conversation {
+                        for conversation in messages::message_conversations(&rows) {
+                            on_conversation(path, conversation)?;
+                        }
+                    } else {
+                        self.messages.get_or_insert_with(Vec::new).extend(rows);
+                    }
+                }
+            }
+            Some("chat_history.csv") => {
+                if options.should_load(Sections::MESSAGES, path) {
+                    let rows: Vec<ChatMessage> = load_rows(path, reader, bytes, options)?;
+                    if let Some(on_conversation) = on_conversation {
+                        for conversation in messages::chat_conversations(&rows) {
+                            on_conversation(path, conversation)?;
+                        }
+                    } else {
+                        self.chat_history = Some(rows);
+                    }
+                }
+            }
+            Some("post_votes.csv") => {
+                if options.should_load(Sections::LIKES, path) {
+                    self.post_votes = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("comment_votes.csv") => {
+                if options.should_load(Sections::LIKES, path) {
+                    self.comment_votes = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("saved_posts.csv") => {
+                if options.should_load(Sections::SAVED, path) {
+                    self.saved_posts = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("saved_comments.csv") => {
+                if options.should_load(Sections::SAVED, path) {
+                    self.saved_comments = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("hidden_posts.csv") => {
+                if options.should_load(Sections::SAVED, path) {
+                    self.hidden_posts = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("subscribed_subreddits.csv") => {
+                if options.should_load(Sections::GROUPS, path) {
+                    self.subscribed_subreddits = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("friends.csv") => {
+                if options.should_load(Sections::FOLLOWERS_AND_FOLLOWING, path) {
+                    self.friends = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("statistics.csv") => {
+                if options.should_load(Sections::PROFILE, path) {
+                    self.statistics = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            Some("ip_logs.csv") => {
+                if options.should_load(Sections::PROFILE, path) {
+                    self.ip_logs = Some(load_rows(path, reader, bytes, options)?);
+                }
+            }
+            _ => {
+                options.skip(path, "Skipped unsupported file");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Username of the account, from `statistics.csv`
+    pub fn username(&self) -> Option<&str> {
+        self.statistics
+            .iter()
+            .flatten()
+            .find(|row| row.statistic == "account name")
+            .map(|row| row.value.as_str())
+    }
+
+    /// Posts made by the account, normalized
+    pub fn normalized_posts(&self) -> Vec<Post> {
+        self.posts
+            .iter()
+            .flatten()
+            .map(|post| post.to_post(self.username()))
+            .collect()
+    }
+
+    /// Comments made by the account, normalized
+    pub fn normalized_comments(&self) -> Vec<Comment> {
+        self.comments
+            .iter()
+            .flatten()
+            .map(|comment| comment.to_comment(self.username()))
+            .collect()
+    }
+
+    /// Private message threads followed by chat channels, normalized
+    pub fn conversations(&self) -> Vec<Conversation> {
+        let mut conversations = self
+            .messages
+            .as_deref()
+            .map(messages::message_conversations)
+            .unwrap_or_default();
+        conversations.extend(
+            self.chat_history
+                .as_deref()
+                .map(messages::chat_conversations)
+                .unwrap_or_default(),
+        );
+        conversations
+    }
+}
+
+impl TryFrom<&Path> for RedditArchive {
+    type Error = ParseError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        RedditArchive::load(path, &LoadOptions::default())
+    }
+}
+
+/// Parse every row of a CSV file, reporting progress through `options`
+fn load_rows<T: DeserializeOwned, R: Read>(
+    path: &Path,
+    reader: R,
+    bytes: u64,
+    options: &LoadOptions,
+) -> Result<Vec<T>, ParseError> {
+    options.load_with(path, bytes, || read_csv_from(reader, path))
+}
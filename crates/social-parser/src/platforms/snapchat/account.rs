@@ -0,0 +1,159 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::parse_date;
+use crate::common::{read_json, ArchiveTimestamp, ItemCount, Milliseconds, ParseError};
+
+/// `json/account.json`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Account {
+    #[serde(rename = "Basic Information")]
+    pub basic_information: Option<BasicInformation>,
+    #[serde(rename = "Device History", default)]
+    pub device_history: Vec<Device>,
+    #[serde(rename = "Login History", default)]
+    pub login_history: Vec<Login>,
+}
+
+impl TryFrom<&Path> for Account {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<Account, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in Account: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for Account {
+    fn item_count(&self) -> usize {
+        1 + self.device_history.len() + self.login_history.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct BasicInformation {
+    #[serde(rename = "Username")]
+    pub username: Option<String>,
+    #[serde(rename = "Name")]
+    pub name: Option<String>,
+    #[serde(rename = "Creation Date")]
+    pub creation_date: Option<String>,
+    #[serde(rename = "Registration IP")]
+    pub registration_ip: Option<String>,
+    #[serde(rename = "Country")]
+    pub country: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Device {
+    #[serde(rename = "Make")]
+    pub make: Option<String>,
+    #[serde(rename = "Model")]
+    pub model: Option<String>,
+    #[serde(rename = "Start Time")]
+    pub start_time: Option<String>,
+    #[serde(rename = "Device Type")]
+    pub device_type: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Login {
+    #[serde(rename = "IP")]
+    pub ip: Option<String>,
+    #[serde(rename = "Country")]
+    pub country: Option<String>,
+    #[serde(rename = "Created")]
+    pub created: Option<String>,
+    /// `success` or `failure`
+    #[serde(rename = "Status")]
+    pub status: Option<String>,
+    #[serde(rename = "Device")]
+    pub device: Option<String>,
+}
+
+impl Login {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        self.created.as_deref().and_then(parse_date)
+    }
+}
+
+/// `json/location_history.json`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct LocationHistory {
+    #[serde(rename = "Frequent Locations", default)]
+    pub frequent_locations: Vec<Place>,
+    #[serde(rename = "Latest Location", default)]
+    pub latest_location: Vec<Place>,
+    #[serde(rename = "Location History", default)]
+    pub location_history: Vec<Location>,
+}
+
+impl TryFrom<&Path> for LocationHistory {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<LocationHistory, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in LocationHistory: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for LocationHistory {
+    fn item_count(&self) -> usize {
+        self.frequent_locations.len() + self.latest_location.len() + self.location_history.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Place {
+    #[serde(rename = "City")]
+    pub city: Option<String>,
+    #[serde(rename = "Region")]
+    pub region: Option<String>,
+    #[serde(rename = "Country")]
+    pub country: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Location {
+    /// e.g. `2023/01/01 10:00:00 UTC`
+    #[serde(rename = "Time")]
+    pub time: Option<String>,
+    /// e.g. `52.52 ± 10.0 meters, 13.40 ± 10.0 meters`
+    #[serde(rename = "Latitude, Longitude")]
+    pub latitude_longitude: Option<String>,
+}
+
+impl Location {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        self.time.as_deref().and_then(parse_date)
+    }
+
+    /// Latitude and longitude, without the accuracy
+    pub fn coordinates(&self) -> Option<(f64, f64)> {
+        let (latitude, longitude) = self.latitude_longitude.as_deref()?.split_once(',')?;
+        let parse = |s: &str| s.split_whitespace().next()?.parse().ok();
+        Some((parse(latitude)?, parse(longitude)?))
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{media::ChatMedia, parse_date};
+use crate::common::{
+    read_json, ArchiveTimestamp, Attachment, Conversation, ConversationMessage, ItemCount,
+    MessageKind, Milliseconds, ParseError,
+};
+
+/// `json/chat_history.json`, saved chat messages keyed by conversation. Conversations
+/// are keyed by the other user's username, or by an id for group chats.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChatHistory(pub BTreeMap<String, Vec<ChatMessage>>);
+
+impl ChatHistory {
+    /// Every conversation, normalized. Attachments point at files in `chat_media` when
+    /// `media` is given.
+    pub fn conversations(&self, media: Option<&ChatMedia>) -> Vec<Conversation> {
+        self.0
+            .iter()
+            .map(|(id, messages)| {
+                let title = messages
+                    .iter()
+                    .find_map(|message| message.conversation_title.clone());
+
+                let mut participants: BTreeSet<&str> = messages
+                    .iter()
+                    .filter_map(|message| message.from.as_deref())
+                    .collect();
+                if title.is_none() {
+                    participants.insert(id);
+                }
+
+                let mut converted: Vec<ConversationMessage> = messages
+                    .iter()
+                    .map(|message| message.to_message(media))
+                    .collect();
+                converted.sort_by_key(|message| message.timestamp);
+
+                Conversation {
+                    id: id.clone(),
+                    title,
+                    participants: participants.into_iter().map(str::to_owned).collect(),
+                    messages: converted,
+                }
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<&Path> for ChatHistory {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<ChatHistory, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in ChatHistory: {:?}",
+                path
+            )));
+        }
+
+        read_json(path).map(ChatHistory)
+    }
+}
+
+impl ItemCount for ChatHistory {
+    fn item_count(&self) -> usize {
+        self.0.values().map(Vec::len).sum()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChatMessage {
+    #[serde(rename = "From")]
+    pub from: Option<String>,
+    /// e.g. `TEXT`, `MEDIA`, `NOTE`, `STICKER`, `SHARE` or `STATUSERASEDMESSAGE`
+    #[serde(rename = "Media Type")]
+    pub media_type: Option<String>,
+    /// e.g. `2023-01-01 10:00:00 UTC`
+    #[serde(rename = "Created")]
+    pub created: Option<String>,
+    #[serde(rename = "Content")]
+    pub content: Option<String>,
+    /// Only set for group chats
+    #[serde(rename = "Conversation Title")]
+    pub conversation_title: Option<String>,
+    #[serde(rename = "IsSender")]
+    pub is_sender: Option<bool>,
+    #[serde(rename = "IsSaved")]
+    pub is_saved: Option<bool>,
+    /// Ids of the files in `chat_media`, separated by ` | `
+    #[serde(rename = "Media IDs")]
+    pub media_ids: Option<String>,
+}
+
+impl ChatMessage {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        self.created.as_deref().and_then(parse_date)
+    }
+
+    /// Ids of the files attached to the message
+    pub fn media_ids(&self) -> impl Iterator<Item = &str> {
+        self.media_ids
+            .iter()
+            .flat_map(|ids| ids.split('|'))
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+    }
+
+    fn to_message(&self, media: Option<&ChatMedia>) -> ConversationMessage {
+        let mut message =
+            ConversationMessage::new(self.from.clone().unwrap_or_default(), self.timestamp());
+        message.content = self.content.clone().filter(|content| !content.is_empty());
+
+        let media_type = self.media_type.as_deref().unwrap_or_default();
+        message.kind = match media_type {
+            "SHARE" => MessageKind::Share {
+                link: None,
+                text: message.content.clone(),
+            },
+            status if status.starts_with("STATUS") => MessageKind::System,
+            _ => MessageKind::Text,
+        };
+        message.is_unsent = media_type == "STATUSERASEDMESSAGE";
+
+        if let (Some(media), Some(created)) = (media, &self.created) {
+            let date = created.get(..10).unwrap_or_default().replace('/', "-");
+            message.attachments = self
+                .media_ids()
+                .flat_map(|id| media.files_for(&date, id))
+                .map(|file| Attachment {
+                    kind: file.kind,
+                    uri: file.uri.clone(),
+                    name: file.uri.rsplit('/').next().map(str::to_owned),
+                    created_at: None,
+                })
+                .collect();
+        }
+
+        message
+    }
+}
+
+/// `json/snap_history.json`, snaps sent and received keyed by conversation. Snaps
+/// themselves aren't included in the export.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct SnapHistory(pub BTreeMap<String, Vec<Snap>>);
+
+impl TryFrom<&Path> for SnapHistory {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<SnapHistory, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in SnapHistory: {:?}",
+                path
+            )));
+        }
+
+        read_json(path).map(SnapHistory)
+    }
+}
+
+impl ItemCount for SnapHistory {
+    fn item_count(&self) -> usize {
+        self.0.values().map(Vec::len).sum()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Snap {
+    #[serde(rename = "From")]
+    pub from: Option<String>,
+    /// `IMAGE` or `VIDEO`
+    #[serde(rename = "Media Type")]
+    pub media_type: Option<String>,
+    #[serde(rename = "Created")]
+    pub created: Option<String>,
+    #[serde(rename = "Conversation Title")]
+    pub conversation_title: Option<String>,
+    #[serde(rename = "IsSender")]
+    pub is_sender: Option<bool>,
+}
+
+impl Snap {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        self.created.as_deref().and_then(parse_date)
+    }
+}
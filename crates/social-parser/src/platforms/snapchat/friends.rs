@@ -0,0 +1,73 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::parse_date;
+use crate::common::{read_json, ArchiveTimestamp, ItemCount, Milliseconds, ParseError};
+
+/// `json/friends.json`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Friends {
+    #[serde(rename = "Friends", default)]
+    pub friends: Vec<Friend>,
+    #[serde(rename = "Friend Requests Sent", default)]
+    pub friend_requests_sent: Vec<Friend>,
+    #[serde(rename = "Blocked Users", default)]
+    pub blocked_users: Vec<Friend>,
+    #[serde(rename = "Deleted Friends", default)]
+    pub deleted_friends: Vec<Friend>,
+    #[serde(rename = "Ignored Snapchatters", default)]
+    pub ignored_snapchatters: Vec<Friend>,
+    #[serde(rename = "Pending Requests", default)]
+    pub pending_requests: Vec<Friend>,
+}
+
+impl TryFrom<&Path> for Friends {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<Friends, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in Friends: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for Friends {
+    fn item_count(&self) -> usize {
+        self.friends.len()
+            + self.friend_requests_sent.len()
+            + self.blocked_users.len()
+            + self.deleted_friends.len()
+            + self.ignored_snapchatters.len()
+            + self.pending_requests.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Friend {
+    #[serde(rename = "Username")]
+    pub username: String,
+    #[serde(rename = "Display Name")]
+    pub display_name: Option<String>,
+    #[serde(rename = "Creation Timestamp")]
+    pub creation_timestamp: Option<String>,
+    #[serde(rename = "Last Modified Timestamp")]
+    pub last_modified_timestamp: Option<String>,
+    /// How the friend was added, e.g. `Added by username`
+    #[serde(rename = "Source")]
+    pub source: Option<String>,
+}
+
+impl Friend {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        self.creation_timestamp.as_deref().and_then(parse_date)
+    }
+}
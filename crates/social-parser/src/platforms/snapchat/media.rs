@@ -0,0 +1,128 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::parse_date;
+use crate::common::{
+    read_json, ArchiveTimestamp, AttachmentKind, ItemCount, LoadOptions, Milliseconds, ParseError,
+};
+
+/// Files in the `chat_media` folder. Files are named `<date>_<media id>`, e.g.
+/// `2023-01-01_b~EiASF.jpeg`, sometimes with a `media~` or `overlay~` marker before the id.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChatMedia(pub Vec<ChatMediaFile>);
+
+impl ChatMedia {
+    /// List the files in a directory. Paths are made relative to the archive root.
+    /// Assumes path is a directory.
+    pub fn load(path: &Path, options: &LoadOptions) -> Result<ChatMedia, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in ChatMedia: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("ChatMedia", path)?;
+
+        let mut files = vec![];
+        for entry in path.read_dir()? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let Some((date, id)) = name.split_once('_') else {
+                options.warn(&path, "Skipped media file without a date prefix");
+                continue;
+            };
+            let id = id.rsplit_once('.').map_or(id, |(id, _)| id);
+
+            files.push(ChatMediaFile {
+                date: date.to_owned(),
+                media_id: id.to_owned(),
+                uri: format!("chat_media/{}", name),
+                kind: AttachmentKind::from_uri(name),
+            });
+        }
+        files.sort_by(|a, b| a.uri.cmp(&b.uri));
+
+        Ok(ChatMedia(files))
+    }
+
+    /// Files sent on `date` (`YYYY-MM-DD`) that belong to the media id of a message
+    pub fn files_for<'a>(
+        &'a self,
+        date: &'a str,
+        media_id: &'a str,
+    ) -> impl Iterator<Item = &'a ChatMediaFile> {
+        self.0
+            .iter()
+            .filter(move |file| file.date == date && file.media_id.ends_with(media_id))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChatMediaFile {
+    /// Date the file was sent, as `YYYY-MM-DD`
+    pub date: String,
+    /// File name without the date prefix and extension
+    pub media_id: String,
+    /// Path relative to the archive root
+    pub uri: String,
+    pub kind: AttachmentKind,
+}
+
+/// `json/memories_history.json`. Memories aren't included in the export, only links to
+/// download them.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct MemoriesHistory {
+    #[serde(rename = "Saved Media", default)]
+    pub saved_media: Vec<Memory>,
+}
+
+impl TryFrom<&Path> for MemoriesHistory {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<MemoriesHistory, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in MemoriesHistory: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for MemoriesHistory {
+    fn item_count(&self) -> usize {
+        self.saved_media.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Memory {
+    #[serde(rename = "Date")]
+    pub date: Option<String>,
+    /// `Image` or `Video`
+    #[serde(rename = "Media Type")]
+    pub media_type: Option<String>,
+    /// e.g. `Latitude, Longitude: 52.52, 13.40`
+    #[serde(rename = "Location")]
+    pub location: Option<String>,
+    /// Expiring URL the file can be downloaded from
+    #[serde(rename = "Download Link")]
+    pub download_link: Option<String>,
+}
+
+impl Memory {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        self.date.as_deref().and_then(parse_date)
+    }
+}
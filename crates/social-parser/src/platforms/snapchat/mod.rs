@@ -0,0 +1,194 @@
+//! Snapchat "My Data" export, requested from Settings > My Data.
+//!
+//! Data files live in `json/` next to an `html/` copy of the same data, which is ignored.
+//! Files sent in chats are in `chat_media/` and are linked to their messages by date and
+//! media id. Like the other newer platforms, these models accept unknown fields and
+//! unknown files are reported as warnings.
+
+pub mod account;
+pub mod chat;
+pub mod friends;
+pub mod media;
+
+use std::path::{absolute, Path};
+
+use account::{Account, LocationHistory};
+use chat::{ChatHistory, SnapHistory};
+use friends::Friends;
+use media::{ChatMedia, MemoriesHistory};
+use serde::{Deserialize, Serialize};
+
+use crate::common::{
+    AccountInfo, Archive, ArchiveTimestamp, Conversation, Dataset, Follow, FollowDirection,
+    LoadOptions, Login, Milliseconds, ParseError, Sections,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct SnapchatArchive {
+    pub account: Option<Account>,
+    pub friends: Option<Friends>,
+    pub chat_history: Option<ChatHistory>,
+    pub snap_history: Option<SnapHistory>,
+    pub memories_history: Option<MemoriesHistory>,
+    pub location_history: Option<LocationHistory>,
+    pub chat_media: Option<ChatMedia>,
+}
+
+impl Archive for SnapchatArchive {
+    /// Load from the extracted export directory, only parsing the sections selected in
+    /// `options`
+    fn load<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Expected a directory, found: {:?}",
+                absolute(path)
+            )));
+        }
+
+        options.enter_section("SnapchatArchive", path)?;
+
+        let mut archive = SnapchatArchive::default();
+
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            match path.file_name().and_then(|s| s.to_str()) {
+                Some("json") if path.is_dir() => {
+                    for entry in path.read_dir()? {
+                        archive.load_file(&entry?.path(), options)?;
+                    }
+                }
+                Some("chat_media") if path.is_dir() => {
+                    if options.should_load(Sections::MEDIA, &path) {
+                        archive.chat_media = Some(ChatMedia::load(&path, options)?);
+                    }
+                }
+                _ if path.is_file() => {
+                    // Older exports keep the JSON files in the root
+                    archive.load_file(&path, options)?;
+                }
+                _ => {
+                    options.skip(&path, "Skipped unsupported section");
+                }
+            }
+        }
+
+        Ok(archive)
+    }
+
+    fn to_dataset(&self) -> Dataset {
+        let mut dataset = Dataset::new("snapchat");
+        if let Some(account) = &self.account {
+            let basic_information = account.basic_information.as_ref();
+            dataset.account = AccountInfo {
+                username: self.username().map(str::to_owned),
+                display_name: basic_information.and_then(|info| info.name.clone()),
+                email: None,
+            };
+            dataset.logins = account
+                .login_history
+                .iter()
+                .map(|login| Login {
+                    timestamp: login.timestamp(),
+                    ip_address: login.ip.clone(),
+                    device: login.device.clone(),
+                    location: login.country.clone(),
+                })
+                .collect();
+        }
+        dataset.conversations = self.conversations();
+        dataset.follows = self
+            .friends
+            .iter()
+            .flat_map(|friends| &friends.friends)
+            .map(|friend| Follow {
+                direction: FollowDirection::Mutual,
+                name: friend.username.clone(),
+                url: None,
+                timestamp: friend.timestamp(),
+            })
+            .collect();
+        dataset
+    }
+}
+
+impl SnapchatArchive {
+    /// Parse one JSON file into the matching section, if that section is selected
+    fn load_file(&mut self, path: &Path, options: &LoadOptions) -> Result<(), ParseError> {
+        match path.file_name().and_then(|s| s.to_str()) {
+            Some("account.json") => {
+                if options.should_load(Sections::PROFILE, path) {
+                    self.account = Some(options.load_file(path)?);
+                }
+            }
+            Some("friends.json") => {
+                if options.should_load(Sections::CONNECTIONS, path) {
+                    self.friends = Some(options.load_file(path)?);
+                }
+            }
+            Some("chat_history.json") => {
+                if options.should_load(Sections::MESSAGES, path) {
+                    self.chat_history = Some(options.load_file(path)?);
+                }
+            }
+            Some("snap_history.json") => {
+                if options.should_load(Sections::MESSAGES, path) {
+                    self.snap_history = Some(options.load_file(path)?);
+                }
+            }
+            Some("memories_history.json") => {
+                if options.should_load(Sections::MEDIA, path) {
+                    self.memories_history = Some(options.load_file(path)?);
+                }
+            }
+            Some("location_history.json") => {
+                if options.should_load(Sections::PROFILE, path) {
+                    self.location_history = Some(options.load_file(path)?);
+                }
+            }
+            _ => {
+                options.skip(path, "Skipped unsupported file");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Username of the account, from `account.json`
+    pub fn username(&self) -> Option<&str> {
+        self.account
+            .as_ref()?
+            .basic_information
+            .as_ref()?
+            .username
+            .as_deref()
+    }
+
+    /// Every saved chat as a normalized conversation, with attachments pointing at the
+    /// files in `chat_media` when the media section was loaded
+    pub fn conversations(&self) -> Vec<Conversation> {
+        self.chat_history
+            .as_ref()
+            .map(|chats| chats.conversations(self.chat_media.as_ref()))
+            .unwrap_or_default()
+    }
+}
+
+impl TryFrom<&Path> for SnapchatArchive {
+    type Error = ParseError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        SnapchatArchive::load(path, &LoadOptions::default())
+    }
+}
+
+/// Parse the dates Snapchat writes, e.g. `2023-01-01 10:00:00 UTC` or
+/// `2023/01/01 10:00:00 UTC`
+pub fn parse_date(s: &str) -> Option<ArchiveTimestamp<Milliseconds>> {
+    let s = s.trim();
+    let date = s.get(..10)?.replace('/', "-");
+    ArchiveTimestamp::parse_iso8601(&format!("{}{}", date, &s[10..]))
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::{ArchiveTimestamp, Milliseconds, Seconds};
+
+/// `personal_information` of the exporting account
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct PersonalInformation {
+    pub user_id: Option<i64>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub phone_number: Option<String>,
+    pub username: Option<String>,
+    pub bio: Option<String>,
+}
+
+/// `contacts`, the phone contacts synced to the account
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Contacts {
+    #[serde(default)]
+    pub list: Vec<Contact>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Contact {
+    pub user_id: Option<i64>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub phone_number: Option<String>,
+    /// When the contact was added, e.g. `2023-01-01T10:00:00`
+    pub date: Option<String>,
+    pub date_unixtime: Option<String>,
+}
+
+impl Contact {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        let seconds = self.date_unixtime.as_deref()?.parse().ok()?;
+        Some(ArchiveTimestamp::<Seconds>::new(seconds).convert())
+    }
+}
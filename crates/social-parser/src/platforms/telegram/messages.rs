@@ -0,0 +1,253 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::text::{FormattedText, Text, TextEntity};
+use crate::common::{
+    ArchiveTimestamp, Attachment, AttachmentKind, Conversation, ConversationMessage, MessageKind,
+    MessageReaction, Milliseconds, Poll, PollOption, ReplyTo, Seconds,
+};
+
+/// Chat with its messages. Messages are empty when the export was streamed.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Chat {
+    pub info: ChatInfo,
+    pub messages: Vec<Message>,
+}
+
+impl Chat {
+    /// Normalize into a conversation. Attachment paths are relative to the folder
+    /// containing `result.json`.
+    pub fn to_conversation(&self) -> Conversation {
+        let participants: BTreeSet<&str> =
+            self.messages.iter().filter_map(Message::sender).collect();
+
+        let mut messages: Vec<ConversationMessage> =
+            self.messages.iter().map(Message::to_message).collect();
+        messages.sort_by_key(|message| message.timestamp);
+
+        Conversation {
+            id: self.info.id.to_string(),
+            title: self.info.name.clone(),
+            participants: participants.into_iter().map(str::to_owned).collect(),
+            messages,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChatInfo {
+    pub id: i64,
+    /// Other user's name for personal chats, missing for saved messages
+    pub name: Option<String>,
+    /// e.g. `personal_chat`, `private_group`, `private_supergroup`, `public_channel`,
+    /// `bot_chat` or `saved_messages`
+    #[serde(rename = "type")]
+    pub chat_type: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Message {
+    pub id: i64,
+    /// `message` or `service`
+    #[serde(rename = "type")]
+    pub message_type: String,
+    /// Local time of the exporting machine, e.g. `2023-01-01T10:00:00`
+    pub date: String,
+    /// Seconds since the Unix epoch, as a string. Missing in older exports.
+    pub date_unixtime: Option<String>,
+    pub edited_unixtime: Option<String>,
+    pub from: Option<String>,
+    pub from_id: Option<String>,
+    /// Sender of a service message
+    pub actor: Option<String>,
+    pub actor_id: Option<String>,
+    /// Service action, e.g. `create_group`, `invite_members`, `pin_message` or
+    /// `phone_call`
+    pub action: Option<String>,
+    /// New title for `create_group` and `edit_group_title`
+    pub title: Option<String>,
+    /// Users added or removed by the action
+    #[serde(default)]
+    pub members: Vec<Option<String>>,
+    #[serde(default)]
+    pub text: Text,
+    /// Same content as `text` as a flat list. Missing in older exports.
+    #[serde(default)]
+    pub text_entities: Vec<TextEntity>,
+    pub reply_to_message_id: Option<i64>,
+    /// Original sender of a forwarded message
+    pub forwarded_from: Option<String>,
+    pub saved_from: Option<String>,
+    pub via_bot: Option<String>,
+    /// Path of the photo, relative to the folder containing `result.json`
+    pub photo: Option<String>,
+    /// Path of the file, or a note that it wasn't included in the export
+    pub file: Option<String>,
+    pub file_name: Option<String>,
+    pub thumbnail: Option<String>,
+    /// e.g. `sticker`, `animation`, `video_file`, `video_message`, `voice_message` or
+    /// `audio_file`
+    pub media_type: Option<String>,
+    pub mime_type: Option<String>,
+    pub sticker_emoji: Option<String>,
+    pub duration_seconds: Option<u64>,
+    /// Reason a `phone_call` ended, e.g. `missed`, `busy` or `hangup`
+    pub discard_reason: Option<String>,
+    pub poll: Option<TelegramPoll>,
+    #[serde(default)]
+    pub reactions: Vec<Reaction>,
+}
+
+impl Message {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        match self.date_unixtime.as_deref().and_then(|s| s.parse().ok()) {
+            Some(seconds) => Some(ArchiveTimestamp::<Seconds>::new(seconds).convert()),
+            None => ArchiveTimestamp::parse_iso8601(&self.date),
+        }
+    }
+
+    /// Author of a message or actor of a service message
+    pub fn sender(&self) -> Option<&str> {
+        self.from.as_deref().or(self.actor.as_deref())
+    }
+
+    /// Plain text with formatting spans, from `text_entities` when present
+    pub fn formatted_text(&self) -> FormattedText {
+        if self.text_entities.is_empty() {
+            FormattedText::from(&self.text)
+        } else {
+            FormattedText::from_entities(&self.text_entities)
+        }
+    }
+
+    /// Files attached to the message that were included in the export
+    pub fn attachments(&self) -> Vec<Attachment> {
+        let photo = self
+            .photo
+            .as_ref()
+            .map(|photo| (AttachmentKind::Photo, photo));
+        let file = self.file.as_ref().map(|file| {
+            let kind = match self.media_type.as_deref() {
+                Some("sticker") => AttachmentKind::Sticker,
+                Some("animation") => AttachmentKind::Gif,
+                Some("video_file") | Some("video_message") => AttachmentKind::Video,
+                Some("voice_message") | Some("audio_file") => AttachmentKind::Audio,
+                _ => AttachmentKind::from_uri(file),
+            };
+            (kind, file)
+        });
+
+        photo
+            .into_iter()
+            .chain(file)
+            // Files left out of the export are replaced by a note in parentheses
+            .filter(|(_, uri)| !uri.starts_with('('))
+            .map(|(kind, uri)| Attachment {
+                kind,
+                uri: uri.clone(),
+                name: self.file_name.clone(),
+                created_at: None,
+            })
+            .collect()
+    }
+
+    pub(crate) fn to_message(&self) -> ConversationMessage {
+        let mut message = ConversationMessage::new(
+            self.sender().unwrap_or_default().to_owned(),
+            self.timestamp(),
+        );
+        message.id = Some(self.id.to_string());
+        message.content = Some(self.formatted_text().text).filter(|text| !text.is_empty());
+        message.attachments = self.attachments();
+        message.reply_to = self.reply_to_message_id.map(|id| ReplyTo {
+            message_id: Some(id.to_string()),
+            sender: None,
+            content: None,
+        });
+        message.reactions = self
+            .reactions
+            .iter()
+            .flat_map(|reaction| {
+                let emoji = reaction.emoji.clone().unwrap_or_default();
+                reaction.recent.iter().map(move |recent| MessageReaction {
+                    reaction: emoji.clone(),
+                    actor: recent.from.clone().unwrap_or_default(),
+                    timestamp: recent
+                        .date
+                        .as_deref()
+                        .and_then(ArchiveTimestamp::parse_iso8601),
+                })
+            })
+            .collect();
+
+        message.kind = match (self.action.as_deref(), &self.poll) {
+            (Some("phone_call") | Some("group_call"), _) => MessageKind::Call {
+                duration_secs: self.duration_seconds,
+                missed: self.discard_reason.as_deref() == Some("missed"),
+            },
+            (Some(_), _) => MessageKind::System,
+            (None, Some(poll)) => MessageKind::Poll(Poll {
+                question: poll.question.clone(),
+                options: poll
+                    .answers
+                    .iter()
+                    .map(|answer| PollOption {
+                        text: answer.text.clone(),
+                        // Only vote counts are exported
+                        voters: vec![],
+                    })
+                    .collect(),
+            }),
+            (None, None) => MessageKind::Text,
+        };
+
+        message
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Reaction {
+    /// `emoji`, `custom_emoji` or `paid`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub count: u64,
+    pub emoji: Option<String>,
+    pub document_id: Option<String>,
+    /// Latest users to react, not every one of them
+    #[serde(default)]
+    pub recent: Vec<RecentReaction>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct RecentReaction {
+    pub from: Option<String>,
+    pub from_id: Option<String>,
+    pub date: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct TelegramPoll {
+    pub question: String,
+    #[serde(default)]
+    pub closed: bool,
+    pub total_voters: Option<u64>,
+    #[serde(default)]
+    pub answers: Vec<PollAnswer>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct PollAnswer {
+    pub text: String,
+    #[serde(default)]
+    pub voters: u64,
+    #[serde(default)]
+    pub chosen: bool,
+}
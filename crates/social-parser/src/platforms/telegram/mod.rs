@@ -0,0 +1,207 @@
+//! Telegram Desktop export, made from Settings > Advanced > Export Telegram data with the
+//! "Machine-readable JSON" format.
+//!
+//! Everything is in a single `result.json`, next to folders of photos, files and
+//! stickers. The file can be several gigabytes, so it is streamed rather than read into
+//! memory at once, and [`TelegramArchive::stream`] hands out messages one at a time
+//! without keeping them. Like the other newer platforms, these models accept unknown
+//! fields.
+
+pub mod account;
+pub mod messages;
+pub mod text;
+
+mod reader;
+
+use std::{
+    fs::File,
+    io::BufReader,
+    path::{absolute, Path, PathBuf},
+};
+
+use account::{Contacts, PersonalInformation};
+use messages::{Chat, ChatInfo, Message};
+use reader::{Reader, Root};
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
+
+use crate::common::{
+    AccountInfo, Archive, Conversation, Dataset, ItemCount, LoadOptions, ParseError,
+    StreamingArchive,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct TelegramArchive {
+    pub personal_information: Option<PersonalInformation>,
+    pub contacts: Option<Contacts>,
+    /// Chats, including chats that were left. Exports of a single chat hold just that
+    /// chat.
+    pub chats: Option<Vec<Chat>>,
+}
+
+impl Archive for TelegramArchive {
+    /// Load from `result.json` or the directory containing it, only parsing the sections
+    /// selected in `options`
+    fn load<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError> {
+        TelegramArchive::read(path.as_ref(), options, true, |_, _| Ok(()))
+    }
+
+    fn to_dataset(&self) -> Dataset {
+        let mut dataset = Dataset::new("telegram");
+        if let Some(info) = &self.personal_information {
+            let names: Vec<&str> = [&info.first_name, &info.last_name]
+                .into_iter()
+                .flatten()
+                .map(String::as_str)
+                .filter(|name| !name.is_empty())
+                .collect();
+            dataset.account = AccountInfo {
+                username: info.username.clone(),
+                display_name: (!names.is_empty()).then(|| names.join(" ")),
+                email: None,
+            };
+        }
+        dataset.conversations = self.conversations();
+        dataset
+    }
+}
+
+impl StreamingArchive for TelegramArchive {
+    const PLATFORM: &'static str = "telegram";
+
+    /// Load from `result.json` or the directory containing it, passing each message as a
+    /// conversation of its own, tagged with the path of `result.json`. Chats are returned
+    /// without messages, like [`TelegramArchive::stream`].
+    fn stream_conversations<P, F>(
+        path: P,
+        options: &LoadOptions,
+        mut on_conversation: F,
+    ) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&Path, Conversation) -> Result<(), ParseError>,
+    {
+        let source = result_file(path.as_ref());
+        TelegramArchive::stream(path, options, |info, message| {
+            let conversation = Conversation {
+                id: info.id.to_string(),
+                title: info.name.clone(),
+                participants: message.sender().map(str::to_owned).into_iter().collect(),
+                messages: vec![message.to_message()],
+            };
+            on_conversation(&source, conversation)
+        })
+    }
+
+    /// Everything is read from `result.json`
+    fn dataset_source(path: &Path) -> PathBuf {
+        result_file(path)
+    }
+}
+
+impl TelegramArchive {
+    /// Load from `result.json` or the directory containing it, passing each message to
+    /// `on_message` as it is parsed instead of keeping it. The returned archive has
+    /// everything else, with chats left without messages. An error from `on_message`
+    /// stops loading and is returned.
+    pub fn stream<P, F>(
+        path: P,
+        options: &LoadOptions,
+        on_message: F,
+    ) -> Result<TelegramArchive, ParseError>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&ChatInfo, Message) -> Result<(), ParseError>,
+    {
+        TelegramArchive::read(path.as_ref(), options, false, on_message)
+    }
+
+    fn read<F>(
+        path: &Path,
+        options: &LoadOptions,
+        collect: bool,
+        on_message: F,
+    ) -> Result<TelegramArchive, ParseError>
+    where
+        F: FnMut(&ChatInfo, Message) -> Result<(), ParseError>,
+    {
+        let file = result_file(path);
+        if !file.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Expected result.json or the directory containing it, found: {:?}",
+                absolute(path)
+            )));
+        }
+
+        options.enter_section("TelegramArchive", &file)?;
+
+        let bytes = file.metadata().map(|m| m.len()).unwrap_or(0);
+        let streamed = options.load_with(&file, bytes, || {
+            let mut reader = Reader {
+                options,
+                path: &file,
+                on_message,
+                collect,
+                archive: TelegramArchive::default(),
+                message_count: 0,
+                error: None,
+            };
+
+            let mut deserializer =
+                serde_json::Deserializer::from_reader(BufReader::new(File::open(&file)?));
+            let result = Root(&mut reader)
+                .deserialize(&mut deserializer)
+                .and_then(|_| deserializer.end());
+            if let Some(error) = reader.error {
+                return Err(error);
+            }
+            result.map_err(|e| ParseError::Serde(file.clone(), e))?;
+
+            Ok(Streamed {
+                archive: reader.archive,
+                message_count: reader.message_count,
+            })
+        })?;
+
+        Ok(streamed.archive)
+    }
+
+    /// Every chat as a normalized conversation. Attachment paths are relative to the
+    /// folder containing `result.json`.
+    pub fn conversations(&self) -> Vec<Conversation> {
+        self.chats
+            .iter()
+            .flatten()
+            .map(Chat::to_conversation)
+            .collect()
+    }
+}
+
+/// `result.json` itself, or inside the directory `path`
+fn result_file(path: &Path) -> PathBuf {
+    if path.is_dir() {
+        path.join("result.json")
+    } else {
+        path.to_owned()
+    }
+}
+
+impl TryFrom<&Path> for TelegramArchive {
+    type Error = ParseError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        TelegramArchive::load(path, &LoadOptions::default())
+    }
+}
+
+/// Archive read from `result.json`, counting the messages that were streamed
+struct Streamed {
+    archive: TelegramArchive,
+    message_count: usize,
+}
+
+impl ItemCount for Streamed {
+    fn item_count(&self) -> usize {
+        self.message_count
+    }
+}
@@ -0,0 +1,276 @@
+//! Streaming visitors for `result.json`, which can be several gigabytes. Messages are
+//! parsed one at a time and sections that weren't selected are skipped without being
+//! kept in memory.
+
+use std::{fmt, path::Path};
+
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+
+use super::{
+    messages::{Chat, ChatInfo, Message},
+    TelegramArchive,
+};
+use crate::common::{LoadOptions, ParseError, Sections};
+
+pub(super) struct Reader<'a, F> {
+    pub options: &'a LoadOptions,
+    pub path: &'a Path,
+    pub on_message: F,
+    /// Keep messages in their chats instead of passing them to `on_message`
+    pub collect: bool,
+    pub archive: TelegramArchive,
+    pub message_count: usize,
+    /// Error that stopped the deserializer, returned instead of the serde error
+    pub error: Option<ParseError>,
+}
+
+impl<F> Reader<'_, F>
+where
+    F: FnMut(&ChatInfo, Message) -> Result<(), ParseError>,
+{
+    fn should_load(&self, sections: Sections) -> bool {
+        self.options.should_load(sections, self.path)
+    }
+
+    fn message(
+        &mut self,
+        info: &ChatInfo,
+        message: Message,
+        messages: &mut Vec<Message>,
+    ) -> Result<(), ParseError> {
+        self.options.check_cancelled()?;
+        self.message_count += 1;
+        if self.collect {
+            messages.push(message);
+            Ok(())
+        } else {
+            (self.on_message)(info, message)
+        }
+    }
+
+    fn push_chat(&mut self, info: ChatInfo, messages: Vec<Message>) {
+        self.archive
+            .chats
+            .get_or_insert_with(Vec::new)
+            .push(Chat { info, messages });
+    }
+}
+
+/// Whole export, or a single chat exported on its own
+pub(super) struct Root<'r, 'a, F>(pub &'r mut Reader<'a, F>);
+
+impl<'de, F> DeserializeSeed<'de> for Root<'_, '_, F>
+where
+    F: FnMut(&ChatInfo, Message) -> Result<(), ParseError>,
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F> Visitor<'de> for Root<'_, '_, F>
+where
+    F: FnMut(&ChatInfo, Message) -> Result<(), ParseError>,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a Telegram export")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let reader = self.0;
+        let mut info = ChatInfo::default();
+        let mut messages = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "personal_information" if reader.should_load(Sections::PROFILE) => {
+                    reader.archive.personal_information = Some(map.next_value()?);
+                }
+                "contacts" if reader.should_load(Sections::CONTACTS) => {
+                    reader.archive.contacts = Some(map.next_value()?);
+                }
+                "chats" | "left_chats" if reader.should_load(Sections::MESSAGES) => {
+                    reader.archive.chats.get_or_insert_with(Vec::new);
+                    map.next_value_seed(ChatList(&mut *reader))?;
+                }
+                "id" => info.id = map.next_value()?,
+                "name" => info.name = map.next_value()?,
+                "type" => info.chat_type = map.next_value()?,
+                "messages" if reader.should_load(Sections::MESSAGES) => {
+                    messages = Some(map.next_value_seed(Messages {
+                        reader: &mut *reader,
+                        info: &info,
+                    })?);
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        if let Some(messages) = messages {
+            reader.push_chat(info, messages);
+        }
+
+        Ok(())
+    }
+}
+
+/// `chats` or `left_chats`, an object holding the `list` of chats
+struct ChatList<'r, 'a, F>(&'r mut Reader<'a, F>);
+
+impl<'de, F> DeserializeSeed<'de> for ChatList<'_, '_, F>
+where
+    F: FnMut(&ChatInfo, Message) -> Result<(), ParseError>,
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F> Visitor<'de> for ChatList<'_, '_, F>
+where
+    F: FnMut(&ChatInfo, Message) -> Result<(), ParseError>,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a list of chats")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "list" {
+                map.next_value_seed(Chats(&mut *self.0))?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Chats<'r, 'a, F>(&'r mut Reader<'a, F>);
+
+impl<'de, F> DeserializeSeed<'de> for Chats<'_, '_, F>
+where
+    F: FnMut(&ChatInfo, Message) -> Result<(), ParseError>,
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, F> Visitor<'de> for Chats<'_, '_, F>
+where
+    F: FnMut(&ChatInfo, Message) -> Result<(), ParseError>,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a list of chats")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while seq.next_element_seed(ChatSeed(&mut *self.0))?.is_some() {}
+        Ok(())
+    }
+}
+
+/// One chat. Telegram writes the chat's details before its messages, so messages are
+/// streamed with the details already known.
+struct ChatSeed<'r, 'a, F>(&'r mut Reader<'a, F>);
+
+impl<'de, F> DeserializeSeed<'de> for ChatSeed<'_, '_, F>
+where
+    F: FnMut(&ChatInfo, Message) -> Result<(), ParseError>,
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F> Visitor<'de> for ChatSeed<'_, '_, F>
+where
+    F: FnMut(&ChatInfo, Message) -> Result<(), ParseError>,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a chat")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let reader = self.0;
+        let mut info = ChatInfo::default();
+        let mut messages = vec![];
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "id" => info.id = map.next_value()?,
+                "name" => info.name = map.next_value()?,
+                "type" => info.chat_type = map.next_value()?,
+                "messages" => {
+                    messages = map.next_value_seed(Messages {
+                        reader: &mut *reader,
+                        info: &info,
+                    })?;
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        reader.push_chat(info, messages);
+        Ok(())
+    }
+}
+
+struct Messages<'r, 'a, 'i, F> {
+    reader: &'r mut Reader<'a, F>,
+    info: &'i ChatInfo,
+}
+
+impl<'de, F> DeserializeSeed<'de> for Messages<'_, '_, '_, F>
+where
+    F: FnMut(&ChatInfo, Message) -> Result<(), ParseError>,
+{
+    type Value = Vec<Message>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Vec<Message>, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, F> Visitor<'de> for Messages<'_, '_, '_, F>
+where
+    F: FnMut(&ChatInfo, Message) -> Result<(), ParseError>,
+{
+    type Value = Vec<Message>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a list of messages")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<Message>, A::Error> {
+        let mut messages = vec![];
+        while let Some(message) = seq.next_element()? {
+            if let Err(error) = self.reader.message(self.info, message, &mut messages) {
+                self.reader.error = Some(error);
+                return Err(de::Error::custom("stopped reading messages"));
+            }
+        }
+        Ok(messages)
+    }
+}
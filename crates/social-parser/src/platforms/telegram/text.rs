@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+/// Message text as exported, either a plain string or a list of plain strings and
+/// formatted entities
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(untagged)]
+pub enum Text {
+    Plain(String),
+    Rich(Vec<TextPart>),
+}
+
+impl Default for Text {
+    fn default() -> Self {
+        Text::Plain(String::new())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(untagged)]
+pub enum TextPart {
+    Plain(String),
+    Entity(TextEntity),
+}
+
+/// Run of text with one kind of formatting
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct TextEntity {
+    /// e.g. `plain`, `bold`, `italic`, `code`, `pre`, `link`, `text_link`, `mention`,
+    /// `mention_name`, `hashtag`, `spoiler` or `custom_emoji`
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub text: String,
+    /// Target of a `text_link`
+    pub href: Option<String>,
+    /// User of a `mention_name`
+    pub user_id: Option<i64>,
+    /// Language of a `pre` block
+    pub language: Option<String>,
+    /// Sticker set document of a `custom_emoji`
+    pub document_id: Option<String>,
+}
+
+/// Plain text with the formatting that applied to it
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct FormattedText {
+    pub text: String,
+    pub spans: Vec<TextSpan>,
+}
+
+impl FormattedText {
+    /// Join entities into plain text, keeping a span for every entity that isn't plain
+    pub fn from_entities<'a>(entities: impl IntoIterator<Item = &'a TextEntity>) -> Self {
+        let mut formatted = FormattedText::default();
+        let mut offset = 0;
+
+        for entity in entities {
+            let length = entity.text.chars().count();
+            if entity.kind != "plain" {
+                formatted.spans.push(TextSpan {
+                    kind: entity.kind.clone(),
+                    offset,
+                    length,
+                    href: entity.href.clone(),
+                    user_id: entity.user_id,
+                    language: entity.language.clone(),
+                });
+            }
+            formatted.text.push_str(&entity.text);
+            offset += length;
+        }
+
+        formatted
+    }
+}
+
+impl From<&Text> for FormattedText {
+    fn from(text: &Text) -> Self {
+        match text {
+            Text::Plain(text) => FormattedText {
+                text: text.clone(),
+                spans: vec![],
+            },
+            Text::Rich(parts) => {
+                let entities: Vec<TextEntity> = parts
+                    .iter()
+                    .map(|part| match part {
+                        TextPart::Plain(text) => TextEntity {
+                            kind: "plain".to_owned(),
+                            text: text.clone(),
+                            href: None,
+                            user_id: None,
+                            language: None,
+                            document_id: None,
+                        },
+                        TextPart::Entity(entity) => entity.clone(),
+                    })
+                    .collect();
+                FormattedText::from_entities(&entities)
+            }
+        }
+    }
+}
+
+/// Formatting applied to part of a [`FormattedText`]. Offsets and lengths count Unicode
+/// characters, not bytes.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct TextSpan {
+    /// Entity type, see [`TextEntity::kind`]
+    pub kind: String,
+    pub offset: usize,
+    pub length: usize,
+    pub href: Option<String>,
+    pub user_id: Option<i64>,
+    pub language: Option<String>,
+}
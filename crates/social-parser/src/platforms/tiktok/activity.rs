@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+
+use super::parse_date;
+use crate::common::{ArchiveTimestamp, Milliseconds};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Activity {
+    #[serde(rename = "Video Browsing History")]
+    pub video_browsing_history: Option<VideoBrowsingHistory>,
+    #[serde(rename = "Like List")]
+    pub like_list: Option<LikeList>,
+    #[serde(rename = "Favorite Videos")]
+    pub favorite_videos: Option<FavoriteVideos>,
+    #[serde(rename = "Follower List")]
+    pub follower_list: Option<FollowerList>,
+    #[serde(rename = "Following List")]
+    pub following_list: Option<FollowingList>,
+    #[serde(rename = "Login History")]
+    pub login_history: Option<LoginHistory>,
+    #[serde(rename = "Search History")]
+    pub search_history: Option<SearchHistory>,
+    #[serde(rename = "Share History")]
+    pub share_history: Option<ShareHistory>,
+}
+
+impl Activity {
+    pub(super) fn item_count(&self) -> usize {
+        self.video_browsing_history
+            .as_ref()
+            .map_or(0, |h| h.videos.len())
+            + self.like_list.as_ref().map_or(0, |l| l.likes.len())
+            + self.favorite_videos.as_ref().map_or(0, |f| f.videos.len())
+            + self.follower_list.as_ref().map_or(0, |f| f.followers.len())
+            + self
+                .following_list
+                .as_ref()
+                .map_or(0, |f| f.following.len())
+            + self.login_history.as_ref().map_or(0, |l| l.logins.len())
+            + self.search_history.as_ref().map_or(0, |s| s.searches.len())
+            + self.share_history.as_ref().map_or(0, |s| s.shares.len())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct VideoBrowsingHistory {
+    #[serde(rename = "VideoList", default)]
+    pub videos: Vec<VideoLink>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct LikeList {
+    #[serde(rename = "ItemFavoriteList", default)]
+    pub likes: Vec<VideoLink>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct FavoriteVideos {
+    #[serde(rename = "FavoriteVideoList", default)]
+    pub videos: Vec<VideoLink>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct FollowerList {
+    #[serde(rename = "FansList", default)]
+    pub followers: Vec<UserEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct FollowingList {
+    #[serde(rename = "Following", default)]
+    pub following: Vec<UserEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct LoginHistory {
+    #[serde(rename = "LoginHistoryList", default)]
+    pub logins: Vec<Login>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct SearchHistory {
+    #[serde(rename = "SearchList", default)]
+    pub searches: Vec<Search>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ShareHistory {
+    #[serde(rename = "ShareHistoryList", default)]
+    pub shares: Vec<Share>,
+}
+
+/// Video watched, liked or favorited
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "PascalCase")]
+pub struct VideoLink {
+    #[serde(default)]
+    pub date: String,
+    /// Share link, e.g. `https://www.tiktokv.com/share/video/7000000000000000000/`
+    pub link: Option<String>,
+}
+
+impl VideoLink {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_date(&self.date)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "PascalCase")]
+pub struct UserEntry {
+    #[serde(default)]
+    pub date: String,
+    pub user_name: Option<String>,
+}
+
+impl UserEntry {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_date(&self.date)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "PascalCase")]
+pub struct Login {
+    #[serde(default)]
+    pub date: String,
+    #[serde(rename = "IP")]
+    pub ip: Option<String>,
+    pub device_model: Option<String>,
+    pub device_system: Option<String>,
+    pub network_type: Option<String>,
+    pub carrier: Option<String>,
+}
+
+impl Login {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_date(&self.date)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "PascalCase")]
+pub struct Search {
+    #[serde(default)]
+    pub date: String,
+    pub search_term: Option<String>,
+}
+
+impl Search {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_date(&self.date)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "PascalCase")]
+pub struct Share {
+    #[serde(default)]
+    pub date: String,
+    /// e.g. `video` or `live`
+    pub shared_content: Option<String>,
+    pub link: Option<String>,
+    /// App or channel it was shared to
+    pub method: Option<String>,
+}
+
+impl Share {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_date(&self.date)
+    }
+}
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+use super::parse_date;
+use crate::common::{ArchiveTimestamp, Comment, Milliseconds, Post};
+
+/// The `Comment` section
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Comments {
+    #[serde(rename = "Comments")]
+    pub comments: Option<CommentList>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct CommentList {
+    #[serde(rename = "CommentsList", default)]
+    pub comments: Vec<TikTokComment>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "PascalCase")]
+pub struct TikTokComment {
+    #[serde(default)]
+    pub date: String,
+    #[serde(default)]
+    pub comment: String,
+    pub photo: Option<String>,
+    /// Video the comment was made on, missing in most exports
+    pub url: Option<String>,
+}
+
+impl TikTokComment {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_date(&self.date)
+    }
+
+    /// Normalize into a comment written by `author`
+    pub fn to_comment(&self, author: Option<&str>) -> Comment {
+        Comment {
+            id: None,
+            author: author.map(str::to_owned),
+            timestamp: self.timestamp(),
+            text: self.comment.clone(),
+            url: None,
+            post: self.url.clone(),
+            parent: None,
+            community: None,
+        }
+    }
+}
+
+/// The `Video` section, videos posted by the account
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Videos {
+    #[serde(rename = "Videos")]
+    pub videos: Option<VideoList>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct VideoList {
+    #[serde(rename = "VideoList", default)]
+    pub videos: Vec<PostedVideo>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "PascalCase")]
+pub struct PostedVideo {
+    #[serde(default)]
+    pub date: String,
+    pub link: Option<String>,
+    /// Caption, only in newer exports
+    pub title: Option<String>,
+    /// Number or numeric string depending on the export
+    pub likes: Option<serde_json::Value>,
+    /// e.g. `Everyone` or `Friends`
+    pub who_can_view: Option<String>,
+    pub sound: Option<String>,
+    pub location: Option<String>,
+}
+
+impl PostedVideo {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_date(&self.date)
+    }
+
+    /// Normalize into a post written by `author`
+    pub fn to_post(&self, author: Option<&str>) -> Post {
+        Post {
+            id: self.link.as_deref().and_then(video_id).map(str::to_owned),
+            author: author.map(str::to_owned),
+            timestamp: self.timestamp(),
+            title: None,
+            text: self.title.clone(),
+            url: self.link.clone(),
+            link: None,
+            community: None,
+            attachments: vec![],
+        }
+    }
+}
+
+/// Id of a video from its share link, the last numeric path segment
+pub fn video_id(link: &str) -> Option<&str> {
+    link.split(['/', '?'])
+        .rfind(|segment| !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()))
+}
@@ -0,0 +1,81 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::parse_date;
+use crate::common::{ArchiveTimestamp, Conversation, ConversationMessage, Milliseconds};
+
+/// The `Direct Messages` section
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct DirectMessages {
+    #[serde(rename = "Chat History")]
+    pub chat_history: Option<ChatHistory>,
+}
+
+impl DirectMessages {
+    /// Every chat as a normalized conversation. Chats are identified by their title,
+    /// e.g. `Chat History with jane:`, as the export has no ids.
+    pub fn conversations(&self) -> Vec<Conversation> {
+        self.chat_history
+            .iter()
+            .flat_map(|history| &history.chats)
+            .map(|(title, messages)| {
+                let other = title
+                    .strip_prefix("Chat History with ")
+                    .unwrap_or(title)
+                    .trim_end_matches(':');
+                let mut participants: BTreeSet<&str> = messages
+                    .iter()
+                    .map(|message| message.from.as_str())
+                    .collect();
+                participants.insert(other);
+
+                let mut converted: Vec<ConversationMessage> = messages
+                    .iter()
+                    .map(|message| {
+                        let mut converted =
+                            ConversationMessage::new(message.from.clone(), message.timestamp());
+                        converted.content = Some(message.content.clone());
+                        converted
+                    })
+                    .collect();
+                converted.sort_by_key(|message| message.timestamp);
+
+                Conversation {
+                    id: title.clone(),
+                    title: None,
+                    participants: participants.into_iter().map(str::to_owned).collect(),
+                    messages: converted,
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChatHistory {
+    /// Keyed by chat title, e.g. `Chat History with jane:`
+    #[serde(rename = "ChatHistory", default)]
+    pub chats: BTreeMap<String, Vec<ChatMessage>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "PascalCase")]
+pub struct ChatMessage {
+    #[serde(default)]
+    pub date: String,
+    #[serde(default)]
+    pub from: String,
+    /// Text, or the share link of a video sent in the chat
+    #[serde(default)]
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_date(&self.date)
+    }
+}
@@ -0,0 +1,342 @@
+//! TikTok data export, requested from the "Download your data" page in settings.
+//!
+//! The export is a single `user_data.json` (or `user_data_tiktok.json`) holding every
+//! section. Only the JSON variant is supported, the TXT variant has no stable layout.
+//! TikTok writes missing values as empty strings or `N/A` and renamed some sections over
+//! time, so the file is normalized before it is parsed into these models.
+
+pub mod activity;
+pub mod content;
+pub mod direct_messages;
+pub mod profile;
+
+use std::path::{absolute, Path};
+
+use activity::Activity;
+use content::{Comments, Videos};
+use direct_messages::DirectMessages;
+use profile::Profile;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::common::{
+    read_json, AccountInfo, Archive, ArchiveTimestamp, Comment, Conversation, Dataset, Follow,
+    FollowDirection, ItemCount, Like, LoadOptions, Login, Milliseconds, ParseError, Post,
+    SavedItem, Sections,
+};
+
+/// Names of the export file, newest first
+const FILE_NAMES: [&str; 2] = ["user_data_tiktok.json", "user_data.json"];
+
+/// Section names used by newer exports and the older names the models use
+const RENAMED_KEYS: [(&str, &str); 5] = [
+    ("Your Activity", "Activity"),
+    ("Direct Message", "Direct Messages"),
+    ("Post", "Video"),
+    ("Posts", "Videos"),
+    ("Watch History", "Video Browsing History"),
+];
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct TikTokArchive {
+    pub activity: Option<Activity>,
+    pub comments: Option<Comments>,
+    pub direct_messages: Option<DirectMessages>,
+    pub profile: Option<Profile>,
+    pub videos: Option<Videos>,
+}
+
+impl Archive for TikTokArchive {
+    /// Load from the `user_data.json` file or the directory containing it, only parsing
+    /// the sections selected in `options`
+    fn load<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        let file = if path.is_dir() {
+            FILE_NAMES
+                .iter()
+                .map(|name| path.join(name))
+                .find(|file| file.is_file())
+                .ok_or_else(|| {
+                    ParseError::UnexpectedFormat(format!(
+                        "Expected a user_data.json file in: {:?}",
+                        absolute(path)
+                    ))
+                })?
+        } else {
+            path.to_owned()
+        };
+
+        if file.extension().and_then(|s| s.to_str()) == Some("txt") {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "TXT exports aren't supported, request the export as JSON instead: {:?}",
+                absolute(&file)
+            )));
+        }
+        if !file.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Expected a directory or JSON file, found: {:?}",
+                absolute(&file)
+            )));
+        }
+
+        options.enter_section("TikTokArchive", &file)?;
+
+        let bytes = file.metadata().map(|m| m.len()).unwrap_or(0);
+        options.load_with(&file, bytes, || {
+            let mut value: Value = read_json(&file)?;
+            normalize(&mut value, 0);
+            let Value::Object(mut sections) = value else {
+                return Err(ParseError::UnexpectedFormat(format!(
+                    "Expected a JSON object in: {:?}",
+                    absolute(&file)
+                )));
+            };
+
+            let mut archive = TikTokArchive::default();
+            if let Some(Value::Object(mut activity)) = sections.remove("Activity") {
+                activity.retain(|key, _| options.should_load(activity_section(key), &file));
+                archive.activity = Some(from_value(Value::Object(activity), &file)?);
+            }
+            if options.should_load(Sections::COMMENTS, &file) {
+                archive.comments = take(&mut sections, "Comment", &file)?;
+            }
+            if options.should_load(Sections::MESSAGES, &file) {
+                archive.direct_messages = take(&mut sections, "Direct Messages", &file)?;
+            }
+            if options.should_load(Sections::PROFILE, &file) {
+                archive.profile = take(&mut sections, "Profile", &file)?;
+            }
+            if options.should_load(Sections::POSTS, &file) {
+                archive.videos = take(&mut sections, "Video", &file)?;
+            }
+
+            Ok(archive)
+        })
+    }
+
+    fn to_dataset(&self) -> Dataset {
+        let mut dataset = Dataset::new("tiktok");
+        dataset.account = AccountInfo {
+            username: self.username().map(str::to_owned),
+            display_name: None,
+            email: self
+                .profile
+                .as_ref()
+                .and_then(|profile| profile.profile_information.as_ref())
+                .and_then(|information| information.profile_map.as_ref())
+                .and_then(|map| map.email_address.clone()),
+        };
+        dataset.conversations = self.conversations();
+        dataset.posts = self.normalized_posts();
+        dataset.comments = self.normalized_comments();
+
+        let Some(activity) = &self.activity else {
+            return dataset;
+        };
+        let followers = activity
+            .follower_list
+            .iter()
+            .flat_map(|list| &list.followers)
+            .map(|user| (FollowDirection::Follower, user));
+        let following = activity
+            .following_list
+            .iter()
+            .flat_map(|list| &list.following)
+            .map(|user| (FollowDirection::Following, user));
+        dataset.follows = followers
+            .chain(following)
+            .filter_map(|(direction, user)| {
+                Some(Follow {
+                    direction,
+                    name: user.user_name.clone()?,
+                    url: None,
+                    timestamp: user.timestamp(),
+                })
+            })
+            .collect();
+        dataset.likes = activity
+            .like_list
+            .iter()
+            .flat_map(|list| &list.likes)
+            .map(|video| Like {
+                target: video.link.clone(),
+                title: None,
+                reaction: None,
+                timestamp: video.timestamp(),
+            })
+            .collect();
+        dataset.saved_items = activity
+            .favorite_videos
+            .iter()
+            .flat_map(|list| &list.videos)
+            .map(|video| SavedItem {
+                target: video.link.clone(),
+                title: None,
+                collection: None,
+                timestamp: video.timestamp(),
+            })
+            .collect();
+        dataset.logins = activity
+            .login_history
+            .iter()
+            .flat_map(|history| &history.logins)
+            .map(|login| Login {
+                timestamp: login.timestamp(),
+                ip_address: login.ip.clone(),
+                device: login.device_model.clone(),
+                location: None,
+            })
+            .collect();
+        dataset
+    }
+}
+
+impl TikTokArchive {
+    /// Username of the account, from the profile
+    pub fn username(&self) -> Option<&str> {
+        self.profile
+            .as_ref()?
+            .profile_information
+            .as_ref()?
+            .profile_map
+            .as_ref()?
+            .user_name
+            .as_deref()
+    }
+
+    /// Videos posted by the account, normalized
+    pub fn normalized_posts(&self) -> Vec<Post> {
+        self.videos
+            .iter()
+            .flat_map(|videos| &videos.videos)
+            .flat_map(|list| &list.videos)
+            .map(|video| video.to_post(self.username()))
+            .collect()
+    }
+
+    /// Comments made by the account, normalized
+    pub fn normalized_comments(&self) -> Vec<Comment> {
+        self.comments
+            .iter()
+            .flat_map(|comments| &comments.comments)
+            .flat_map(|list| &list.comments)
+            .map(|comment| comment.to_comment(self.username()))
+            .collect()
+    }
+
+    /// Every direct message chat as a normalized conversation
+    pub fn conversations(&self) -> Vec<Conversation> {
+        self.direct_messages
+            .as_ref()
+            .map(DirectMessages::conversations)
+            .unwrap_or_default()
+    }
+}
+
+impl TryFrom<&Path> for TikTokArchive {
+    type Error = ParseError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        TikTokArchive::load(path, &LoadOptions::default())
+    }
+}
+
+impl ItemCount for TikTokArchive {
+    fn item_count(&self) -> usize {
+        self.activity.as_ref().map_or(0, Activity::item_count)
+            + self.normalized_comments().len()
+            + self
+                .direct_messages
+                .iter()
+                .flat_map(|messages| &messages.chat_history)
+                .flat_map(|history| history.chats.values())
+                .map(Vec::len)
+                .sum::<usize>()
+            + self.normalized_posts().len()
+    }
+}
+
+/// Parse the dates TikTok writes, e.g. `2023-01-01 10:00:00`, `2023/01/01 10:00:00` or
+/// `01/31/2023 10:00:00`. Dates without an offset are in UTC.
+pub fn parse_date(s: &str) -> Option<ArchiveTimestamp<Milliseconds>> {
+    let s = s.trim();
+    if let Some(timestamp) = ArchiveTimestamp::parse_iso8601(s) {
+        return Some(timestamp);
+    }
+
+    let (date, time) = s.split_once([' ', 'T']).unwrap_or((s, "00:00:00"));
+    let parts: Vec<&str> = date.split('/').collect();
+    let (year, month, day) = match parts[..] {
+        [year, month, day] if year.len() == 4 => (year, month, day),
+        [month, day, year] if year.len() == 4 => (year, month, day),
+        _ => return None,
+    };
+    let month: u32 = month.parse().ok()?;
+    let day: u32 = day.parse().ok()?;
+
+    ArchiveTimestamp::parse_iso8601(&format!("{}-{:02}-{:02} {}", year, month, day, time))
+}
+
+/// Which sections an entry of the `Activity` section belongs to
+fn activity_section(key: &str) -> Sections {
+    match key {
+        "Video Browsing History" => Sections::WATCH_HISTORY,
+        "Like List" => Sections::LIKES,
+        "Favorite Videos" => Sections::SAVED,
+        "Follower List" | "Following List" => Sections::FOLLOWERS_AND_FOLLOWING,
+        "Search History" => Sections::SEARCH_HISTORY,
+        "Login History" => Sections::PROFILE,
+        _ => Sections::ACTIVITY,
+    }
+}
+
+/// Drop empty values so they parse as missing, and rename sections of newer exports.
+/// `depth` is the nesting level of `value`, only the two outer levels hold section names.
+fn normalize(value: &mut Value, depth: usize) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, value| !matches!(value, Value::Null) && !is_empty_string(value));
+            if depth < 2 {
+                for (new, old) in RENAMED_KEYS {
+                    if !map.contains_key(old) {
+                        if let Some(value) = map.remove(new) {
+                            map.insert(old.to_owned(), value);
+                        }
+                    }
+                }
+            }
+            for value in map.values_mut() {
+                normalize(value, depth + 1);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                normalize(item, depth + 1);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_empty_string(value: &Value) -> bool {
+    value
+        .as_str()
+        .is_some_and(|s| s.trim().is_empty() || s == "N/A")
+}
+
+/// Remove and parse a top-level section, if present
+fn take<T: DeserializeOwned>(
+    sections: &mut Map<String, Value>,
+    key: &str,
+    path: &Path,
+) -> Result<Option<T>, ParseError> {
+    sections
+        .remove(key)
+        .map(|value| from_value(value, path))
+        .transpose()
+}
+
+fn from_value<T: DeserializeOwned>(value: Value, path: &Path) -> Result<T, ParseError> {
+    serde_json::from_value(value).map_err(|e| ParseError::Serde(path.to_owned(), e))
+}
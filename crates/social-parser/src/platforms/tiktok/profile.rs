@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// The `Profile` section
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Profile {
+    #[serde(rename = "Profile Information")]
+    pub profile_information: Option<ProfileInformation>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ProfileInformation {
+    #[serde(rename = "ProfileMap")]
+    pub profile_map: Option<ProfileMap>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileMap {
+    pub user_name: Option<String>,
+    pub bio_description: Option<String>,
+    pub birth_date: Option<String>,
+    pub email_address: Option<String>,
+    pub telephone_number: Option<String>,
+    /// URL of the profile photo
+    pub profile_photo: Option<String>,
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+use super::parse_date;
+use crate::common::{ArchiveTimestamp, Milliseconds};
+
+/// Email and blogs of the account
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Account {
+    pub email: Option<String>,
+    pub blogs: Vec<Blog>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Blog {
+    pub name: String,
+    pub url: Option<String>,
+    pub title: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct LikedPost {
+    /// Blog the liked post is from
+    pub blog_name: Option<String>,
+    pub post_id: Option<String>,
+    pub post_url: Option<String>,
+    /// When the post was liked, e.g. `2023-01-31 10:00:00 GMT`
+    pub liked_at: Option<String>,
+}
+
+impl LikedPost {
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        self.liked_at.as_deref().and_then(parse_date)
+    }
+}
@@ -0,0 +1,122 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{
+    read_json, ArchiveTimestamp, Attachment, AttachmentKind, Conversation, ConversationMessage,
+    ItemCount, MessageKind, ParseError, Seconds,
+};
+
+/// `conversations.json`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Conversations(pub Vec<TumblrConversation>);
+
+impl TryFrom<&Path> for Conversations {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<Conversations, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in Conversations: {:?}",
+                path
+            )));
+        }
+
+        read_json(path)
+    }
+}
+
+impl ItemCount for Conversations {
+    fn item_count(&self) -> usize {
+        self.0.iter().map(|c| c.messages.len()).sum()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct TumblrConversation {
+    pub id: String,
+    #[serde(default)]
+    pub participants: Vec<Participant>,
+    #[serde(default)]
+    pub messages: Vec<TumblrMessage>,
+}
+
+impl TumblrConversation {
+    /// Normalize into a conversation, naming senders by blog
+    pub fn to_conversation(&self) -> Conversation {
+        Conversation {
+            id: self.id.clone(),
+            title: None,
+            participants: self.participants.iter().map(|p| p.name.clone()).collect(),
+            messages: self
+                .messages
+                .iter()
+                .map(|message| message.to_message(&self.participants))
+                .collect(),
+        }
+    }
+}
+
+/// Blog taking part in a conversation
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Participant {
+    pub name: String,
+    /// e.g. `t:abc123`
+    pub uuid: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct TumblrMessage {
+    /// `TEXT`, `IMAGE` or `POSTREF` for a shared post
+    #[serde(rename = "type")]
+    pub message_type: String,
+    /// Sender, as a participant's uuid or name
+    pub participant: Option<String>,
+    /// Unix timestamp in seconds
+    pub timestamp: i64,
+    pub content: Option<String>,
+    /// Address of a shared post or image
+    pub url: Option<String>,
+}
+
+impl TumblrMessage {
+    fn to_message(&self, participants: &[Participant]) -> ConversationMessage {
+        let sender = self
+            .participant
+            .as_deref()
+            .map(|participant| {
+                participants
+                    .iter()
+                    .find(|p| p.uuid.as_deref() == Some(participant))
+                    .map_or(participant, |p| p.name.as_str())
+            })
+            .unwrap_or_default();
+
+        let mut message = ConversationMessage::new(
+            sender.to_owned(),
+            Some(ArchiveTimestamp::<Seconds>::new(self.timestamp).convert()),
+        );
+        message.content = self.content.clone().filter(|content| !content.is_empty());
+        match (self.message_type.as_str(), &self.url) {
+            ("POSTREF", link) => {
+                message.kind = MessageKind::Share {
+                    link: link.clone(),
+                    text: None,
+                };
+            }
+            ("IMAGE", Some(url)) => message.attachments.push(Attachment {
+                kind: AttachmentKind::Photo,
+                uri: url.clone(),
+                name: None,
+                created_at: None,
+            }),
+            _ => {}
+        }
+        message
+    }
+}
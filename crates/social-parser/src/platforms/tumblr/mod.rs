@@ -0,0 +1,395 @@
+//! Tumblr data export, requested from Account > Settings > Blog settings > Export.
+//!
+//! The export folder holds `posts.zip`, which extracts to a page per post under
+//! `posts/html` and the post media under `media`, next to `conversations.json` for
+//! messages and `payload-0.json` for the account's blogs, likes and following. The posts
+//! can be left zipped. Posts are read from their HTML, including the trail of posts a
+//! reblog quotes. Like the other newer platforms, these models accept unknown fields.
+
+pub mod account;
+pub mod conversations;
+pub mod posts;
+
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::{absolute, Path},
+};
+
+use account::{Account, Blog, LikedPost};
+use conversations::Conversations;
+use posts::TumblrPost;
+use serde::{Deserialize, Serialize};
+
+use crate::common::{
+    bundle::{for_each_zip_file, is_zip},
+    AccountInfo, Archive, ArchiveTimestamp, Attachment, AttachmentKind, Conversation, Dataset,
+    Follow, FollowDirection, ItemCount, Like, LoadOptions, Milliseconds, ParseError, Post,
+    Sections,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct TumblrArchive {
+    pub account: Option<Account>,
+    pub posts: Option<Vec<TumblrPost>>,
+    pub likes: Option<Vec<LikedPost>>,
+    /// Blogs the account follows
+    pub following: Option<Vec<Blog>>,
+    pub conversations: Option<Conversations>,
+    /// Paths of the media files, relative to the export folder or to `posts.zip`, e.g.
+    /// `media/123456789_0.jpg`
+    pub media: Option<Vec<String>>,
+}
+
+impl Archive for TumblrArchive {
+    /// Load from the export folder, only parsing the sections selected in `options`
+    fn load<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Expected a directory, found: {:?}",
+                absolute(path)
+            )));
+        }
+
+        options.enter_section("TumblrArchive", path)?;
+
+        let root = path;
+        let mut archive = TumblrArchive::default();
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+            let name = entry.file_name();
+
+            match name.to_str() {
+                Some("posts" | "media") if path.is_dir() => {
+                    archive.load_dir(&path, root, options)?;
+                }
+                Some("posts.zip") if is_zip(&path) => {
+                    options.enter_section("posts", &path)?;
+                    for_each_zip_file(&path, |entry_path, reader, bytes| {
+                        archive.load_post_file(&path, entry_path, reader, bytes, options)
+                    })?;
+                }
+                Some("conversations.json") => {
+                    if options.should_load(Sections::MESSAGES, &path) {
+                        archive.conversations = Some(options.load_file(&path)?);
+                    }
+                }
+                Some(name) if name.starts_with("payload-") && name.ends_with(".json") => {
+                    if options.should_load(
+                        Sections::PROFILE | Sections::LIKES | Sections::FOLLOWERS_AND_FOLLOWING,
+                        &path,
+                    ) {
+                        archive.load_payload(&path, options)?;
+                    }
+                }
+                _ => {
+                    options.warn(&path, "Skipped unsupported file");
+                }
+            }
+        }
+
+        Ok(archive)
+    }
+
+    fn to_dataset(&self) -> Dataset {
+        Dataset {
+            account: AccountInfo {
+                username: self.blog_name().map(str::to_owned),
+                display_name: None,
+                email: self
+                    .account
+                    .as_ref()
+                    .and_then(|account| account.email.clone()),
+            },
+            conversations: self.conversations(),
+            posts: self.normalized_posts(),
+            follows: self
+                .following
+                .iter()
+                .flatten()
+                .map(|blog| Follow {
+                    direction: FollowDirection::Following,
+                    name: blog.name.clone(),
+                    url: blog.url.clone(),
+                    timestamp: None,
+                })
+                .collect(),
+            likes: self
+                .likes
+                .iter()
+                .flatten()
+                .map(|like| Like {
+                    target: like.post_url.clone().or_else(|| like.post_id.clone()),
+                    title: like.blog_name.clone(),
+                    reaction: None,
+                    timestamp: like.timestamp(),
+                })
+                .collect(),
+            ..Dataset::new("tumblr")
+        }
+    }
+}
+
+impl TumblrArchive {
+    /// Load every post page and media file under `dir`
+    fn load_dir(
+        &mut self,
+        dir: &Path,
+        root: &Path,
+        options: &LoadOptions,
+    ) -> Result<(), ParseError> {
+        for entry in dir.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if path.is_dir() {
+                self.load_dir(&path, root, options)?;
+            } else if path.is_file() {
+                let bytes = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                self.load_post_file(root, &path, File::open(&path)?, bytes, options)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parse a post page, or record a media file, named relative to `root`
+    fn load_post_file<R: Read>(
+        &mut self,
+        root: &Path,
+        path: &Path,
+        mut reader: R,
+        bytes: u64,
+        options: &LoadOptions,
+    ) -> Result<(), ParseError> {
+        let Ok(relative) = path.strip_prefix(root) else {
+            return Ok(());
+        };
+        let mut components = relative.components();
+        let folder = components.next().and_then(|c| c.as_os_str().to_str());
+        let name = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+
+        match folder {
+            Some("posts") => {
+                let id = name.strip_suffix(".html").unwrap_or_default();
+                if id.is_empty() || !id.bytes().all(|b| b.is_ascii_digit()) {
+                    options.warn(path, "Skipped unsupported file");
+                } else if options.should_load(Sections::POSTS, path) {
+                    let post = options.load_with(path, bytes, || {
+                        let mut page = String::new();
+                        reader.read_to_string(&mut page)?;
+                        Ok(TumblrPost::parse(id, &page))
+                    })?;
+                    self.posts.get_or_insert_with(Vec::new).push(post);
+                }
+            }
+            Some("media") => {
+                if options.should_load(Sections::MEDIA, path) {
+                    self.media
+                        .get_or_insert_with(Vec::new)
+                        .push(format!("media/{}", name));
+                }
+            }
+            _ => {
+                options.warn(path, "Skipped unsupported file");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read `payload-0.json`, which is an object or a list of objects, each with the
+    /// account data at the top or under `data`
+    fn load_payload(&mut self, path: &Path, options: &LoadOptions) -> Result<(), ParseError> {
+        let payload: Payload =
+            options.load_with(path, path.metadata().map(|m| m.len()).unwrap_or(0), || {
+                let value: serde_json::Value =
+                    serde_json::from_reader(BufReader::new(File::open(path)?))
+                        .map_err(|e| ParseError::Serde(path.to_owned(), e))?;
+                let values = match value {
+                    serde_json::Value::Array(values) => values,
+                    value => vec![value],
+                };
+
+                let mut payload = Payload::default();
+                for mut value in values {
+                    if let Some(data) = value.get_mut("data") {
+                        value = data.take();
+                    }
+                    let part: Payload = serde_json::from_value(value)
+                        .map_err(|e| ParseError::Serde(path.to_owned(), e))?;
+                    payload.email = payload.email.or(part.email);
+                    payload.blogs.extend(part.blogs);
+                    payload.likes.extend(part.likes);
+                    payload.following.extend(part.following);
+                }
+                Ok(payload)
+            })?;
+
+        if options.includes(Sections::PROFILE) {
+            self.account = Some(Account {
+                email: payload.email,
+                blogs: payload.blogs,
+            });
+        }
+        if options.includes(Sections::LIKES) {
+            self.likes = Some(payload.likes);
+        }
+        if options.includes(Sections::FOLLOWERS_AND_FOLLOWING) {
+            self.following = Some(payload.following);
+        }
+
+        Ok(())
+    }
+
+    /// Name of the account's primary blog
+    pub fn blog_name(&self) -> Option<&str> {
+        let blog = self.account.as_ref()?.blogs.first()?;
+        Some(&blog.name)
+    }
+
+    /// Posts of the account, normalized. Reblogs link to the post they reblog, and media
+    /// resolves to the files in `media` when they were exported.
+    pub fn normalized_posts(&self) -> Vec<Post> {
+        self.posts
+            .iter()
+            .flatten()
+            .map(|post| Post {
+                id: Some(post.id.clone()),
+                author: self.blog_name().map(str::to_owned),
+                timestamp: post.timestamp(),
+                title: post.title.clone(),
+                text: Some(post.text.clone()).filter(|text| !text.is_empty()),
+                url: self
+                    .blog_name()
+                    .map(|blog| format!("https://{}.tumblr.com/post/{}", blog, post.id)),
+                link: post.trail.last().and_then(|item| item.url.clone()),
+                community: None,
+                attachments: self.attachments(post),
+            })
+            .collect()
+    }
+
+    /// Media of a post: the files its page refers to, then any other exported file named
+    /// after the post, e.g. `media/123456789_0.jpg` for photo posts
+    fn attachments(&self, post: &TumblrPost) -> Vec<Attachment> {
+        let media = self.media.as_deref().unwrap_or_default();
+        let mut uris: Vec<String> = post
+            .sources
+            .iter()
+            .map(|source| {
+                let name = source.rsplit('/').next().unwrap_or(source);
+                media
+                    .iter()
+                    .find(|path| path.rsplit('/').next() == Some(name))
+                    .unwrap_or(source)
+                    .clone()
+            })
+            .collect();
+        for path in media {
+            let stem = path.rsplit('/').next().unwrap_or(path);
+            let stem = stem.split_once('.').map_or(stem, |(stem, _)| stem);
+            let belongs = stem == post.id
+                || stem
+                    .strip_prefix(&post.id)
+                    .is_some_and(|rest| rest.starts_with('_'));
+            if belongs && !uris.contains(path) {
+                uris.push(path.clone());
+            }
+        }
+
+        uris.into_iter()
+            .map(|uri| Attachment {
+                kind: AttachmentKind::from_uri(&uri),
+                uri,
+                name: None,
+                created_at: None,
+            })
+            .collect()
+    }
+
+    /// Conversations normalized, with senders named by blog
+    pub fn conversations(&self) -> Vec<Conversation> {
+        self.conversations
+            .iter()
+            .flat_map(|conversations| &conversations.0)
+            .map(|conversation| conversation.to_conversation())
+            .collect()
+    }
+}
+
+impl TryFrom<&Path> for TumblrArchive {
+    type Error = ParseError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        TumblrArchive::load(path, &LoadOptions::default())
+    }
+}
+
+/// Contents of `payload-0.json`
+#[derive(Deserialize, Default)]
+struct Payload {
+    email: Option<String>,
+    #[serde(default)]
+    blogs: Vec<Blog>,
+    #[serde(default)]
+    likes: Vec<LikedPost>,
+    #[serde(default)]
+    following: Vec<Blog>,
+}
+
+impl ItemCount for Payload {
+    fn item_count(&self) -> usize {
+        self.blogs.len() + self.likes.len() + self.following.len()
+    }
+}
+
+/// Parse the dates Tumblr writes, e.g. `May 7th, 2014 7:30pm` on post pages or
+/// `2023-01-31 10:00:00 GMT`. Dates are read as UTC.
+pub fn parse_date(s: &str) -> Option<ArchiveTimestamp<Milliseconds>> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let s = s.trim();
+    let iso = s.strip_suffix(" GMT").unwrap_or(s);
+    if let Some(timestamp) = ArchiveTimestamp::parse_iso8601(iso) {
+        return Some(timestamp);
+    }
+
+    // `May 7th, 2014 7:30pm`
+    let mut parts = s.split_whitespace();
+    let month = parts.next()?;
+    let month = MONTHS.iter().position(|m| month.starts_with(m))? + 1;
+    let day: u32 = parts
+        .next()?
+        .trim_end_matches(',')
+        .trim_end_matches(char::is_alphabetic)
+        .parse()
+        .ok()?;
+    let year: u32 = parts.next()?.parse().ok()?;
+
+    let time = parts.next().unwrap_or("12:00am").to_ascii_lowercase();
+    let (clock, pm) = match time.strip_suffix("pm") {
+        Some(clock) => (clock, true),
+        None => (time.strip_suffix("am").unwrap_or(&time), false),
+    };
+    let (hours, minutes) = clock.split_once(':')?;
+    let mut hours: u32 = hours.parse().ok()?;
+    if pm && hours < 12 {
+        hours += 12;
+    } else if !pm && hours == 12 && time.ends_with("am") {
+        hours = 0;
+    }
+
+    ArchiveTimestamp::parse_iso8601(&format!(
+        "{}-{:02}-{:02}T{:02}:{}",
+        year, month, day, hours, minutes
+    ))
+}
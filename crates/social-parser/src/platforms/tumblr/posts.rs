@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+
+use super::parse_date;
+use crate::common::{html, ArchiveTimestamp, ItemCount, Milliseconds};
+
+/// Post read from its page in `posts/html`, e.g. `posts/html/123456789.html`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct TumblrPost {
+    /// Post id, from the name of the page
+    pub id: String,
+    /// e.g. `May 7th, 2014 7:30pm`
+    pub date: Option<String>,
+    pub title: Option<String>,
+    /// What the blog added to the post, as plain text. Empty for plain reblogs.
+    pub text: String,
+    /// Posts this one reblogs, oldest first
+    pub trail: Vec<TrailItem>,
+    pub tags: Vec<String>,
+    /// Sources of the images, videos and audio in the post, as written in the page
+    pub sources: Vec<String>,
+}
+
+impl TumblrPost {
+    /// Parse the page of a post
+    pub fn parse(id: &str, page: &str) -> TumblrPost {
+        let body = html::inner(page, "<body", "</body>").unwrap_or(page);
+        let (content, footer) = match body.find("<div id=\"footer\"") {
+            Some(start) => body.split_at(start),
+            None => (body, ""),
+        };
+
+        let date = html::inner(footer, "id=\"timestamp\"", "</span>")
+            .map(html::text)
+            .filter(|date| !date.is_empty());
+        let tags = footer
+            .split("<span class=\"tag\"")
+            .skip(1)
+            .filter_map(|tag| html::inner(tag, "", "</span>"))
+            .map(html::text)
+            .filter(|tag| !tag.is_empty())
+            .collect();
+
+        let content = content.trim();
+        let (title, content) = match content
+            .strip_prefix("<h1>")
+            .or_else(|| content.strip_prefix("<h2>"))
+            .and_then(|rest| rest.split_once("</h"))
+        {
+            Some((title, rest)) => (
+                Some(html::text(title)).filter(|title| !title.is_empty()),
+                rest.split_once('>').map_or("", |(_, rest)| rest),
+            ),
+            None => (None, content),
+        };
+
+        let (trail, own) = split_trail(content);
+        TumblrPost {
+            id: id.to_owned(),
+            date,
+            title,
+            text: html::text_with_breaks(own),
+            trail,
+            tags,
+            sources: html::sources(content),
+        }
+    }
+
+    pub fn timestamp(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        self.date.as_deref().and_then(parse_date)
+    }
+
+    /// Whether the post reblogs another one
+    pub fn is_reblog(&self) -> bool {
+        !self.trail.is_empty()
+    }
+}
+
+impl ItemCount for TumblrPost {
+    fn item_count(&self) -> usize {
+        1
+    }
+}
+
+/// One post of a reblog trail
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct TrailItem {
+    /// Name of the blog that wrote this part
+    pub blog: String,
+    /// Address of the reblogged post
+    pub url: Option<String>,
+    pub text: String,
+}
+
+/// Split a reblog into its trail and what the reblogging blog added. Reblogs quote their
+/// source as `<p><a class="tumblr_blog" href="...">name</a>:</p><blockquote>...</blockquote>`,
+/// and the quote may itself be a reblog.
+fn split_trail(content: &str) -> (Vec<TrailItem>, &str) {
+    let mut trail = vec![];
+    let trimmed = content.trim_start();
+
+    let Some(rest) = trimmed
+        .strip_prefix("<p>")
+        .filter(|rest| rest.starts_with("<a class=\"tumblr_blog\""))
+    else {
+        return (trail, content);
+    };
+    let Some((link, rest)) = rest.split_once("</a>") else {
+        return (trail, content);
+    };
+    let Some(quote_start) = rest.find("<blockquote") else {
+        return (trail, content);
+    };
+    let quote = &rest[quote_start..];
+    let Some(open_end) = quote.find('>') else {
+        return (trail, content);
+    };
+    let Some(quote_end) = closing_blockquote(&quote[open_end + 1..]) else {
+        return (trail, content);
+    };
+    let inner = &quote[open_end + 1..open_end + 1 + quote_end];
+    let own = &quote[open_end + 1 + quote_end + "</blockquote>".len()..];
+
+    let url = link
+        .split_once("href=\"")
+        .and_then(|(_, rest)| rest.split_once('"'))
+        .map(|(href, _)| html::decode_entities(href));
+    let (earlier, quoted) = split_trail(inner);
+    trail.extend(earlier);
+    trail.push(TrailItem {
+        blog: html::text(link),
+        url,
+        text: html::text_with_breaks(quoted),
+    });
+
+    (trail, own)
+}
+
+/// Offset of the `</blockquote>` closing a blockquote whose content starts `html`,
+/// skipping nested ones
+fn closing_blockquote(html: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut offset = 0;
+    loop {
+        let rest = &html[offset..];
+        let open = rest.find("<blockquote");
+        let close = rest.find("</blockquote>")?;
+        match open {
+            Some(open) if open < close => {
+                depth += 1;
+                offset += open + 1;
+            }
+            _ if depth == 0 => return Some(offset + close),
+            _ => {
+                depth -= 1;
+                offset += close + 1;
+            }
+        }
+    }
+}
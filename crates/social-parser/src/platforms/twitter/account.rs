@@ -0,0 +1,43 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::read_ytd_items;
+use crate::common::{ItemCount, ParseError};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct Account {
+    pub account_id: String,
+    pub username: String,
+    pub account_display_name: Option<String>,
+    pub email: Option<String>,
+    pub created_via: Option<String>,
+    /// ISO 8601
+    pub created_at: Option<String>,
+}
+
+impl TryFrom<&Path> for Account {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<Account, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in Account: {:?}",
+                path
+            )));
+        }
+
+        read_ytd_items(path)?.into_iter().next().ok_or_else(|| {
+            ParseError::UnexpectedFormat(format!("Found empty account file: {:?}", path))
+        })
+    }
+}
+
+impl ItemCount for Account {
+    fn item_count(&self) -> usize {
+        1
+    }
+}
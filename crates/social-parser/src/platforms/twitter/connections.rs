@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{read_ytd_items, Parts};
+use crate::common::{ItemCount, ParseError};
+
+/// Followers, following, blocked or muted accounts
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct AccountList(pub Vec<AccountRef>);
+
+impl TryFrom<&Path> for AccountList {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<AccountList, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in AccountList: {:?}",
+                path
+            )));
+        }
+
+        read_ytd_items(path).map(AccountList)
+    }
+}
+
+impl ItemCount for AccountList {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl Parts for AccountList {
+    fn merge(&mut self, part: Self) {
+        self.0.extend(part.0);
+    }
+}
+
+/// Other account, identified by id only. The archive doesn't include handles.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct AccountRef {
+    pub account_id: String,
+    /// e.g. `https://twitter.com/intent/user?user_id=12`
+    pub user_link: Option<String>,
+}
+
+/// Lists created, joined or subscribed to
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Lists(pub Vec<ListInfo>);
+
+impl TryFrom<&Path> for Lists {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<Lists, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in Lists: {:?}",
+                path
+            )));
+        }
+
+        read_ytd_items(path).map(Lists)
+    }
+}
+
+impl ItemCount for Lists {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl Parts for Lists {
+    fn merge(&mut self, part: Self) {
+        self.0.extend(part.0);
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ListInfo {
+    /// e.g. `https://twitter.com/jane/lists/rust`
+    pub url: String,
+}
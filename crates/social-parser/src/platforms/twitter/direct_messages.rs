@@ -0,0 +1,267 @@
+use std::{collections::BTreeSet, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use super::{media::MediaFolder, read_ytd_items, Parts};
+use crate::common::{
+    ArchiveTimestamp, Attachment, AttachmentKind, Conversation, ConversationMessage, ItemCount,
+    MessageKind, MessageReaction, ParseError,
+};
+
+/// Conversations from `direct-messages.js` or `direct-messages-group.js`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct DirectMessages(pub Vec<DmConversation>);
+
+impl TryFrom<&Path> for DirectMessages {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<DirectMessages, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in DirectMessages: {:?}",
+                path
+            )));
+        }
+
+        read_ytd_items(path).map(DirectMessages)
+    }
+}
+
+impl ItemCount for DirectMessages {
+    fn item_count(&self) -> usize {
+        self.0
+            .iter()
+            .map(|conversation| conversation.messages.len())
+            .sum()
+    }
+}
+
+impl Parts for DirectMessages {
+    fn merge(&mut self, part: Self) {
+        self.0.extend(part.0);
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct DmConversation {
+    /// `<user id>-<user id>` for one-to-one conversations
+    pub conversation_id: String,
+    /// Newest first
+    pub messages: Vec<DmEvent>,
+}
+
+impl DmConversation {
+    /// Normalize into a conversation, using the downloaded files in `media` for attachments
+    /// when available and the remote URLs otherwise. Participants are user ids.
+    pub fn to_conversation(&self, media: Option<&MediaFolder>) -> Conversation {
+        let mut participants = BTreeSet::new();
+        let mut title = None;
+        let mut messages = vec![];
+
+        if !self.conversation_id.contains(char::is_alphabetic) {
+            participants.extend(self.conversation_id.split('-').map(str::to_owned));
+        }
+
+        for event in &self.messages {
+            match event {
+                DmEvent::MessageCreate(message) => {
+                    participants.insert(message.sender_id.clone());
+                    participants.extend(message.recipient_id.clone());
+                    messages.push(message.to_message(media));
+                }
+                DmEvent::ParticipantsJoin(event) | DmEvent::ParticipantsLeave(event) => {
+                    participants.extend(event.user_ids.iter().cloned());
+                    messages.push(event.to_message());
+                }
+                DmEvent::JoinConversation(event) => {
+                    participants.extend(event.participants_snapshot.iter().cloned());
+                    messages.push(event.to_message());
+                }
+                DmEvent::ConversationNameUpdate(event) => {
+                    // Events are newest first, so the first rename is the current name
+                    title.get_or_insert_with(|| event.name.clone());
+                    messages.push(event.to_message());
+                }
+            }
+        }
+        messages.sort_by_key(|message| message.timestamp);
+
+        Conversation {
+            id: self.conversation_id.clone(),
+            title,
+            participants: participants.into_iter().collect(),
+            messages,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub enum DmEvent {
+    MessageCreate(MessageCreate),
+    /// Group conversations only
+    JoinConversation(JoinConversation),
+    ParticipantsJoin(ParticipantsChange),
+    ParticipantsLeave(ParticipantsChange),
+    ConversationNameUpdate(ConversationNameUpdate),
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct MessageCreate {
+    pub id: String,
+    pub sender_id: String,
+    /// Missing in group conversations
+    pub recipient_id: Option<String>,
+    pub text: String,
+    /// ISO 8601
+    pub created_at: String,
+    #[serde(default)]
+    pub reactions: Vec<DmReaction>,
+    #[serde(default)]
+    pub urls: Vec<DmUrl>,
+    #[serde(default)]
+    pub media_urls: Vec<String>,
+}
+
+impl MessageCreate {
+    fn to_message(&self, media: Option<&MediaFolder>) -> ConversationMessage {
+        let timestamp = ArchiveTimestamp::parse_iso8601(&self.created_at);
+        let mut message = ConversationMessage::new(self.sender_id.clone(), timestamp);
+        message.id = Some(self.id.clone());
+        message.content = Some(self.text.clone());
+
+        let local: Vec<Attachment> = media
+            .into_iter()
+            .flat_map(|media| media.files_for(&self.id))
+            .map(|file| Attachment {
+                kind: file.kind,
+                uri: file.uri.clone(),
+                name: file.uri.rsplit('/').next().map(str::to_owned),
+                created_at: None,
+            })
+            .collect();
+        message.attachments = if local.is_empty() {
+            self.media_urls
+                .iter()
+                .map(|url| Attachment {
+                    kind: AttachmentKind::from_uri(url),
+                    uri: url.clone(),
+                    name: None,
+                    created_at: None,
+                })
+                .collect()
+        } else {
+            local
+        };
+
+        message.reactions = self
+            .reactions
+            .iter()
+            .map(|reaction| MessageReaction {
+                reaction: reaction.reaction_key.clone(),
+                actor: reaction.sender_id.clone(),
+                timestamp: ArchiveTimestamp::parse_iso8601(&reaction.created_at),
+            })
+            .collect();
+
+        if let Some(url) = self.urls.first() {
+            message.kind = MessageKind::Share {
+                link: url.expanded.clone().or_else(|| Some(url.url.clone())),
+                text: None,
+            };
+        }
+
+        message
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct DmReaction {
+    pub sender_id: String,
+    /// e.g. `like`, `funny` or `sad`
+    pub reaction_key: String,
+    pub event_id: Option<String>,
+    /// ISO 8601
+    pub created_at: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct DmUrl {
+    pub url: String,
+    pub expanded: Option<String>,
+    pub display: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct JoinConversation {
+    pub initiating_user_id: String,
+    #[serde(default)]
+    pub participants_snapshot: Vec<String>,
+    /// ISO 8601
+    pub created_at: String,
+}
+
+impl JoinConversation {
+    fn to_message(&self) -> ConversationMessage {
+        system_message(&self.initiating_user_id, &self.created_at)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct ParticipantsChange {
+    pub initiating_user_id: Option<String>,
+    #[serde(default)]
+    pub user_ids: Vec<String>,
+    /// ISO 8601
+    pub created_at: String,
+}
+
+impl ParticipantsChange {
+    fn to_message(&self) -> ConversationMessage {
+        let sender = self
+            .initiating_user_id
+            .as_deref()
+            .or(self.user_ids.first().map(String::as_str))
+            .unwrap_or_default();
+        system_message(sender, &self.created_at)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationNameUpdate {
+    pub initiating_user_id: String,
+    pub name: String,
+    /// ISO 8601
+    pub created_at: String,
+}
+
+impl ConversationNameUpdate {
+    fn to_message(&self) -> ConversationMessage {
+        let mut message = system_message(&self.initiating_user_id, &self.created_at);
+        message.content = Some(self.name.clone());
+        message
+    }
+}
+
+fn system_message(sender: &str, created_at: &str) -> ConversationMessage {
+    let timestamp = ArchiveTimestamp::parse_iso8601(created_at);
+    let mut message = ConversationMessage::new(sender.to_owned(), timestamp);
+    message.kind = MessageKind::System;
+    message
+}
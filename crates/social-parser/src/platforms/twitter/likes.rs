@@ -0,0 +1,48 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{read_ytd_items, Parts};
+use crate::common::{ItemCount, ParseError};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Likes(pub Vec<Like>);
+
+impl TryFrom<&Path> for Likes {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<Likes, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in Likes: {:?}",
+                path
+            )));
+        }
+
+        read_ytd_items(path).map(Likes)
+    }
+}
+
+impl ItemCount for Likes {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl Parts for Likes {
+    fn merge(&mut self, part: Self) {
+        self.0.extend(part.0);
+    }
+}
+
+/// Liked tweet. The text is missing for tweets that were deleted or made private.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct Like {
+    pub tweet_id: String,
+    pub full_text: Option<String>,
+    pub expanded_url: Option<String>,
+}
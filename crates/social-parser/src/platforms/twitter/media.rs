@@ -0,0 +1,72 @@
+use std::{collections::HashMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{AttachmentKind, LoadOptions, ParseError};
+
+/// Media files downloaded into a folder like `data/tweets_media`. Files are named
+/// `<tweet or message id>-<media name>`, and grouped by that id.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct MediaFolder(pub HashMap<String, Vec<MediaFile>>);
+
+impl MediaFolder {
+    /// List the files in a directory. `prefix` is prepended to each file's path,
+    /// e.g. `data/tweets_media`, to make them relative to the archive root.
+    /// Assumes path is a directory.
+    pub fn load(
+        path: &Path,
+        prefix: &str,
+        options: &LoadOptions,
+    ) -> Result<MediaFolder, ParseError> {
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-directory in MediaFolder: {:?}",
+                path
+            )));
+        }
+
+        options.enter_section("MediaFolder", path)?;
+
+        let mut files: HashMap<String, Vec<MediaFile>> = HashMap::new();
+        for entry in path.read_dir()? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let Some((owner_id, _)) = name.split_once('-') else {
+                options.warn(&path, "Skipped media file without an id prefix");
+                continue;
+            };
+
+            files
+                .entry(owner_id.to_owned())
+                .or_default()
+                .push(MediaFile {
+                    owner_id: owner_id.to_owned(),
+                    uri: format!("{}/{}", prefix, name),
+                    kind: AttachmentKind::from_uri(name),
+                });
+        }
+        for owned in files.values_mut() {
+            owned.sort_by(|a, b| a.uri.cmp(&b.uri));
+        }
+
+        Ok(MediaFolder(files))
+    }
+
+    /// Files belonging to a tweet or message
+    pub fn files_for(&self, owner_id: &str) -> &[MediaFile] {
+        self.0.get(owner_id).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct MediaFile {
+    /// Id of the tweet or direct message the file was attached to
+    pub owner_id: String,
+    /// Path relative to the archive root
+    pub uri: String,
+    pub kind: AttachmentKind,
+}
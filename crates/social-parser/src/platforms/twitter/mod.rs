@@ -0,0 +1,472 @@
+//! Twitter/X archive, requested from Settings > Your account > Download an archive.
+//!
+//! Data files live in `data/*.js` and are JavaScript assigning JSON to a global, e.g.
+//! `window.YTD.tweets.part0 = [...]`. Large files are split into `tweets-part1.js`,
+//! `tweets-part2.js`, and so on, which are merged when loading. Like the other newer
+//! platforms, these models accept unknown fields and unknown files are reported as warnings.
+
+pub mod account;
+pub mod connections;
+pub mod direct_messages;
+pub mod likes;
+pub mod media;
+pub mod tweets;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{absolute, Path, PathBuf},
+};
+
+use account::Account;
+use connections::{AccountList, Lists};
+use direct_messages::DirectMessages;
+use likes::Likes;
+use media::MediaFolder;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tweets::Tweets;
+
+use crate::common::{
+    self, AccountInfo, Archive, ArchiveTimestamp, Attachment, Comment, Conversation, Dataset,
+    Follow, FollowDirection, ItemCount, LoadOptions, Milliseconds, ParseError, Post, Sections,
+    StreamingArchive,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct TwitterArchive {
+    pub account: Option<Account>,
+    pub tweets: Option<Tweets>,
+    pub direct_messages: Option<DirectMessages>,
+    pub group_direct_messages: Option<DirectMessages>,
+    pub likes: Option<Likes>,
+    pub followers: Option<AccountList>,
+    pub following: Option<AccountList>,
+    pub blocks: Option<AccountList>,
+    pub mutes: Option<AccountList>,
+    pub lists_created: Option<Lists>,
+    pub lists_member: Option<Lists>,
+    pub lists_subscribed: Option<Lists>,
+    /// Downloaded media keyed by folder name, e.g. `tweets_media`
+    pub media: Option<HashMap<String, MediaFolder>>,
+}
+
+impl Archive for TwitterArchive {
+    /// Load from the archive root or its `data` directory, only parsing the sections
+    /// selected in `options`
+    fn load<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        if !path.is_dir() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Expected a directory, found: {:?}",
+                absolute(path)
+            )));
+        }
+
+        options.enter_section("TwitterArchive", path)?;
+
+        let (data, prefix) = data_dir(path);
+
+        // Group multipart files by their base name, e.g. `tweets-part1.js` under `tweets`
+        let mut files: BTreeMap<String, Vec<(u32, PathBuf)>> = BTreeMap::new();
+        let mut archive = TwitterArchive::default();
+
+        for entry in data.read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+            let Some(name) = path.file_name().and_then(|s| s.to_str()) else {
+                continue;
+            };
+
+            if path.is_dir() {
+                if name.ends_with("_media") {
+                    if options.should_load(Sections::MEDIA, &path) {
+                        let folder =
+                            MediaFolder::load(&path, &format!("{}{}", prefix, name), options)?;
+                        archive
+                            .media
+                            .get_or_insert_with(Default::default)
+                            .insert(name.to_owned(), folder);
+                    }
+                } else {
+                    options.skip(&path, "Skipped unsupported section");
+                }
+                continue;
+            }
+
+            let Some(stem) = name.strip_suffix(".js") else {
+                options.warn(&path, "Skipped unsupported file");
+                continue;
+            };
+            let (base, part) = split_part(stem);
+            files.entry(base.to_owned()).or_default().push((part, path));
+        }
+
+        for (name, mut parts) in files {
+            parts.sort();
+            let paths: Vec<PathBuf> = parts.into_iter().map(|(_, path)| path).collect();
+            let path = &paths[0];
+
+            match name.as_str() {
+                "account" => {
+                    if options.should_load(Sections::PROFILE, path) {
+                        archive.account = Some(options.load_file(path)?);
+                    }
+                }
+                "tweets" | "tweet" => {
+                    if options.should_load(Sections::POSTS, path) {
+                        archive.tweets = Some(load_parts(&paths, options)?);
+                    }
+                }
+                "direct-messages" => {
+                    if options.should_load(Sections::MESSAGES, path) {
+                        archive.direct_messages = Some(load_parts(&paths, options)?);
+                    }
+                }
+                "direct-messages-group" => {
+                    if options.should_load(Sections::MESSAGES, path) {
+                        archive.group_direct_messages = Some(load_parts(&paths, options)?);
+                    }
+                }
+                "like" => {
+                    if options.should_load(Sections::LIKES, path) {
+                        archive.likes = Some(load_parts(&paths, options)?);
+                    }
+                }
+                "follower" => {
+                    if options.should_load(Sections::FOLLOWERS_AND_FOLLOWING, path) {
+                        archive.followers = Some(load_parts(&paths, options)?);
+                    }
+                }
+                "following" => {
+                    if options.should_load(Sections::FOLLOWERS_AND_FOLLOWING, path) {
+                        archive.following = Some(load_parts(&paths, options)?);
+                    }
+                }
+                "block" => {
+                    if options.should_load(Sections::CONTACTS, path) {
+                        archive.blocks = Some(load_parts(&paths, options)?);
+                    }
+                }
+                "mute" => {
+                    if options.should_load(Sections::CONTACTS, path) {
+                        archive.mutes = Some(load_parts(&paths, options)?);
+                    }
+                }
+                "lists-created" => {
+                    if options.should_load(Sections::GROUPS, path) {
+                        archive.lists_created = Some(load_parts(&paths, options)?);
+                    }
+                }
+                "lists-member" => {
+                    if options.should_load(Sections::GROUPS, path) {
+                        archive.lists_member = Some(load_parts(&paths, options)?);
+                    }
+                }
+                "lists-subscribed" => {
+                    if options.should_load(Sections::GROUPS, path) {
+                        archive.lists_subscribed = Some(load_parts(&paths, options)?);
+                    }
+                }
+                _ => {
+                    for path in &paths {
+                        options.skip(path, "Skipped unsupported file");
+                    }
+                }
+            }
+        }
+
+        Ok(archive)
+    }
+
+    fn to_dataset(&self) -> Dataset {
+        let followers = self
+            .followers
+            .iter()
+            .flat_map(|list| &list.0)
+            .map(|account| (FollowDirection::Follower, account));
+        let following = self
+            .following
+            .iter()
+            .flat_map(|list| &list.0)
+            .map(|account| (FollowDirection::Following, account));
+
+        Dataset {
+            account: self
+                .account
+                .as_ref()
+                .map(|account| AccountInfo {
+                    username: Some(account.username.clone()),
+                    display_name: account.account_display_name.clone(),
+                    email: account.email.clone(),
+                })
+                .unwrap_or_default(),
+            conversations: self.conversations(),
+            posts: self.normalized_posts(),
+            comments: self.normalized_comments(),
+            follows: followers
+                .chain(following)
+                .map(|(direction, account)| Follow {
+                    direction,
+                    name: account.account_id.clone(),
+                    url: account.user_link.clone(),
+                    timestamp: None,
+                })
+                .collect(),
+            likes: self
+                .likes
+                .iter()
+                .flat_map(|likes| &likes.0)
+                .map(|like| common::Like {
+                    target: Some(
+                        like.expanded_url
+                            .clone()
+                            .unwrap_or_else(|| like.tweet_id.clone()),
+                    ),
+                    title: like.full_text.clone(),
+                    reaction: None,
+                    timestamp: None,
+                })
+                .collect(),
+            ..Dataset::new("twitter")
+        }
+    }
+}
+
+impl StreamingArchive for TwitterArchive {
+    const PLATFORM: &'static str = "twitter";
+
+    /// Load from the archive root or its `data` directory, passing the conversations in
+    /// each part of the direct message files to `on_conversation` instead of keeping them
+    fn stream_conversations<P, F>(
+        path: P,
+        options: &LoadOptions,
+        mut on_conversation: F,
+    ) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&Path, Conversation) -> Result<(), ParseError>,
+    {
+        let path = path.as_ref();
+        let archive = TwitterArchive::load(
+            path,
+            &options
+                .clone()
+                .with_sections(options.sections - Sections::MESSAGES),
+        )?;
+
+        let (data, _) = data_dir(path);
+        if !options.should_load(Sections::MESSAGES, &data) {
+            return Ok(archive);
+        }
+
+        let mut parts = Vec::new();
+        for entry in data.read_dir()? {
+            let path = entry?.path();
+            let Some(stem) = path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .and_then(|name| name.strip_suffix(".js"))
+            else {
+                continue;
+            };
+            let media = match split_part(stem) {
+                ("direct-messages", part) => ("direct_messages_media", part),
+                ("direct-messages-group", part) => ("direct_messages_group_media", part),
+                _ => continue,
+            };
+            parts.push((media, path));
+        }
+        parts.sort();
+
+        for ((media, _), path) in parts {
+            let messages = options.load_file::<DirectMessages>(&path)?;
+            let media = archive.media_folder(media);
+            for conversation in &messages.0 {
+                on_conversation(&path, conversation.to_conversation(media))?;
+            }
+        }
+
+        Ok(archive)
+    }
+}
+
+impl TwitterArchive {
+    fn username(&self) -> Option<&str> {
+        self.account
+            .as_ref()
+            .map(|account| account.username.as_str())
+    }
+
+    /// Address of a tweet, using the account's username when it was loaded
+    fn tweet_url(&self, tweet_id: &str) -> String {
+        format!(
+            "https://x.com/{}/status/{}",
+            self.username().unwrap_or("i/web"),
+            tweet_id
+        )
+    }
+
+    /// Tweets and retweets posted by the account, normalized. Replies are left out, see
+    /// [`TwitterArchive::normalized_comments`].
+    pub fn normalized_posts(&self) -> Vec<Post> {
+        self.tweets
+            .iter()
+            .flat_map(|tweets| &tweets.0)
+            .filter(|tweet| tweet.in_reply_to_status_id_str.is_none())
+            .map(|tweet| Post {
+                id: Some(tweet.id_str.clone()),
+                author: self.username().map(str::to_owned),
+                timestamp: tweet.created_at(),
+                title: None,
+                text: Some(tweet.full_text.clone()),
+                url: Some(self.tweet_url(&tweet.id_str)),
+                link: tweet
+                    .entities
+                    .iter()
+                    .flat_map(|entities| &entities.urls)
+                    .find_map(|url| url.expanded_url.clone()),
+                community: None,
+                attachments: self.tweet_media(&tweet.id_str),
+            })
+            .collect()
+    }
+
+    /// Replies posted by the account, normalized, with the replied tweet as the post
+    pub fn normalized_comments(&self) -> Vec<Comment> {
+        self.tweets
+            .iter()
+            .flat_map(|tweets| &tweets.0)
+            .filter_map(|tweet| {
+                Some(Comment {
+                    id: Some(tweet.id_str.clone()),
+                    author: self.username().map(str::to_owned),
+                    timestamp: tweet.created_at(),
+                    text: tweet.full_text.clone(),
+                    url: Some(self.tweet_url(&tweet.id_str)),
+                    post: Some(tweet.in_reply_to_status_id_str.clone()?),
+                    parent: None,
+                    community: None,
+                })
+            })
+            .collect()
+    }
+
+    fn media_folder(&self, name: &str) -> Option<&MediaFolder> {
+        self.media.as_ref().and_then(|media| media.get(name))
+    }
+
+    /// Every direct message conversation, including group conversations, with attachments
+    /// pointing at the downloaded media when the media section was loaded
+    pub fn conversations(&self) -> Vec<Conversation> {
+        let direct = self.direct_messages.iter().flat_map(|messages| {
+            let media = self.media_folder("direct_messages_media");
+            messages.0.iter().map(move |c| c.to_conversation(media))
+        });
+        let group = self.group_direct_messages.iter().flat_map(|messages| {
+            let media = self.media_folder("direct_messages_group_media");
+            messages.0.iter().map(move |c| c.to_conversation(media))
+        });
+
+        direct.chain(group).collect()
+    }
+
+    /// Downloaded media files attached to a tweet
+    pub fn tweet_media(&self, tweet_id: &str) -> Vec<Attachment> {
+        self.media_folder("tweets_media")
+            .into_iter()
+            .flat_map(|media| media.files_for(tweet_id))
+            .map(|file| Attachment {
+                kind: file.kind,
+                uri: file.uri.clone(),
+                name: file.uri.rsplit('/').next().map(str::to_owned),
+                created_at: None,
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<&Path> for TwitterArchive {
+    type Error = ParseError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        TwitterArchive::load(path, &LoadOptions::default())
+    }
+}
+
+/// The directory holding the data files, and the prefix that makes media paths relative
+/// to the directory that was passed in
+fn data_dir(path: &Path) -> (PathBuf, &'static str) {
+    match path.join("data") {
+        data if data.is_dir() => (data, "data/"),
+        _ => (path.to_owned(), ""),
+    }
+}
+
+/// Split a multipart file name into its base name and part number, e.g. `tweets-part1`
+/// into `tweets` and 1. Files that aren't split are part 0.
+fn split_part(stem: &str) -> (&str, u32) {
+    match stem.rsplit_once("-part") {
+        Some((base, part)) => match part.parse() {
+            Ok(part) => (base, part),
+            Err(_) => (stem, 0),
+        },
+        None => (stem, 0),
+    }
+}
+
+/// Data split over several files that can be combined into one
+trait Parts {
+    fn merge(&mut self, part: Self);
+}
+
+/// Load each part of a multipart file in order and merge them
+fn load_parts<T>(paths: &[PathBuf], options: &LoadOptions) -> Result<T, ParseError>
+where
+    T: for<'a> TryFrom<&'a Path, Error = ParseError> + ItemCount + Parts + Default,
+{
+    let mut data = T::default();
+    for path in paths {
+        data.merge(options.load_file(path)?);
+    }
+    Ok(data)
+}
+
+/// Read a `.js` data file, skipping the `window.YTD.<name>.partN =` assignment
+pub(crate) fn read_ytd<T: DeserializeOwned>(path: &Path) -> Result<T, ParseError> {
+    let contents = fs::read_to_string(path)?;
+    let Some((_, json)) = contents.split_once('=') else {
+        return Err(ParseError::UnexpectedFormat(format!(
+            "Expected a window.YTD assignment in {:?}",
+            path
+        )));
+    };
+    let json = json.trim().trim_end_matches(';');
+
+    serde_json::from_str(json).map_err(|e| ParseError::Serde(path.to_owned(), e))
+}
+
+/// Read a data file whose items are each wrapped in an object with a single key,
+/// e.g. `[{ "tweet": {...} }, { "tweet": {...} }]`
+pub(crate) fn read_ytd_items<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, ParseError> {
+    let items: Vec<BTreeMap<String, T>> = read_ytd(path)?;
+    Ok(items.into_iter().flat_map(BTreeMap::into_values).collect())
+}
+
+/// Parse the timestamp format used by tweets, e.g. `Wed Oct 10 20:19:24 +0000 2018`
+pub fn parse_created_at(s: &str) -> Option<ArchiveTimestamp<Milliseconds>> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let [_, month, day, time, offset, year] = s.split_whitespace().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    let month = MONTHS.iter().position(|m| *m == month)? + 1;
+    let day: u32 = day.parse().ok()?;
+
+    ArchiveTimestamp::parse_iso8601(&format!(
+        "{}-{:02}-{:02}T{}{}",
+        year, month, day, time, offset
+    ))
+}
@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{parse_created_at, read_ytd_items, Parts};
+use crate::common::{ArchiveTimestamp, ItemCount, Milliseconds, ParseError};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Tweets(pub Vec<Tweet>);
+
+impl TryFrom<&Path> for Tweets {
+    type Error = ParseError;
+
+    /// Load from a file. Assumes path is a file.
+    fn try_from(path: &Path) -> Result<Tweets, Self::Error> {
+        if !path.is_file() {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Found unexpected non-file in Tweets: {:?}",
+                path
+            )));
+        }
+
+        read_ytd_items(path).map(Tweets)
+    }
+}
+
+impl ItemCount for Tweets {
+    fn item_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl Parts for Tweets {
+    fn merge(&mut self, part: Self) {
+        self.0.extend(part.0);
+    }
+}
+
+/// Counts and ids are strings in the archive
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Tweet {
+    pub id_str: String,
+    pub full_text: String,
+    /// e.g. `Wed Oct 10 20:19:24 +0000 2018`
+    pub created_at: String,
+    pub favorite_count: Option<String>,
+    pub retweet_count: Option<String>,
+    pub in_reply_to_status_id_str: Option<String>,
+    pub in_reply_to_user_id_str: Option<String>,
+    pub in_reply_to_screen_name: Option<String>,
+    pub lang: Option<String>,
+    /// HTML link to the client used to post
+    pub source: Option<String>,
+    pub entities: Option<Entities>,
+    pub extended_entities: Option<ExtendedEntities>,
+}
+
+impl Tweet {
+    pub fn created_at(&self) -> Option<ArchiveTimestamp<Milliseconds>> {
+        parse_created_at(&self.created_at)
+    }
+
+    /// Retweets are stored as tweets starting with `RT @user:`
+    pub fn is_retweet(&self) -> bool {
+        self.full_text.starts_with("RT @")
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Entities {
+    #[serde(default)]
+    pub hashtags: Vec<Hashtag>,
+    #[serde(default)]
+    pub user_mentions: Vec<UserMention>,
+    #[serde(default)]
+    pub urls: Vec<Url>,
+    #[serde(default)]
+    pub media: Vec<TweetMedia>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ExtendedEntities {
+    #[serde(default)]
+    pub media: Vec<TweetMedia>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Hashtag {
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct UserMention {
+    pub name: String,
+    pub screen_name: String,
+    pub id_str: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Url {
+    /// Shortened `t.co` link as it appears in the text
+    pub url: String,
+    pub expanded_url: Option<String>,
+    pub display_url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct TweetMedia {
+    pub id_str: String,
+    /// `photo`, `video` or `animated_gif`
+    #[serde(rename = "type")]
+    pub media_type: Option<String>,
+    pub media_url_https: String,
+    /// Shortened `t.co` link as it appears in the text
+    pub url: Option<String>,
+}
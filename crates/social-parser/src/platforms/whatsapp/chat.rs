@@ -0,0 +1,424 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::{
+    ArchiveTimestamp, Attachment, AttachmentKind, Conversation, ConversationMessage, ItemCount,
+    MessageKind, Milliseconds,
+};
+
+/// Left-to-right mark WhatsApp puts before system messages and attachments on iOS
+const LRM: char = '\u{200e}';
+
+/// Whole messages standing in for an attachment left out of the export. Android writes
+/// `<Media omitted>`, iOS names the kind of attachment.
+const OMITTED_MEDIA: &[&str] = &[
+    "<Media omitted>",
+    "image omitted",
+    "video omitted",
+    "audio omitted",
+    "sticker omitted",
+    "GIF omitted",
+    "document omitted",
+    "Contact card omitted",
+];
+
+/// Order of the numbers in a message date, which depends on the exporting phone's locale
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum DateOrder {
+    /// e.g. `31/01/2023`
+    DayMonthYear,
+    /// e.g. `1/31/23`
+    MonthDayYear,
+    /// e.g. `2023-01-31`
+    YearMonthDay,
+}
+
+/// Messages parsed from a chat export
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChatLog {
+    /// Date order the messages were read with
+    pub date_order: DateOrder,
+    pub messages: Vec<WhatsAppMessage>,
+}
+
+impl ChatLog {
+    /// Parse the text of a chat export, e.g. `_chat.txt`. The date order is detected from
+    /// the dates in the chat unless given. Timestamps are in the exporting phone's local
+    /// time and are read as UTC.
+    pub fn parse(text: &str, date_order: Option<DateOrder>) -> ChatLog {
+        let lines: Vec<(Option<Header>, &str)> = text
+            .lines()
+            .map(|line| {
+                let line = line.trim_start_matches(['\u{feff}', LRM]);
+                match parse_header(line) {
+                    Some((header, rest)) => (Some(header), rest),
+                    None => (None, line),
+                }
+            })
+            .collect();
+
+        let date_order = date_order.unwrap_or_else(|| {
+            detect_date_order(lines.iter().filter_map(|(header, _)| header.as_ref()))
+        });
+
+        let mut messages: Vec<WhatsAppMessage> = vec![];
+        for (header, rest) in lines {
+            match header.and_then(|header| header.timestamp(date_order)) {
+                Some(timestamp) => messages.push(WhatsAppMessage::new(timestamp, rest)),
+                None => {
+                    // Continuation of a multi-line message
+                    if let Some(message) = messages.last_mut() {
+                        // Captions of attachments start on the next line
+                        if !message.text.is_empty() {
+                            message.text.push('\n');
+                        }
+                        message.text.push_str(rest);
+                    }
+                }
+            }
+        }
+
+        ChatLog {
+            date_order,
+            messages,
+        }
+    }
+
+    /// Normalize into a conversation. Attachment paths are relative to the export folder.
+    pub fn to_conversation(&self, id: &str, title: Option<String>) -> Conversation {
+        let mut participants: Vec<String> = vec![];
+        for sender in self.messages.iter().filter_map(|m| m.sender.as_ref()) {
+            if !participants.contains(sender) {
+                participants.push(sender.clone());
+            }
+        }
+
+        Conversation {
+            id: id.to_owned(),
+            title,
+            participants,
+            messages: self
+                .messages
+                .iter()
+                .map(WhatsAppMessage::to_message)
+                .collect(),
+        }
+    }
+}
+
+impl ItemCount for ChatLog {
+    fn item_count(&self) -> usize {
+        self.messages.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct WhatsAppMessage {
+    pub timestamp: ArchiveTimestamp<Milliseconds>,
+    /// Missing for system messages, e.g. someone joining the group
+    pub sender: Option<String>,
+    pub text: String,
+    /// Names of attached files included in the export
+    pub attachments: Vec<String>,
+    /// An attachment was sent but left out of the export
+    pub media_omitted: bool,
+    pub is_deleted: bool,
+}
+
+impl WhatsAppMessage {
+    /// Read the first line of a message, after the timestamp
+    fn new(timestamp: ArchiveTimestamp<Milliseconds>, rest: &str) -> WhatsAppMessage {
+        let (sender, text) = match rest.split_once(": ") {
+            Some((sender, text)) if !sender.is_empty() => (Some(sender.to_owned()), text),
+            _ => (None, rest),
+        };
+
+        let mut message = WhatsAppMessage {
+            timestamp,
+            sender,
+            text: String::new(),
+            attachments: vec![],
+            media_omitted: false,
+            is_deleted: false,
+        };
+
+        // System messages on iOS are attributed to the chat, marked by a leading LRM
+        let is_system = text.starts_with(LRM);
+        let text = text.trim_start_matches(LRM);
+        let trimmed = text.trim_end_matches('.');
+
+        if let Some(name) = text
+            .strip_prefix("<attached: ")
+            .and_then(|s| s.strip_suffix('>'))
+        {
+            message.attachments.push(name.to_owned());
+        } else if let Some(name) = text.strip_suffix(" (file attached)") {
+            message.attachments.push(name.to_owned());
+        } else if OMITTED_MEDIA.contains(&text) {
+            message.media_omitted = true;
+        } else if trimmed == "This message was deleted" || trimmed == "You deleted this message" {
+            message.is_deleted = true;
+        } else {
+            if is_system {
+                message.sender = None;
+            }
+            message.text.push_str(text);
+        }
+
+        message
+    }
+
+    fn to_message(&self) -> ConversationMessage {
+        let mut message = ConversationMessage::new(
+            self.sender.clone().unwrap_or_default(),
+            Some(self.timestamp),
+        );
+        message.content = Some(self.text.clone()).filter(|text| !text.is_empty());
+        message.is_unsent = self.is_deleted;
+        if self.sender.is_none() {
+            message.kind = MessageKind::System;
+        }
+        message.attachments = self
+            .attachments
+            .iter()
+            .map(|name| Attachment {
+                kind: AttachmentKind::from_uri(name),
+                uri: name.clone(),
+                name: Some(name.clone()),
+                created_at: None,
+            })
+            .collect();
+        message
+    }
+}
+
+/// Date and time at the start of a message line, before the date order is known
+#[derive(Debug, Clone, Copy)]
+struct Header {
+    date: [u32; 3],
+    /// Whether the first number has four digits, i.e. is a year
+    year_first: bool,
+    hours: u32,
+    minutes: u32,
+    seconds: u32,
+    /// `Some(true)` for PM on a 12-hour clock
+    pm: Option<bool>,
+}
+
+impl Header {
+    fn timestamp(&self, order: DateOrder) -> Option<ArchiveTimestamp<Milliseconds>> {
+        let [a, b, c] = self.date;
+        let (year, month, day) = match order {
+            _ if self.year_first => (a, b, c),
+            DateOrder::YearMonthDay => (a, b, c),
+            DateOrder::DayMonthYear => (c, b, a),
+            DateOrder::MonthDayYear => (c, a, b),
+        };
+        let year = if year < 100 { year + 2000 } else { year };
+
+        let hours = match self.pm {
+            Some(true) if self.hours < 12 => self.hours + 12,
+            Some(false) if self.hours == 12 => 0,
+            _ => self.hours,
+        };
+
+        ArchiveTimestamp::parse_iso8601(&format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year, month, day, hours, self.minutes, self.seconds
+        ))
+    }
+}
+
+/// Split a line into its timestamp and the rest. iOS writes `[31/01/2023, 10:00:00] `,
+/// Android writes `31/01/2023, 10:00 - `, with the date and time formats of the locale.
+fn parse_header(line: &str) -> Option<(Header, &str)> {
+    let (stamp, rest) = match line.strip_prefix('[') {
+        Some(line) => {
+            let (stamp, rest) = line.split_once(']')?;
+            (stamp, rest.strip_prefix(' ').unwrap_or(rest))
+        }
+        None => line.split_once(" - ")?,
+    };
+
+    let stamp = stamp.replace(['\u{202f}', '\u{a0}'], " ");
+    let (date, time) = stamp.split_once(", ").or_else(|| stamp.split_once(' '))?;
+
+    let date: Vec<&str> = date
+        .trim_end_matches('.')
+        .split(['/', '.', '-'])
+        .map(str::trim)
+        .collect();
+    let [a, b, c] = date[..] else {
+        return None;
+    };
+    let year_first = a.len() == 4;
+    let date = [a.parse().ok()?, b.parse().ok()?, c.parse().ok()?];
+
+    // The clock may be followed by `AM`, `pm`, `a. m.` and similar
+    let time = time.trim();
+    let clock_end = time
+        .find(|c: char| !c.is_ascii_digit() && c != ':' && c != '.')
+        .unwrap_or(time.len());
+    let (clock, suffix) = time.split_at(clock_end);
+    let suffix: String = suffix
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .collect::<String>()
+        .to_lowercase();
+    let pm = match suffix.as_str() {
+        "" => None,
+        "am" => Some(false),
+        "pm" => Some(true),
+        _ => return None,
+    };
+
+    let mut clock = clock.trim_end_matches('.').split([':', '.']);
+    let hours = clock.next()?.parse().ok()?;
+    let minutes = clock.next()?.parse().ok()?;
+    let seconds = clock.next().map_or(Some(0), |s| s.parse().ok())?;
+    if clock.next().is_some() {
+        return None;
+    }
+
+    let header = Header {
+        date,
+        year_first,
+        hours,
+        minutes,
+        seconds,
+        pm,
+    };
+    Some((header, rest))
+}
+
+/// Guess the date order from every date in the chat. A day above 12 settles it,
+/// otherwise 12-hour clocks suggest a US locale.
+fn detect_date_order<'a>(headers: impl Iterator<Item = &'a Header>) -> DateOrder {
+    let mut twelve_hour = false;
+    for header in headers {
+        let [a, b, _] = header.date;
+        if header.year_first {
+            return DateOrder::YearMonthDay;
+        } else if a > 12 {
+            return DateOrder::DayMonthYear;
+        } else if b > 12 {
+            return DateOrder::MonthDayYear;
+        }
+        twelve_hour |= header.pm.is_some();
+    }
+
+    if twelve_hour {
+        DateOrder::MonthDayYear
+    } else {
+        DateOrder::DayMonthYear
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(line: &str) -> WhatsAppMessage {
+        let (header, rest) = parse_header(line).expect("header");
+        let timestamp = header
+            .timestamp(DateOrder::MonthDayYear)
+            .expect("timestamp");
+        WhatsAppMessage::new(timestamp, rest)
+    }
+
+    fn headers(lines: &[&str]) -> Vec<Header> {
+        lines
+            .iter()
+            .map(|line| parse_header(line).expect("header").0)
+            .collect()
+    }
+
+    #[test]
+    fn parses_android_and_ios_headers() {
+        let (header, rest) = parse_header("12/31/23, 9:16 PM - Bob: hi").unwrap();
+        assert_eq!(rest, "Bob: hi");
+        assert_eq!(
+            header.timestamp(DateOrder::MonthDayYear),
+            ArchiveTimestamp::parse_iso8601("2023-12-31T21:16:00")
+        );
+
+        let (header, rest) = parse_header("[31.01.23, 10:00:05] Bob: hi").unwrap();
+        assert_eq!(rest, "Bob: hi");
+        assert_eq!(
+            header.timestamp(DateOrder::DayMonthYear),
+            ArchiveTimestamp::parse_iso8601("2023-01-31T10:00:05")
+        );
+
+        let (header, _) = parse_header("2023-01-31 12:05\u{202f}a.\u{a0}m. - Bob: hi").unwrap();
+        assert!(header.year_first);
+        assert_eq!(
+            header.timestamp(DateOrder::DayMonthYear),
+            ArchiveTimestamp::parse_iso8601("2023-01-31T00:05:00")
+        );
+
+        assert!(parse_header("just a continuation line").is_none());
+        assert!(parse_header("12/31/23, 9:16 XM - Bob: hi").is_none());
+    }
+
+    #[test]
+    fn reads_message_text() {
+        let text = message("12/31/23, 9:16 PM - Bob: details omitted");
+        assert_eq!(text.sender.as_deref(), Some("Bob"));
+        assert_eq!(text.text, "details omitted");
+        assert!(!text.media_omitted);
+
+        for placeholder in ["<Media omitted>", "\u{200e}image omitted", "GIF omitted"] {
+            let omitted = message(&format!("12/31/23, 9:16 PM - Bob: {}", placeholder));
+            assert!(omitted.media_omitted);
+            assert_eq!(omitted.text, "");
+        }
+
+        let attached =
+            message("[12/31/23, 9:16:00 PM] Bob: \u{200e}<attached: 00000012-PHOTO.jpg>");
+        assert_eq!(attached.attachments, vec!["00000012-PHOTO.jpg"]);
+
+        let deleted = message("12/31/23, 9:16 PM - Bob: This message was deleted");
+        assert!(deleted.is_deleted);
+
+        let system = message("12/31/23, 9:16 PM - Bob joined using this group's invite link");
+        assert_eq!(system.sender, None);
+    }
+
+    #[test]
+    fn joins_continuation_lines() {
+        let log = ChatLog::parse(
+            "31/01/2023, 10:00 - Bob: first\nsecond\n31/01/2023, 10:01 - Ann: third",
+            None,
+        );
+        assert_eq!(log.date_order, DateOrder::DayMonthYear);
+        assert_eq!(log.messages.len(), 2);
+        assert_eq!(log.messages[0].text, "first\nsecond");
+        assert_eq!(log.messages[1].sender.as_deref(), Some("Ann"));
+    }
+
+    #[test]
+    fn detects_date_order() {
+        let detect = |lines: &[&str]| detect_date_order(headers(lines).iter());
+        assert_eq!(
+            detect(&["01/02/23, 10:00 - a: x", "31/01/23, 10:00 - a: x"]),
+            DateOrder::DayMonthYear
+        );
+        assert_eq!(
+            detect(&["01/02/23, 10:00 - a: x", "01/31/23, 10:00 - a: x"]),
+            DateOrder::MonthDayYear
+        );
+        assert_eq!(
+            detect(&["2023-01-02 10:00 - a: x"]),
+            DateOrder::YearMonthDay
+        );
+        assert_eq!(
+            detect(&["01/02/23, 10:00 AM - a: x"]),
+            DateOrder::MonthDayYear
+        );
+        assert_eq!(detect(&["01/02/23, 10:00 - a: x"]), DateOrder::DayMonthYear);
+        assert_eq!(detect(&[]), DateOrder::DayMonthYear);
+    }
+}
@@ -0,0 +1,169 @@
+//! WhatsApp chat export, made from a chat's menu with "Export chat".
+//!
+//! Each export is a single chat: a text file, `_chat.txt` on iOS or
+//! `WhatsApp Chat with <name>.txt` on Android, zipped together with the attached media.
+//! The text format depends on the phone's platform and locale, see [`ChatLog::parse`].
+//! Only English system messages are recognized as deleted messages.
+
+pub mod chat;
+
+use std::{
+    fs::File,
+    io::Read,
+    path::{absolute, Path},
+};
+
+use chat::{ChatLog, DateOrder};
+use serde::{Deserialize, Serialize};
+
+use crate::common::{
+    bundle::{for_each_zip_file, is_zip},
+    Archive, Conversation, Dataset, LoadOptions, ParseError, Sections,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct WhatsAppArchive {
+    /// Name of the other person or group, from the name of the export
+    pub title: Option<String>,
+    pub chat: Option<ChatLog>,
+    /// Names of the media files included in the export
+    pub media: Option<Vec<String>>,
+}
+
+impl Archive for WhatsAppArchive {
+    /// Load from the exported zip file, its extracted folder or the chat's text file,
+    /// detecting the date order. Only parses the sections selected in `options`.
+    fn load<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, ParseError> {
+        WhatsAppArchive::read(path.as_ref(), options, None)
+    }
+
+    fn to_dataset(&self) -> Dataset {
+        Dataset {
+            conversations: self.conversations(),
+            ..Dataset::new("whatsapp")
+        }
+    }
+}
+
+impl WhatsAppArchive {
+    /// Same as [`Archive::load`], reading dates in the given order instead of detecting
+    /// it. Useful for short chats where every day is 12 or lower.
+    pub fn load_with_date_order<P: AsRef<Path>>(
+        path: P,
+        options: &LoadOptions,
+        date_order: DateOrder,
+    ) -> Result<Self, ParseError> {
+        WhatsAppArchive::read(path.as_ref(), options, Some(date_order))
+    }
+
+    fn read(
+        path: &Path,
+        options: &LoadOptions,
+        date_order: Option<DateOrder>,
+    ) -> Result<Self, ParseError> {
+        let mut archive = WhatsAppArchive {
+            title: chat_title(path),
+            ..Default::default()
+        };
+
+        if is_zip(path) {
+            options.enter_section("WhatsAppArchive", path)?;
+            for_each_zip_file(path, |path, reader, bytes| {
+                archive.load_file(path, reader, bytes, options, date_order)
+            })?;
+        } else if path.is_dir() {
+            options.enter_section("WhatsAppArchive", path)?;
+            for entry in path.read_dir()? {
+                let entry = entry?;
+                let path = entry.path();
+                if !path.is_file() {
+                    options.warn(&path, "Skipped unsupported section");
+                    continue;
+                }
+
+                let bytes = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                archive.load_file(&path, File::open(&path)?, bytes, options, date_order)?;
+            }
+        } else if is_chat_file(path) {
+            options.enter_section("WhatsAppArchive", path)?;
+            let bytes = path.metadata().map(|m| m.len()).unwrap_or(0);
+            archive.load_file(path, File::open(path)?, bytes, options, date_order)?;
+        } else {
+            return Err(ParseError::UnexpectedFormat(format!(
+                "Expected a zip file, directory or chat text file, found: {:?}",
+                absolute(path)
+            )));
+        }
+
+        Ok(archive)
+    }
+
+    /// Parse the chat's text file, or record any other file as attached media
+    fn load_file<R: Read>(
+        &mut self,
+        path: &Path,
+        mut reader: R,
+        bytes: u64,
+        options: &LoadOptions,
+        date_order: Option<DateOrder>,
+    ) -> Result<(), ParseError> {
+        if is_chat_file(path) {
+            if self.title.is_none() {
+                self.title = chat_title(path);
+            }
+            if options.should_load(Sections::MESSAGES, path) {
+                self.chat = Some(options.load_with(path, bytes, || {
+                    let mut text = String::new();
+                    reader.read_to_string(&mut text)?;
+                    Ok(ChatLog::parse(&text, date_order))
+                })?);
+            }
+        } else if options.should_load(Sections::MEDIA, path) {
+            if let Some(name) = path.file_name().and_then(|s| s.to_str()) {
+                self.media
+                    .get_or_insert_with(Vec::new)
+                    .push(name.to_owned());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The chat as a normalized conversation, identified by its title. Attachment paths
+    /// are relative to the export folder.
+    pub fn conversations(&self) -> Vec<Conversation> {
+        let id = self.title.as_deref().unwrap_or("WhatsApp Chat");
+        self.chat
+            .iter()
+            .map(|chat| chat.to_conversation(id, self.title.clone()))
+            .collect()
+    }
+}
+
+impl TryFrom<&Path> for WhatsAppArchive {
+    type Error = ParseError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        WhatsAppArchive::load(path, &LoadOptions::default())
+    }
+}
+
+/// Whether a file is the chat's text, `_chat.txt` or `WhatsApp Chat with <name>.txt`
+fn is_chat_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|s| s.to_str())
+        .is_some_and(|name| {
+            name == "_chat.txt" || (name.starts_with("WhatsApp Chat") && name.ends_with(".txt"))
+        })
+}
+
+/// Chat name from an export named like `WhatsApp Chat - Jane.zip` or
+/// `WhatsApp Chat with Jane.txt`
+fn chat_title(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    let title = stem
+        .strip_prefix("WhatsApp Chat with ")
+        .or_else(|| stem.strip_prefix("WhatsApp Chat - "))?;
+    Some(title.to_owned())
+}
@@ -0,0 +1,21 @@
+"""Find out who doesn't follow you back on Instagram."""
+
+import social_parser
+
+# Only load connections, skipping messages and the rest of the archive
+archive = social_parser.load_instagram("./data/instagram", sections=["connections"])
+
+fnf = archive.connections.followers_and_following
+followers = {
+    data["href"] for relationship in fnf["followers"] for data in relationship["string_list_data"]
+}
+following = [
+    data
+    for relationship in fnf["following"]["relationships_following"]
+    for data in relationship["string_list_data"]
+]
+
+not_following_back = [data for data in following if data["href"] not in followers]
+for data in not_following_back:
+    print(f"{data.get('value') or '???'}: {data['href']}")
+print(f"\n{len(not_following_back)} don't follow you back")
@@ -1,11 +1,11 @@
 //! Find out who doesn't follow you back on Instagram.
 
 use color_eyre::eyre::{OptionExt, Result};
-use social_parser::platforms::meta::instagram::InstagramArchive;
-use std::{
-    collections::HashMap,
-    path,
+use social_parser::{
+    common::{Archive, LoadOptions, Sections},
+    platforms::meta::instagram::InstagramArchive,
 };
+use std::{collections::HashMap, path};
 
 // See who doesn't follow you back
 fn main() -> Result<()> {
@@ -14,8 +14,9 @@ fn main() -> Result<()> {
     let path = path::absolute("./data/instagram")?;
     println!("Parsing at: {path:?}");
 
-    // Load Instagram Archive
-    let archive = InstagramArchive::try_from(path.as_path())?;
+    // Load Instagram Archive, skipping everything but connections
+    let options = LoadOptions::new().with_sections(Sections::CONNECTIONS);
+    let archive = InstagramArchive::load(&path, &options)?;
     // println!("{:#?}", archive);
 
     let fnf = archive
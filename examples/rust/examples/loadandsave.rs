@@ -1,7 +1,7 @@
 //! Test load and save an Instagram archive using the social_parser crate.
 
 use color_eyre::eyre::Result;
-use social_parser::platforms::meta::instagram::InstagramArchive;
+use social_parser::{common::Archive, platforms::meta::instagram::InstagramArchive};
 use std::path;
 
 fn main() -> Result<()> {
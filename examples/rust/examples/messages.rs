@@ -69,15 +69,13 @@ fn main() -> Result<()> {
         messages.sort_by_key(|x| x.1);
 
         for message in messages.iter() {
-            let (sender_name, timestamp_ms, text) = message;
-            // Convert milliseconds to seconds and nanoseconds
-            let seconds = timestamp_ms / 1000;
-            let nanoseconds = (timestamp_ms % 1000) * 1_000_000;
+            let (sender_name, timestamp, text) = message;
 
             // Create a DateTime<Local> from timestamp
-            let date_time: DateTime<Local> = Local
-                .timestamp_opt(seconds.try_into().unwrap(), nanoseconds as u32)
-                .unwrap();
+            let date_time: DateTime<Local> = timestamp
+                .to_chrono()
+                .ok_or_eyre("Timestamp out of range")?
+                .with_timezone(&Local);
             println!(
                 "{} [{}]: {}",
                 sender_name,
@@ -1,3 +1,26 @@
-import { greeting } from "social-parser";
+import { loadInstagramArchiveAsync } from "social-parser";
 
-console.log(greeting().message); // TODO: Update
+// See who doesn't follow you back
+const archive = await loadInstagramArchiveAsync("./data/instagram", {
+    sections: ["connections"],
+    onProgress: (event) => {
+        if (event.kind === "file_finished") {
+            console.log(`Parsed ${event.path} (${event.items} items)`);
+        }
+    },
+});
+
+const fnf = archive.connections?.followers_n_following;
+const followers = new Set(
+    (fnf?.followers ?? [])
+        .flatMap((r) => r.string_list_data)
+        .map((s) => s.href),
+);
+const notFollowingBack = (fnf?.following?.relationships_following ?? [])
+    .flatMap((r) => r.string_list_data)
+    .filter((s) => !followers.has(s.href));
+
+for (const { href, value } of notFollowingBack) {
+    console.log(`${value ?? "???"}: ${href}`);
+}
+console.log(`\n${notFollowingBack.length} don't follow you back`);
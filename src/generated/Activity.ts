@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Comments } from "./Comments.js";
+import type { Likes } from "./Likes.js";
+import type { Messages } from "./Messages.js";
+import type { Saved } from "./Saved.js";
+import type { Threads } from "./Threads.js";
+
+export type Activity = { comments: Comments | null, likes: Likes | null, messages: Messages | null, saved: Saved | null, threads: Threads | null, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LinkTimeValueData } from "./LinkTimeValueData.js";
+
+export type BlockedAccount = { title: string, string_list_data: Array<LinkTimeValueData>, };
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PostComments } from "./PostComments.js";
+import type { ReelComments } from "./ReelComments.js";
+
+export type Comments = { post_comments: PostComments | null, reel_comments: ReelComments | null, };
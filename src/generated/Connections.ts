@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Contacts } from "./Contacts.js";
+import type { FollowersNFollowing } from "./FollowersNFollowing.js";
+
+export type Connections = { contacts: Contacts | null, followers_n_following: FollowersNFollowing | null, };
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ContactStringMapData } from "./ContactStringMapData.js";
+import type { MediaMapData } from "./MediaMapData.js";
+
+export type ContactInfo = { title: string, media_map_data: MediaMapData, string_map_data: ContactStringMapData, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LinkTimeValueData } from "./LinkTimeValueData.js";
+
+export type ContactStringMapData = { "First Name": LinkTimeValueData, "Last Name": LinkTimeValueData, "Contact Information": LinkTimeValueData, "Imported Time": LinkTimeValueData, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FavouriteAccount } from "./FavouriteAccount.js";
+
+export type FavouriteAccounts = { relationships_feed_favorites: Array<FavouriteAccount>, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BlockedAccounts } from "./BlockedAccounts.js";
+import type { CloseFriends } from "./CloseFriends.js";
+import type { FavouriteAccounts } from "./FavouriteAccounts.js";
+import type { Followers } from "./Followers.js";
+import type { Following } from "./Following.js";
+import type { HideStoryFrom } from "./HideStoryFrom.js";
+import type { PendingFollowRequests } from "./PendingFollowRequests.js";
+import type { RecentFollowRequests } from "./RecentFollowRequests.js";
+import type { RecentlyUnfollowed } from "./RecentlyUnfollowed.js";
+import type { RemovedSuggestions } from "./RemovedSuggestions.js";
+import type { RestrictedAccounts } from "./RestrictedAccounts.js";
+
+export type FollowersNFollowing = { favourite_accounts: FavouriteAccounts | null, blocked_accounts: BlockedAccounts | null, close_friends: CloseFriends | null, followers: Followers | null, following: Following | null, hide_story_from: HideStoryFrom | null, pending_follow_requests: PendingFollowRequests | null, recent_follow_requests: RecentFollowRequests | null, recently_unfollowed: RecentlyUnfollowed | null, removed_suggestions: RemovedSuggestions | null, restricted_accounts: RestrictedAccounts | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JoinableMode } from "./JoinableMode.js";
+import type { MediaUri } from "./MediaUri.js";
+import type { Message } from "./Message.js";
+import type { Participant } from "./Participant.js";
+
+export type InboxData = { participants: Array<Participant>, messages: Array<Message>, title: string, is_still_participant: boolean, thread_path: string, magic_words: Array<null>, image: MediaUri | null, joinable_mode: JoinableMode | null, };
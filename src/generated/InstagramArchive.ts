@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Activity } from "./Activity.js";
+import type { Connections } from "./Connections.js";
+import type { Media } from "./Media.js";
+
+export type InstagramArchive = { connections: Connections | null, media: Media | null, activity: Activity | null, };
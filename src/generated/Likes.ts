@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LikedComments } from "./LikedComments.js";
+import type { LikedPosts } from "./LikedPosts.js";
+
+export type Likes = { liked_comments: LikedComments | null, liked_posts: LikedPosts | null, };
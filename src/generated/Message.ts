@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MediaUri } from "./MediaUri.js";
+import type { Reaction } from "./Reaction.js";
+import type { Share } from "./Share.js";
+
+export type Message = { sender_name: string, timestamp_ms: number, content: string | null, photos: Array<MediaUri> | null, videos: Array<MediaUri> | null, gifs: Array<MediaUri> | null, files: Array<MediaUri> | null, audio_files: Array<MediaUri> | null, share: Share | null, call_duration: number | null, is_unsent: boolean | null, is_geoblocked_for_viewer: boolean, is_unsent_image_by_messenger_kid_parent: boolean | null, reactions: Array<Reaction> | null, };
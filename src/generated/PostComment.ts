@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CommentData } from "./CommentData.js";
+import type { MediaUri } from "./MediaUri.js";
+
+export type PostComment = { media_list_data: Array<MediaUri>, string_map_data: CommentData, };
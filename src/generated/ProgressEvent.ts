@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Event reported while an archive is being loaded
+ */
+export type ProgressEvent = { "kind": "enter_section", name: string, path: string, } | { "kind": "file_started", path: string, } | { "kind": "file_finished", path: string, bytes: number, items: number, } | { "kind": "warning", path: string, message: string, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Reaction = { 
+/**
+ * Emoji reaction in unicode
+ */
+reaction: string, 
+/**
+ * User who reacted
+ */
+actor: string, 
+/**
+ * Timestamp of the reaction in seconds
+ */
+timestamp: number | null, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CommentData } from "./CommentData.js";
+
+export type ReelComment = { string_map_data: CommentData, };
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SavedCollections } from "./SavedCollections.js";
+import type { SavedPosts } from "./SavedPosts.js";
+
+export type Saved = { saved_collections: SavedCollections | null, saved_posts: SavedPosts | null, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SavedCollectionAlbumData } from "./SavedCollectionAlbumData.js";
+
+export type SavedCollectionAlbum = { title: string, string_map_data: SavedCollectionAlbumData, };
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Timestamp } from "./Timestamp.js";
+import type { Value } from "./Value.js";
+
+export type SavedCollectionAlbumData = { Name: Value, "Creation Time": Timestamp, "Update Time": Timestamp, };
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SavedCollectionAlbum } from "./SavedCollectionAlbum.js";
+import type { SavedCollectionEntry } from "./SavedCollectionEntry.js";
+
+export type SavedCollectionData = SavedCollectionAlbum | SavedCollectionEntry;
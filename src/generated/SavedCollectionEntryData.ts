@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LinkData } from "./LinkData.js";
+import type { Timestamp } from "./Timestamp.js";
+
+export type SavedCollectionEntryData = { Name: LinkData, "Added Time": Timestamp, };
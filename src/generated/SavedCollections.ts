@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SavedCollectionData } from "./SavedCollectionData.js";
+
+export type SavedCollections = { saved_saved_collections: Array<SavedCollectionData>, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SavedContent } from "./SavedContent.js";
+
+export type SavedPosts = { saved_saved_media: Array<SavedContent>, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Share = { link: string | null, share_text: string | null, original_content_owner: string | null, profile_share_username: string | null, profile_share_name: string | null, };
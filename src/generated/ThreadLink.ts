@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LinkTimeValueData } from "./LinkTimeValueData.js";
+
+export type ThreadLink = { 
+/**
+ * Author of the post, empty for followers and following
+ */
+title: string | null, string_list_data: Array<LinkTimeValueData>, };
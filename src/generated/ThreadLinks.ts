@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadLink } from "./ThreadLink.js";
+
+/**
+ * Reposts, likes, followers or following. Each entry links to a post or profile.
+ */
+export type ThreadLinks = Array<ThreadLink>;
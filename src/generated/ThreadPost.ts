@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadMedia } from "./ThreadMedia.js";
+
+/**
+ * Text-only posts are exported as a single media entry with an empty `uri`, carrying
+ * the text in its `title`
+ */
+export type ThreadPost = { media: Array<ThreadMedia>, title: string | null, creation_timestamp: number | null, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadPost } from "./ThreadPost.js";
+
+/**
+ * Threads posted by the account, from `threads_and_replies.json`, or the replies and
+ * quotes files in newer exports
+ */
+export type ThreadPosts = Array<ThreadPost>;
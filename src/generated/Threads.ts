@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadLinks } from "./ThreadLinks.js";
+import type { ThreadPosts } from "./ThreadPosts.js";
+
+export type Threads = { posts: ThreadPosts | null, replies: ThreadPosts | null, quotes: ThreadPosts | null, reposts: ThreadLinks | null, likes: ThreadLinks | null, followers: ThreadLinks | null, following: ThreadLinks | null, };
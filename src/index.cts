@@ -3,18 +3,113 @@
 // The Rust addon.
 import * as addon from "./load.cjs";
 
+import type { InstagramArchive } from "./generated/InstagramArchive.js";
+import type { ProgressEvent } from "./generated/ProgressEvent.js";
+
+export type * from "./generated/InstagramArchive.js";
+export type * from "./generated/ProgressEvent.js";
+
+/** Opaque handle to a cancellation token owned by the Rust addon. */
+declare const tokenBrand: unique symbol;
+type TokenHandle = { readonly [tokenBrand]: never };
+
+type AddonLoadOptions = {
+    sections?: Section[];
+    onProgress?: (event: ProgressEvent) => void;
+    cancellation?: TokenHandle;
+};
+
 // Use this declaration to assign types to the addon's exports,
 // which otherwise by default are `any`.
 declare module "./load.cjs" {
-    function hello(): string;
+    function setLogger(
+        callback: ((record: LogRecord) => void) | null,
+        level?: LogLevel,
+    ): void;
+    function createCancellationToken(): TokenHandle;
+    function cancel(token: TokenHandle): void;
+    function loadInstagramArchive(
+        path: string,
+        options?: AddonLoadOptions,
+    ): InstagramArchive;
+    function loadInstagramArchiveAsync(
+        path: string,
+        options?: AddonLoadOptions,
+    ): Promise<InstagramArchive>;
 }
 
-// TODO: Implement JS bindings here
+export type LogLevel = "off" | "error" | "warn" | "info" | "debug" | "trace";
 
-export type Greeting = {
+export type LogRecord = {
+    level: Exclude<LogLevel, "off">;
+    /** Rust module that emitted the record */
+    target: string;
     message: string;
 };
 
-export function greeting(): Greeting {
-    return { message: "hello" };
+/**
+ * Forward the library's log records at or above `level` (default "info") to
+ * `callback`, e.g. `setLogger((r) => console.log(r.message))`. Passing `null`
+ * stops forwarding.
+ */
+export function setLogger(
+    callback: ((record: LogRecord) => void) | null,
+    level?: LogLevel,
+): void {
+    addon.setLogger(callback, level);
+}
+
+/** Sections of an archive that can be selected for loading. */
+export type Section =
+    | "contacts"
+    | "followers_and_following"
+    | "connections"
+    | "media"
+    | "comments"
+    | "likes"
+    | "messages"
+    | "saved"
+    | "threads"
+    | "activity";
+
+export type LoadOptions = {
+    /** Only load the given sections. Defaults to every section. */
+    sections?: Section[];
+    /** Called for every progress event. Events are delivered asynchronously. */
+    onProgress?: (event: ProgressEvent) => void;
+    /** Stops loading with an `ERR_CANCELLED` error once aborted. */
+    signal?: AbortSignal;
+};
+
+function toAddonOptions(options: LoadOptions): AddonLoadOptions {
+    const { signal, ...rest } = options;
+    if (!signal) {
+        return rest;
+    }
+
+    const cancellation = addon.createCancellationToken();
+    if (signal.aborted) {
+        addon.cancel(cancellation);
+    } else {
+        signal.addEventListener("abort", () => addon.cancel(cancellation), {
+            once: true,
+        });
+    }
+    return { ...rest, cancellation };
+}
+
+/** Load an Instagram archive directory, blocking until it is parsed. */
+export function loadInstagramArchive(
+    path: string,
+    options: LoadOptions = {},
+): InstagramArchive {
+    return addon.loadInstagramArchive(path, toAddonOptions(options));
+}
+
+/** Load an Instagram archive directory off the main thread. */
+export function loadInstagramArchiveAsync(
+    path: string,
+    options: LoadOptions = {},
+): Promise<InstagramArchive> {
+    return addon.loadInstagramArchiveAsync(path, toAddonOptions(options));
 }